@@ -1,5 +1,6 @@
 use clap::ValueHint;
 use clap::{Parser, Subcommand};
+use ecitygml::io::validate::ReportFormat;
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -13,19 +14,57 @@ pub struct Cli {
 pub enum Commands {
     /// Compute some statistics about the dataset
     Statistics {
-        /// Input directory
+        /// Input file, directory or `-` for stdin
         #[clap(short, long, value_hint = ValueHint::FilePath)]
         file_path: PathBuf,
     },
 
-    /// Validate the dataset
-    Validate {
-        /// Input directory
+    /// Compute footprint, roof and wall areas, volume and height per city object as CSV
+    Metrics {
+        /// Input file, directory or `-` for stdin
         #[clap(short, long, value_hint = ValueHint::FilePath)]
         file_path: PathBuf,
 
+        /// Output CSV file
+        #[clap(short, long, value_hint = ValueHint::FilePath)]
+        output_file_path: PathBuf,
+    },
+
+    /// Validate the dataset
+    Validate {
+        /// Input files, directories or zip archives, or `-` for stdin
+        ///
+        /// References between the documents of multiple inputs are resolved across files.
+        #[clap(short, long, num_args = 1.., required = true, value_hint = ValueHint::AnyPath)]
+        file_path: Vec<PathBuf>,
+
         /// Output directory
         #[clap(short, long, value_hint = ValueHint::DirPath)]
         output_directory_path: PathBuf,
+
+        /// Check the well-formedness and the structure of GML geometries, the city model root and
+        /// generic attributes (no XSD validation)
+        #[clap(long)]
+        structure: bool,
+
+        /// Check polygons and solids following the val3dity error codes
+        #[clap(long)]
+        geometry: bool,
+
+        /// Check the semantic consistency of building boundary surfaces
+        #[clap(long)]
+        semantic: bool,
+
+        /// Check the predecessor and successor links between traffic spaces of roads
+        #[clap(long)]
+        topology: bool,
+
+        /// Validation profile selecting the rules with their severities and parameters
+        #[clap(long, value_hint = ValueHint::FilePath)]
+        profile: Option<PathBuf>,
+
+        /// Formats of the report: yaml, json, junit, html or sarif
+        #[clap(long, value_delimiter = ',', default_value = "yaml")]
+        format: Vec<ReportFormat>,
     },
 }
@@ -0,0 +1,131 @@
+use crate::STDIN_PATH;
+use crate::error::Error;
+use ecitygml::io::{CitygmlDatasetReader, CitygmlReader};
+use ecitygml::transform::metrics::{CityObjectMetrics, compute_metrics};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use tracing::info;
+
+const CSV_HEADER: &str = "gml_id,class,footprint_area,roof_area,roof_area_flat,roof_area_north,roof_area_east,roof_area_south,roof_area_west,wall_area,volume,height";
+
+pub fn run(path: impl AsRef<Path>, output_file_path: impl AsRef<Path>) -> Result<(), Error> {
+    info!("Computing metrics for: {}", path.as_ref().display());
+
+    let citygml_model = if path.as_ref() == Path::new(STDIN_PATH) {
+        CitygmlReader::new(std::io::stdin().lock()).finish()?
+    } else {
+        CitygmlDatasetReader::from_path(path)?.finish()?
+    };
+    let metrics = compute_metrics(&citygml_model);
+
+    let mut writer = BufWriter::new(File::create(output_file_path.as_ref())?);
+    write_csv(&mut writer, &metrics)?;
+    writer.flush()?;
+    info!(
+        "Wrote metrics of {} city objects to: {}",
+        metrics.len(),
+        output_file_path.as_ref().display()
+    );
+
+    Ok(())
+}
+
+fn write_csv(writer: &mut impl Write, metrics: &[CityObjectMetrics]) -> Result<(), Error> {
+    writeln!(writer, "{CSV_HEADER}")?;
+    for city_object_metrics in metrics {
+        let roof_area = city_object_metrics.roof_area;
+        let values = [
+            city_object_metrics.footprint_area,
+            roof_area.map(|x| x.total()),
+            roof_area.map(|x| x.flat),
+            roof_area.map(|x| x.north),
+            roof_area.map(|x| x.east),
+            roof_area.map(|x| x.south),
+            roof_area.map(|x| x.west),
+            city_object_metrics.wall_area,
+            city_object_metrics.volume,
+            city_object_metrics.height,
+        ]
+        .into_iter()
+        .map(|x| x.map(|x| format!("{x:.3}")).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(",");
+
+        writeln!(
+            writer,
+            "{},{},{values}",
+            escape_csv(&city_object_metrics.gml_id),
+            city_object_metrics.class
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Quotes the field, if it contains a separator, quote or line break.
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ecitygml::common::CityObjectClass;
+    use ecitygml::transform::metrics::OrientedArea;
+
+    #[test]
+    fn test_escape_csv() {
+        assert_eq!(escape_csv("building_1"), "building_1");
+        assert_eq!(escape_csv("b,1"), "\"b,1\"");
+        assert_eq!(escape_csv("b\"1"), "\"b\"\"1\"");
+        assert_eq!(escape_csv("b\n1"), "\"b\n1\"");
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let metrics = vec![
+            CityObjectMetrics {
+                gml_id: "b,1".to_string(),
+                class: CityObjectClass::Building,
+                footprint_area: Some(80.0),
+                roof_area: Some(OrientedArea {
+                    flat: 0.0,
+                    north: 50.0,
+                    east: 0.0,
+                    south: 50.0,
+                    west: 0.0,
+                }),
+                wall_area: Some(90.0),
+                volume: Some(400.0),
+                height: Some(8.0),
+            },
+            CityObjectMetrics {
+                gml_id: "bench".to_string(),
+                class: CityObjectClass::CityFurniture,
+                footprint_area: None,
+                roof_area: None,
+                wall_area: None,
+                volume: Some(0.8),
+                height: Some(0.8),
+            },
+        ];
+
+        let mut output: Vec<u8> = Vec::new();
+        write_csv(&mut output, &metrics).expect("should work");
+
+        let output = String::from_utf8(output).expect("should work");
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(
+            lines[1],
+            "\"b,1\",Building,80.000,100.000,0.000,50.000,0.000,50.000,0.000,90.000,400.000,8.000"
+        );
+        assert_eq!(lines[2], "bench,CityFurniture,,,,,,,,,0.800,0.800");
+        assert_eq!(lines.len(), 3);
+    }
+}
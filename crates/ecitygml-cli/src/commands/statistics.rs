@@ -1,38 +1,56 @@
+use crate::STDIN_PATH;
 use crate::error::Error;
-use ecitygml::io::{FILE_EXTENSION_CITYGML_GML_FORMAT, FILE_EXTENSION_CITYGML_XML_FORMAT};
+use ecitygml::io::{
+    CitygmlDatasetReader, CitygmlReader, ReadProgress, ReadStage, is_citygml_document_path,
+};
 use ecitygml::model::building::{Building, BuildingConstructiveElement};
+use ecitygml::model::city_model::CitygmlModel;
 use ecitygml::model::construction::{
-    DoorSurface, GroundSurface, RoofSurface, WallSurface, WindowSurface,
+    CeilingSurface, ClosureSurface, Door, DoorSurface, FloorSurface, GroundSurface,
+    InteriorWallSurface, OuterCeilingSurface, OuterFloorSurface, RoofSurface, WallSurface, Window,
+    WindowSurface,
 };
 use ecitygml::model::core::{OccupiedSpace, Space, ThematicSurface};
 use ecitygml::operations::FeatureWithGeometry;
+use indicatif::{ProgressBar, ProgressStyle};
 use std::path::Path;
 use std::time::Instant;
 use tracing::info;
 use walkdir::WalkDir;
 
+const PROGRESS_BAR_LENGTH: u64 = 1000;
+
 pub fn run(path: impl AsRef<Path>) -> Result<(), Error> {
     info!("Creating statistics for: {}", path.as_ref().display());
 
-    if path.as_ref().is_file() {
-        print_citygml_model_statistics(path)?;
+    if path.as_ref() == Path::new(STDIN_PATH) {
+        let now = Instant::now();
+        let progress_bar = create_progress_bar();
+        let citygml_model = CitygmlReader::new(std::io::stdin().lock())
+            .with_progress_callback(update_progress_bar(&progress_bar))
+            .finish()?;
+        progress_bar.finish_and_clear();
+        info!("Read model in {:.3?}", now.elapsed());
+        print_citygml_model_statistics(&citygml_model)?;
+    } else if path.as_ref().is_file() {
+        let now = Instant::now();
+        let progress_bar = create_progress_bar();
+        let citygml_model = CitygmlDatasetReader::from_path(path)?
+            .with_progress_callback(update_progress_bar(&progress_bar))
+            .finish()?;
+        progress_bar.finish_and_clear();
+        info!("Read model in {:.3?}", now.elapsed());
+        print_citygml_model_statistics(&citygml_model)?;
     } else if path.as_ref().is_dir() {
         for entry in WalkDir::new(path)
             .sort_by(|a, b| a.file_name().cmp(b.file_name()))
             .into_iter()
             .filter_map(Result::ok)
-            .filter(|e| e.file_type().is_file() && e.path().extension().is_some())
-            .filter(|e| {
-                e.path().extension().expect("must have an extension")
-                    == FILE_EXTENSION_CITYGML_GML_FORMAT
-                    || e.path().extension().expect("must have an extension")
-                        == FILE_EXTENSION_CITYGML_XML_FORMAT
-            })
+            .filter(|e| e.file_type().is_file() && is_citygml_document_path(e.path()))
         {
             info!("Start reading: {:?}", entry);
             let now = Instant::now();
-            let citygml_model =
-                ecitygml::io::CitygmlReader::from_path(entry.into_path())?.finish()?;
+            let _citygml_model = CitygmlReader::from_path(entry.into_path())?.finish()?;
             info!("Read model in {:.3?}", now.elapsed());
         }
     }
@@ -40,11 +58,33 @@ pub fn run(path: impl AsRef<Path>) -> Result<(), Error> {
     Ok(())
 }
 
-fn print_citygml_model_statistics(file_path: impl AsRef<Path>) -> Result<(), Error> {
-    let now = Instant::now();
-    let citygml_model = ecitygml::io::CitygmlReader::from_path(file_path)?.finish()?;
-    info!("Read model in {:.3?}", now.elapsed());
+fn create_progress_bar() -> ProgressBar {
+    let progress_bar = ProgressBar::new(PROGRESS_BAR_LENGTH);
+    progress_bar.set_style(
+        ProgressStyle::with_template("{spinner} [{elapsed_precise}] [{wide_bar}] {percent}% {msg}")
+            .expect("template should be valid"),
+    );
+    progress_bar
+}
 
+fn update_progress_bar(progress_bar: &ProgressBar) -> impl Fn(&ReadProgress) + use<> {
+    let progress_bar = progress_bar.clone();
+    move |progress: &ReadProgress| {
+        progress_bar.set_position((progress.fraction() * PROGRESS_BAR_LENGTH as f64) as u64);
+        progress_bar.set_message(match progress.stage {
+            ReadStage::Buffering => format!(
+                "{:.1} MB buffered",
+                progress.bytes_consumed as f64 / (1024.0 * 1024.0)
+            ),
+            ReadStage::Parsing => format!(
+                "{}/{} features",
+                progress.features_parsed, progress.features_total
+            ),
+        });
+    }
+}
+
+fn print_citygml_model_statistics(citygml_model: &CitygmlModel) -> Result<(), Error> {
     info!(
         "Number of city objects: {}\n",
         citygml_model.number_of_objects()
@@ -84,7 +124,9 @@ fn print_citygml_model_statistics(file_path: impl AsRef<Path>) -> Result<(), Err
         .collect();
     info!("Total DoorSurface: {}", door_surfaces.len());
     if !door_surfaces.is_empty() {
-        print_statistics_occupied_space(door_surfaces.iter().map(|x| &x.occupied_space).collect())?;
+        print_statistics_thematic_surface(
+            door_surfaces.iter().map(|x| &x.thematic_surface).collect(),
+        )?;
     }
 
     let window_surfaces: Vec<&WindowSurface> = citygml_model
@@ -95,8 +137,11 @@ fn print_citygml_model_statistics(file_path: impl AsRef<Path>) -> Result<(), Err
         .collect();
     info!("Total WindowSurface: {}", window_surfaces.len());
     if !window_surfaces.is_empty() {
-        print_statistics_occupied_space(
-            window_surfaces.iter().map(|x| &x.occupied_space).collect(),
+        print_statistics_thematic_surface(
+            window_surfaces
+                .iter()
+                .map(|x| &x.thematic_surface)
+                .collect(),
         )?;
     }
 
@@ -127,6 +172,99 @@ fn print_citygml_model_statistics(file_path: impl AsRef<Path>) -> Result<(), Err
         )?;
     }
 
+    let ceiling_surfaces: Vec<&CeilingSurface> = citygml_model
+        .building
+        .iter()
+        .flat_map(|x| &x.ceiling_surface)
+        .collect();
+    info!("Total CeilingSurface: {}", ceiling_surfaces.len());
+    if !ceiling_surfaces.is_empty() {
+        print_statistics_thematic_surface(
+            ceiling_surfaces
+                .iter()
+                .map(|x| &x.thematic_surface)
+                .collect(),
+        )?;
+    }
+
+    let floor_surfaces: Vec<&FloorSurface> = citygml_model
+        .building
+        .iter()
+        .flat_map(|x| &x.floor_surface)
+        .collect();
+    info!("Total FloorSurface: {}", floor_surfaces.len());
+    if !floor_surfaces.is_empty() {
+        print_statistics_thematic_surface(
+            floor_surfaces.iter().map(|x| &x.thematic_surface).collect(),
+        )?;
+    }
+
+    let interior_wall_surfaces: Vec<&InteriorWallSurface> = citygml_model
+        .building
+        .iter()
+        .flat_map(|x| &x.interior_wall_surface)
+        .collect();
+    info!(
+        "Total InteriorWallSurface: {}",
+        interior_wall_surfaces.len()
+    );
+    if !interior_wall_surfaces.is_empty() {
+        print_statistics_thematic_surface(
+            interior_wall_surfaces
+                .iter()
+                .map(|x| &x.thematic_surface)
+                .collect(),
+        )?;
+    }
+
+    let outer_ceiling_surfaces: Vec<&OuterCeilingSurface> = citygml_model
+        .building
+        .iter()
+        .flat_map(|x| &x.outer_ceiling_surface)
+        .collect();
+    info!(
+        "Total OuterCeilingSurface: {}",
+        outer_ceiling_surfaces.len()
+    );
+    if !outer_ceiling_surfaces.is_empty() {
+        print_statistics_thematic_surface(
+            outer_ceiling_surfaces
+                .iter()
+                .map(|x| &x.thematic_surface)
+                .collect(),
+        )?;
+    }
+
+    let outer_floor_surfaces: Vec<&OuterFloorSurface> = citygml_model
+        .building
+        .iter()
+        .flat_map(|x| &x.outer_floor_surface)
+        .collect();
+    info!("Total OuterFloorSurface: {}", outer_floor_surfaces.len());
+    if !outer_floor_surfaces.is_empty() {
+        print_statistics_thematic_surface(
+            outer_floor_surfaces
+                .iter()
+                .map(|x| &x.thematic_surface)
+                .collect(),
+        )?;
+    }
+
+    let closure_surfaces: Vec<&ClosureSurface> = citygml_model
+        .building
+        .iter()
+        .flat_map(|x| &x.closure_surface)
+        .collect();
+    info!("Total ClosureSurface: {}", closure_surfaces.len());
+    if !closure_surfaces.is_empty() {
+        print_statistics_thematic_surface(
+            closure_surfaces
+                .iter()
+                .map(|x| &x.thematic_surface)
+                .collect(),
+        )?;
+    }
+
     let building_constructive_elements: Vec<&BuildingConstructiveElement> = citygml_model
         .building
         .iter()
@@ -145,6 +283,24 @@ fn print_citygml_model_statistics(file_path: impl AsRef<Path>) -> Result<(), Err
         )?;
     }
 
+    let doors: Vec<&Door> = building_constructive_elements
+        .iter()
+        .flat_map(|x| &x.door)
+        .collect();
+    info!("Total Door: {}", doors.len());
+    if !doors.is_empty() {
+        print_statistics_occupied_space(doors.iter().map(|x| &x.occupied_space).collect())?;
+    }
+
+    let windows: Vec<&Window> = building_constructive_elements
+        .iter()
+        .flat_map(|x| &x.window)
+        .collect();
+    info!("Total Window: {}", windows.len());
+    if !windows.is_empty() {
+        print_statistics_occupied_space(windows.iter().map(|x| &x.occupied_space).collect())?;
+    }
+
     info!(
         "Total CityFurniture: {}",
         citygml_model.city_furniture.len()
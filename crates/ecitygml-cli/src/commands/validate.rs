@@ -1,24 +1,87 @@
+use crate::STDIN_PATH;
 use crate::error::Error;
-use ecitygml::io::CitygmlReader;
+use ecitygml::io::validate::{Report, ReportFormat, Severity, ValidateOptions, ValidationProfile};
+use ecitygml::io::{CitygmlDatasetReader, CitygmlReader, is_citygml_document_path};
+use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
-use tracing::info;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Checks enabled in addition to the ones of the validation profile.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Checks {
+    pub structure: bool,
+    pub geometry: bool,
+    pub semantic: bool,
+    pub topology: bool,
+}
 
 pub fn run(
-    file_path: impl AsRef<Path>,
+    file_paths: &[PathBuf],
     output_directory_path: impl AsRef<Path>,
+    checks: Checks,
+    profile_path: Option<impl AsRef<Path>>,
+    report_formats: &[ReportFormat],
 ) -> Result<(), Error> {
     info!("Start validation");
 
-    let reader = CitygmlReader::from_path(file_path)?;
-    let report = reader.validate()?;
+    let profile = profile_path.map(ValidationProfile::from_path).transpose()?;
+    if let Some(profile) = &profile {
+        info!("Validation profile: {}", profile.name);
+    }
+    let validate_options = validate_options(profile, checks);
+    let reports: Vec<Report> = match file_paths {
+        [file_path] if file_path == Path::new(STDIN_PATH) => vec![
+            CitygmlReader::new(std::io::stdin().lock())
+                .with_validate_options(validate_options)
+                .validate()?,
+        ],
+        [file_path] if is_citygml_document_path(file_path) => {
+            let mut report = CitygmlReader::from_path(file_path)?
+                .with_validate_options(validate_options)
+                .validate()?;
+            report.source = Some(file_path.display().to_string());
+            vec![report]
+        }
+        _ => CitygmlDatasetReader::from_paths(file_paths)?
+            .with_validate_options(validate_options)
+            .validate()?,
+    };
+
+    let count_findings =
+        |severity: Severity| -> usize { reports.iter().map(|x| x.count_findings(severity)).sum() };
+    info!(
+        "Findings: {} errors, {} warnings, {} infos",
+        count_findings(Severity::Error),
+        count_findings(Severity::Warning),
+        count_findings(Severity::Info)
+    );
+    if reports.len() > 1 {
+        info!(
+            "Cross-file references: {}",
+            reports
+                .iter()
+                .map(|x| x.statistics.number_of_cross_file_references)
+                .sum::<usize>()
+        );
+    }
+    if count_findings(Severity::Error) > 0 {
+        warn!("The dataset does not pass the validation");
+    }
 
     fs::create_dir_all(&output_directory_path)?;
-    let path = output_directory_path
-        .as_ref()
-        .to_owned()
-        .join("report.yaml");
-    report.write(path);
+    let mut report_names: HashSet<String> = HashSet::new();
+    for report in &reports {
+        let report_name = report_name(report, reports.len(), &report_names);
+        for report_format in report_formats {
+            let path = output_directory_path
+                .as_ref()
+                .join(format!("{report_name}.{}", report_format.file_extension()));
+            report.write(&path, *report_format)?;
+            info!("Wrote {report_format} report to {}", path.display());
+        }
+        report_names.insert(report_name);
+    }
 
     /*if report.contains_gml_id_duplicates() {
         warn!("GML ID duplicates identified: ");
@@ -56,3 +119,50 @@ pub fn run(
 
     Ok(())
 }
+
+/// Returns `report` for a single document and `<document>.report` for each document of a
+/// dataset, where documents with the same file name are numbered.
+fn report_name(
+    report: &Report,
+    number_of_reports: usize,
+    report_names: &HashSet<String>,
+) -> String {
+    if number_of_reports == 1 {
+        return "report".to_string();
+    }
+
+    let document_name = report
+        .source
+        .as_deref()
+        .and_then(|x| Path::new(x).file_name())
+        .map(|x| x.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let report_name = format!("{document_name}.report");
+    (1..)
+        .map(|x| match x {
+            1 => report_name.clone(),
+            _ => format!("{document_name}.{x}.report"),
+        })
+        .find(|x| !report_names.contains(x))
+        .expect("should find an unused name")
+}
+
+/// Applies the profile, while the checks are enabled in addition to the profile.
+fn validate_options(profile: Option<ValidationProfile>, checks: Checks) -> ValidateOptions {
+    let mut validate_options = profile
+        .map(ValidateOptions::from_profile)
+        .unwrap_or_default();
+    if checks.structure {
+        validate_options = validate_options.with_structure_validation(true);
+    }
+    if checks.geometry {
+        validate_options = validate_options.with_geometry_validation(true);
+    }
+    if checks.semantic {
+        validate_options = validate_options.with_semantic_validation(true);
+    }
+    if checks.topology {
+        validate_options = validate_options.with_topology_validation(true);
+    }
+    validate_options
+}
@@ -5,7 +5,12 @@ mod error;
 use anyhow::Result;
 
 use crate::cli::{Cli, Commands};
+use crate::commands::validate::Checks;
 use clap::Parser;
+use std::path::{Path, PathBuf};
+
+/// Input path denoting that the dataset is read from stdin.
+pub const STDIN_PATH: &str = "-";
 
 fn main() -> Result<()> {
     tracing_subscriber::fmt::init();
@@ -13,15 +18,50 @@ fn main() -> Result<()> {
 
     match &cli.command {
         Commands::Statistics { file_path } => {
-            commands::statistics::run(file_path.canonicalize()?)?;
+            commands::statistics::run(resolve_input_path(file_path)?)?;
+        }
+        Commands::Metrics {
+            file_path,
+            output_file_path,
+        } => {
+            commands::metrics::run(resolve_input_path(file_path)?, output_file_path)?;
         }
         Commands::Validate {
             file_path,
             output_directory_path,
+            structure,
+            geometry,
+            semantic,
+            topology,
+            profile,
+            format,
         } => {
-            commands::validate::run(file_path.canonicalize()?, output_directory_path)?;
+            let file_paths = file_path
+                .iter()
+                .map(|x| resolve_input_path(x))
+                .collect::<Result<Vec<_>>>()?;
+            commands::validate::run(
+                &file_paths,
+                output_directory_path,
+                Checks {
+                    structure: *structure,
+                    geometry: *geometry,
+                    semantic: *semantic,
+                    topology: *topology,
+                },
+                profile.as_ref(),
+                format,
+            )?;
         }
     };
 
     Ok(())
 }
+
+fn resolve_input_path(path: &Path) -> Result<PathBuf> {
+    if path == Path::new(STDIN_PATH) {
+        return Ok(path.to_path_buf());
+    }
+
+    Ok(path.canonicalize()?)
+}
@@ -0,0 +1,75 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+/// Typed content of an Application Domain Extension (ADE) property.
+pub trait AdeProperty: Debug + Send + Sync {
+    /// Serializes the property back to an XML fragment including its start and end tag.
+    fn to_xml(&self) -> String;
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn clone_box(&self) -> Box<dyn AdeProperty>;
+}
+
+impl Clone for Box<dyn AdeProperty> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl PartialEq for Box<dyn AdeProperty> {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_xml() == other.to_xml()
+    }
+}
+
+/// ADE properties of a city object, grouped by the namespace of the ADE.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AdeExtensionMap {
+    properties: BTreeMap<String, Vec<Box<dyn AdeProperty>>>,
+}
+
+impl AdeExtensionMap {
+    pub fn new() -> Self {
+        Self {
+            properties: BTreeMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, namespace: impl Into<String>, property: Box<dyn AdeProperty>) {
+        self.properties
+            .entry(namespace.into())
+            .or_default()
+            .push(property);
+    }
+
+    pub fn get(&self, namespace: &str) -> &[Box<dyn AdeProperty>] {
+        self.properties
+            .get(namespace)
+            .map(|x| x.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns all properties of the namespace that are of type `T`.
+    pub fn get_typed<T: 'static>(&self, namespace: &str) -> Vec<&T> {
+        self.get(namespace)
+            .iter()
+            .filter_map(|x| x.as_any().downcast_ref::<T>())
+            .collect()
+    }
+
+    pub fn namespaces(&self) -> impl Iterator<Item = &str> {
+        self.properties.keys().map(|x| x.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Box<dyn AdeProperty>)> {
+        self.properties
+            .iter()
+            .flat_map(|(n, p)| p.iter().map(move |x| (n.as_str(), x)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+}
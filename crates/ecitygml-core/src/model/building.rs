@@ -1,4 +1,7 @@
-use crate::model::construction::{GroundSurface, RoofSurface, WallSurface};
+use crate::model::construction::{
+    CeilingSurface, ClosureSurface, Door, FloorSurface, GroundSurface, InteriorWallSurface,
+    OuterCeilingSurface, OuterFloorSurface, RoofSurface, WallSurface, Window,
+};
 use crate::model::core::OccupiedSpace;
 use crate::operations::{CityObjectVisitor, FeatureWithGeometry, Visitable};
 use egml::model::geometry::Envelope;
@@ -10,6 +13,12 @@ pub struct Building {
     pub wall_surface: Vec<WallSurface>,
     pub roof_surface: Vec<RoofSurface>,
     pub ground_surface: Vec<GroundSurface>,
+    pub ceiling_surface: Vec<CeilingSurface>,
+    pub floor_surface: Vec<FloorSurface>,
+    pub interior_wall_surface: Vec<InteriorWallSurface>,
+    pub outer_ceiling_surface: Vec<OuterCeilingSurface>,
+    pub outer_floor_surface: Vec<OuterFloorSurface>,
+    pub closure_surface: Vec<ClosureSurface>,
     pub building_constructive_element: Vec<BuildingConstructiveElement>,
 }
 
@@ -20,6 +29,12 @@ impl Building {
             wall_surface: Vec::new(),
             roof_surface: Vec::new(),
             ground_surface: Vec::new(),
+            ceiling_surface: Vec::new(),
+            floor_surface: Vec::new(),
+            interior_wall_surface: Vec::new(),
+            outer_ceiling_surface: Vec::new(),
+            outer_floor_surface: Vec::new(),
+            closure_surface: Vec::new(),
             building_constructive_element: Vec::new(),
         }
     }
@@ -31,6 +46,18 @@ impl Visitable for Building {
         self.wall_surface.iter().for_each(|x| x.accept(visitor));
         self.roof_surface.iter().for_each(|x| x.accept(visitor));
         self.ground_surface.iter().for_each(|x| x.accept(visitor));
+        self.ceiling_surface.iter().for_each(|x| x.accept(visitor));
+        self.floor_surface.iter().for_each(|x| x.accept(visitor));
+        self.interior_wall_surface
+            .iter()
+            .for_each(|x| x.accept(visitor));
+        self.outer_ceiling_surface
+            .iter()
+            .for_each(|x| x.accept(visitor));
+        self.outer_floor_surface
+            .iter()
+            .for_each(|x| x.accept(visitor));
+        self.closure_surface.iter().for_each(|x| x.accept(visitor));
         self.building_constructive_element
             .iter()
             .for_each(|x| x.accept(visitor));
@@ -44,6 +71,12 @@ impl FeatureWithGeometry for Building {
         envelopes.extend(self.wall_surface.iter().map(|x| x.envelope()));
         envelopes.extend(self.roof_surface.iter().map(|x| x.envelope()));
         envelopes.extend(self.ground_surface.iter().map(|x| x.envelope()));
+        envelopes.extend(self.ceiling_surface.iter().map(|x| x.envelope()));
+        envelopes.extend(self.floor_surface.iter().map(|x| x.envelope()));
+        envelopes.extend(self.interior_wall_surface.iter().map(|x| x.envelope()));
+        envelopes.extend(self.outer_ceiling_surface.iter().map(|x| x.envelope()));
+        envelopes.extend(self.outer_floor_surface.iter().map(|x| x.envelope()));
+        envelopes.extend(self.closure_surface.iter().map(|x| x.envelope()));
         envelopes.extend(
             self.building_constructive_element
                 .iter()
@@ -64,6 +97,24 @@ impl FeatureWithGeometry for Building {
         self.ground_surface
             .iter_mut()
             .for_each(|x| x.apply_transform(m));
+        self.ceiling_surface
+            .iter_mut()
+            .for_each(|x| x.apply_transform(m));
+        self.floor_surface
+            .iter_mut()
+            .for_each(|x| x.apply_transform(m));
+        self.interior_wall_surface
+            .iter_mut()
+            .for_each(|x| x.apply_transform(m));
+        self.outer_ceiling_surface
+            .iter_mut()
+            .for_each(|x| x.apply_transform(m));
+        self.outer_floor_surface
+            .iter_mut()
+            .for_each(|x| x.apply_transform(m));
+        self.closure_surface
+            .iter_mut()
+            .for_each(|x| x.apply_transform(m));
         self.building_constructive_element
             .iter_mut()
             .for_each(|x| x.apply_transform(m));
@@ -73,26 +124,40 @@ impl FeatureWithGeometry for Building {
 #[derive(Debug, Clone, PartialEq)]
 pub struct BuildingConstructiveElement {
     pub occupied_space: OccupiedSpace,
+    pub door: Vec<Door>,
+    pub window: Vec<Window>,
 }
 
 impl BuildingConstructiveElement {
     pub fn new(occupied_space: OccupiedSpace) -> Self {
-        Self { occupied_space }
+        Self {
+            occupied_space,
+            door: Vec::new(),
+            window: Vec::new(),
+        }
     }
 }
 
 impl Visitable for BuildingConstructiveElement {
     fn accept<V: CityObjectVisitor>(&self, visitor: &mut V) {
         visitor.visit_building_constructive_element(self);
+        self.door.iter().for_each(|x| x.accept(visitor));
+        self.window.iter().for_each(|x| x.accept(visitor));
     }
 }
 
 impl FeatureWithGeometry for BuildingConstructiveElement {
     fn envelope(&self) -> Option<Envelope> {
-        self.occupied_space.envelope()
+        let mut envelopes: Vec<Option<Envelope>> = vec![self.occupied_space.envelope()];
+        envelopes.extend(self.door.iter().map(|x| x.envelope()));
+        envelopes.extend(self.window.iter().map(|x| x.envelope()));
+
+        Envelope::from_optional_envelopes(&envelopes).expect("should work")
     }
 
     fn apply_transform(&mut self, m: &Isometry3<f64>) {
         self.occupied_space.apply_transform(m);
+        self.door.iter_mut().for_each(|x| x.apply_transform(m));
+        self.window.iter_mut().for_each(|x| x.apply_transform(m));
     }
 }
@@ -2,10 +2,11 @@ use crate::model::building::Building;
 use crate::model::city_furniture::CityFurniture;
 use crate::model::solitary_vegetation_object::SolitaryVegetationObject;
 use crate::model::transportation::Road;
+use crate::model::versioning::{Version, VersionTransition};
 use crate::operations::{CityObjectVisitor, FeatureWithGeometry, Visitable};
 use egml::model::geometry::Envelope;
 use nalgebra::Isometry3;
-use rayon::prelude::*;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct CitygmlModel {
@@ -13,6 +14,10 @@ pub struct CitygmlModel {
     pub city_furniture: Vec<CityFurniture>,
     pub road: Vec<Road>,
     pub solitary_vegetation_object: Vec<SolitaryVegetationObject>,
+    pub version: Vec<Version>,
+    pub version_transition: Vec<VersionTransition>,
+    /// Namespace declarations (prefix to URI) of the source document.
+    pub namespace_declarations: BTreeMap<String, String>,
 }
 
 impl CitygmlModel {
@@ -27,10 +32,13 @@ impl CitygmlModel {
             city_furniture,
             road,
             solitary_vegetation_object,
+            version: Vec::new(),
+            version_transition: Vec::new(),
+            namespace_declarations: BTreeMap::new(),
         }
     }
 
-    pub fn from_citygml_models(citygml_models: &Vec<Self>) -> Self {
+    pub fn from_citygml_models(citygml_models: &[Self]) -> Self {
         let building: Vec<Building> = citygml_models
             .iter()
             .flat_map(|x| x.building.iter().cloned())
@@ -48,7 +56,23 @@ impl CitygmlModel {
             .flat_map(|x| x.solitary_vegetation_object.iter().cloned())
             .collect();
 
-        CitygmlModel::new(building, city_furniture, road, solitary_vegetation_object)
+        let mut citygml_model =
+            CitygmlModel::new(building, city_furniture, road, solitary_vegetation_object);
+        citygml_model.version = citygml_models
+            .iter()
+            .flat_map(|x| x.version.iter().cloned())
+            .collect();
+        citygml_model.version_transition = citygml_models
+            .iter()
+            .flat_map(|x| x.version_transition.iter().cloned())
+            .collect();
+        citygml_model.namespace_declarations = citygml_models
+            .iter()
+            .rev()
+            .flat_map(|x| x.namespace_declarations.clone())
+            .collect();
+
+        citygml_model
     }
 
     pub fn is_empty(&self) -> bool {
@@ -56,6 +80,8 @@ impl CitygmlModel {
             && self.city_furniture.is_empty()
             && self.road.is_empty()
             && self.solitary_vegetation_object.is_empty()
+            && self.version.is_empty()
+            && self.version_transition.is_empty()
     }
 
     pub fn number_of_objects(&self) -> usize {
@@ -64,6 +90,10 @@ impl CitygmlModel {
             + self.road.len()
             + self.solitary_vegetation_object.len()
     }
+
+    pub fn find_version_by_tag(&self, tag: &str) -> Option<&Version> {
+        self.version.iter().find(|x| x.has_tag(tag))
+    }
 }
 
 impl Visitable for CitygmlModel {
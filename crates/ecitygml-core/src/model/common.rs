@@ -29,6 +29,7 @@ pub enum CityObjectClass {
     CityFurniture,
     CityObjectGroup,
     ClearanceSpace,
+    ClosureSurface,
     Door,
     DoorSurface,
     FloorSurface,
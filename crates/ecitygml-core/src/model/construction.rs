@@ -102,14 +102,196 @@ impl FeatureWithGeometry for GroundSurface {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct CeilingSurface {
+    pub thematic_surface: ThematicSurface,
+}
+
+impl CeilingSurface {
+    pub fn new(thematic_surface: ThematicSurface) -> Self {
+        Self { thematic_surface }
+    }
+}
+
+impl Visitable for CeilingSurface {
+    fn accept<V: CityObjectVisitor>(&self, visitor: &mut V) {
+        visitor.visit_ceiling_surface(self);
+    }
+}
+
+impl FeatureWithGeometry for CeilingSurface {
+    fn envelope(&self) -> Option<Envelope> {
+        self.thematic_surface.envelope()
+    }
+
+    fn apply_transform(&mut self, m: &Isometry3<f64>) {
+        self.thematic_surface.apply_transform(m);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloorSurface {
+    pub thematic_surface: ThematicSurface,
+}
+
+impl FloorSurface {
+    pub fn new(thematic_surface: ThematicSurface) -> Self {
+        Self { thematic_surface }
+    }
+}
+
+impl Visitable for FloorSurface {
+    fn accept<V: CityObjectVisitor>(&self, visitor: &mut V) {
+        visitor.visit_floor_surface(self);
+    }
+}
+
+impl FeatureWithGeometry for FloorSurface {
+    fn envelope(&self) -> Option<Envelope> {
+        self.thematic_surface.envelope()
+    }
+
+    fn apply_transform(&mut self, m: &Isometry3<f64>) {
+        self.thematic_surface.apply_transform(m);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InteriorWallSurface {
+    pub thematic_surface: ThematicSurface,
+    pub door_surface: Vec<DoorSurface>,
+    pub window_surface: Vec<WindowSurface>,
+}
+
+impl InteriorWallSurface {
+    pub fn new(thematic_surface: ThematicSurface) -> Self {
+        Self {
+            thematic_surface,
+            door_surface: Vec::new(),
+            window_surface: Vec::new(),
+        }
+    }
+}
+
+impl Visitable for InteriorWallSurface {
+    fn accept<V: CityObjectVisitor>(&self, visitor: &mut V) {
+        visitor.visit_interior_wall_surface(self);
+        self.door_surface.iter().for_each(|x| x.accept(visitor));
+        self.window_surface.iter().for_each(|x| x.accept(visitor));
+    }
+}
+
+impl FeatureWithGeometry for InteriorWallSurface {
+    fn envelope(&self) -> Option<Envelope> {
+        let mut envelopes: Vec<Option<Envelope>> = vec![self.thematic_surface.envelope()];
+        envelopes.extend(self.door_surface.iter().map(|x| x.envelope()));
+        envelopes.extend(self.window_surface.iter().map(|x| x.envelope()));
+
+        Envelope::from_optional_envelopes(&envelopes).expect("should work")
+    }
+
+    fn apply_transform(&mut self, m: &Isometry3<f64>) {
+        self.thematic_surface.apply_transform(m);
+        self.door_surface
+            .iter_mut()
+            .for_each(|x| x.apply_transform(m));
+        self.window_surface
+            .iter_mut()
+            .for_each(|x| x.apply_transform(m));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OuterCeilingSurface {
+    pub thematic_surface: ThematicSurface,
+}
+
+impl OuterCeilingSurface {
+    pub fn new(thematic_surface: ThematicSurface) -> Self {
+        Self { thematic_surface }
+    }
+}
+
+impl Visitable for OuterCeilingSurface {
+    fn accept<V: CityObjectVisitor>(&self, visitor: &mut V) {
+        visitor.visit_outer_ceiling_surface(self);
+    }
+}
+
+impl FeatureWithGeometry for OuterCeilingSurface {
+    fn envelope(&self) -> Option<Envelope> {
+        self.thematic_surface.envelope()
+    }
+
+    fn apply_transform(&mut self, m: &Isometry3<f64>) {
+        self.thematic_surface.apply_transform(m);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OuterFloorSurface {
+    pub thematic_surface: ThematicSurface,
+}
+
+impl OuterFloorSurface {
+    pub fn new(thematic_surface: ThematicSurface) -> Self {
+        Self { thematic_surface }
+    }
+}
+
+impl Visitable for OuterFloorSurface {
+    fn accept<V: CityObjectVisitor>(&self, visitor: &mut V) {
+        visitor.visit_outer_floor_surface(self);
+    }
+}
+
+impl FeatureWithGeometry for OuterFloorSurface {
+    fn envelope(&self) -> Option<Envelope> {
+        self.thematic_surface.envelope()
+    }
+
+    fn apply_transform(&mut self, m: &Isometry3<f64>) {
+        self.thematic_surface.apply_transform(m);
+    }
+}
+
+/// Virtual surface closing a space, e.g. an open garage or a tunnel entrance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClosureSurface {
+    pub thematic_surface: ThematicSurface,
+}
+
+impl ClosureSurface {
+    pub fn new(thematic_surface: ThematicSurface) -> Self {
+        Self { thematic_surface }
+    }
+}
+
+impl Visitable for ClosureSurface {
+    fn accept<V: CityObjectVisitor>(&self, visitor: &mut V) {
+        visitor.visit_closure_surface(self);
+    }
+}
+
+impl FeatureWithGeometry for ClosureSurface {
+    fn envelope(&self) -> Option<Envelope> {
+        self.thematic_surface.envelope()
+    }
+
+    fn apply_transform(&mut self, m: &Isometry3<f64>) {
+        self.thematic_surface.apply_transform(m);
+    }
+}
+
+/// Boundary surface of a window opening.
 #[derive(Debug, Clone, PartialEq)]
 pub struct WindowSurface {
-    pub occupied_space: OccupiedSpace,
+    pub thematic_surface: ThematicSurface,
 }
 
 impl WindowSurface {
-    pub fn new(occupied_space: OccupiedSpace) -> Self {
-        Self { occupied_space }
+    pub fn new(thematic_surface: ThematicSurface) -> Self {
+        Self { thematic_surface }
     }
 }
 
@@ -121,22 +303,23 @@ impl Visitable for WindowSurface {
 
 impl FeatureWithGeometry for WindowSurface {
     fn envelope(&self) -> Option<Envelope> {
-        self.occupied_space.envelope()
+        self.thematic_surface.envelope()
     }
 
     fn apply_transform(&mut self, m: &Isometry3<f64>) {
-        self.occupied_space.apply_transform(m);
+        self.thematic_surface.apply_transform(m);
     }
 }
 
+/// Boundary surface of a door opening.
 #[derive(Debug, Clone, PartialEq)]
 pub struct DoorSurface {
-    pub occupied_space: OccupiedSpace,
+    pub thematic_surface: ThematicSurface,
 }
 
 impl DoorSurface {
-    pub fn new(occupied_space: OccupiedSpace) -> Self {
-        Self { occupied_space }
+    pub fn new(thematic_surface: ThematicSurface) -> Self {
+        Self { thematic_surface }
     }
 }
 
@@ -148,10 +331,88 @@ impl Visitable for DoorSurface {
 
 impl FeatureWithGeometry for DoorSurface {
     fn envelope(&self) -> Option<Envelope> {
-        self.occupied_space.envelope()
+        self.thematic_surface.envelope()
+    }
+
+    fn apply_transform(&mut self, m: &Isometry3<f64>) {
+        self.thematic_surface.apply_transform(m);
+    }
+}
+
+/// Window modelled as occupied space filling an opening of a constructive element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Window {
+    pub occupied_space: OccupiedSpace,
+    pub window_surface: Vec<WindowSurface>,
+}
+
+impl Window {
+    pub fn new(occupied_space: OccupiedSpace) -> Self {
+        Self {
+            occupied_space,
+            window_surface: Vec::new(),
+        }
+    }
+}
+
+impl Visitable for Window {
+    fn accept<V: CityObjectVisitor>(&self, visitor: &mut V) {
+        visitor.visit_window(self);
+        self.window_surface.iter().for_each(|x| x.accept(visitor));
+    }
+}
+
+impl FeatureWithGeometry for Window {
+    fn envelope(&self) -> Option<Envelope> {
+        let mut envelopes: Vec<Option<Envelope>> = vec![self.occupied_space.envelope()];
+        envelopes.extend(self.window_surface.iter().map(|x| x.envelope()));
+
+        Envelope::from_optional_envelopes(&envelopes).expect("should work")
+    }
+
+    fn apply_transform(&mut self, m: &Isometry3<f64>) {
+        self.occupied_space.apply_transform(m);
+        self.window_surface
+            .iter_mut()
+            .for_each(|x| x.apply_transform(m));
+    }
+}
+
+/// Door modelled as occupied space filling an opening of a constructive element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Door {
+    pub occupied_space: OccupiedSpace,
+    pub door_surface: Vec<DoorSurface>,
+}
+
+impl Door {
+    pub fn new(occupied_space: OccupiedSpace) -> Self {
+        Self {
+            occupied_space,
+            door_surface: Vec::new(),
+        }
+    }
+}
+
+impl Visitable for Door {
+    fn accept<V: CityObjectVisitor>(&self, visitor: &mut V) {
+        visitor.visit_door(self);
+        self.door_surface.iter().for_each(|x| x.accept(visitor));
+    }
+}
+
+impl FeatureWithGeometry for Door {
+    fn envelope(&self) -> Option<Envelope> {
+        let mut envelopes: Vec<Option<Envelope>> = vec![self.occupied_space.envelope()];
+        envelopes.extend(self.door_surface.iter().map(|x| x.envelope()));
+
+        Envelope::from_optional_envelopes(&envelopes).expect("should work")
     }
 
     fn apply_transform(&mut self, m: &Isometry3<f64>) {
         self.occupied_space.apply_transform(m);
+        self.door_surface
+            .iter_mut()
+            .for_each(|x| x.apply_transform(m));
     }
 }
@@ -1,21 +1,172 @@
+use crate::model::ade::AdeExtensionMap;
+use crate::model::dynamizer::{Dynamizer, TimeseriesValue};
+use crate::model::point_cloud::PointCloud;
 use crate::operations::FeatureWithGeometry;
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
 use egml::model::base::AbstractGml;
 use egml::model::geometry;
 use egml::model::geometry::{DirectPosition, Envelope};
 use egml::operations::geometry::Geometry;
 use nalgebra::Isometry3;
+use std::collections::BTreeMap;
+use strum_macros::{Display, EnumIter, EnumString};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct CityObject {
     pub abstract_gml: AbstractGml,
+    pub creation_date: Option<DateTime<Utc>>,
+    pub termination_date: Option<DateTime<Utc>>,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_to: Option<DateTime<Utc>>,
+    pub relative_to_terrain: Option<RelativeToTerrain>,
+    pub relative_to_water: Option<RelativeToWater>,
+    pub external_reference: Vec<ExternalReference>,
     pub generic_attributes: Vec<GenericAttribute>,
+    pub dynamizer: Vec<Dynamizer>,
+    pub ade_extension: AdeExtensionMap,
+    /// Child elements that are not interpreted, preserved for writing them back.
+    pub unknown_content: Vec<XmlFragment>,
 }
 
 impl CityObject {
     pub fn new(abstract_gml: AbstractGml, generic_attributes: Vec<GenericAttribute>) -> Self {
         Self {
             abstract_gml,
+            creation_date: None,
+            termination_date: None,
+            valid_from: None,
+            valid_to: None,
+            relative_to_terrain: None,
+            relative_to_water: None,
+            external_reference: Vec::new(),
             generic_attributes,
+            dynamizer: Vec::new(),
+            ade_extension: AdeExtensionMap::new(),
+            unknown_content: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if the object exists in the real world at the given time.
+    ///
+    /// Missing `validFrom`/`validTo` values are treated as unbounded.
+    pub fn is_valid_at(&self, time: &DateTime<Utc>) -> bool {
+        self.valid_from.is_none_or(|x| x <= *time) && self.valid_to.is_none_or(|x| *time < x)
+    }
+
+    /// Returns the first generic attribute with the given name.
+    pub fn generic_attribute(&self, name: &str) -> Option<&GenericAttribute> {
+        self.generic_attributes.iter().find(|x| x.name() == name)
+    }
+
+    pub fn contains_generic_attribute(&self, name: &str) -> bool {
+        self.generic_attribute(name).is_some()
+    }
+
+    /// Returns the value of the attribute as string, independent of its type.
+    pub fn generic_attribute_as_string(&self, name: &str) -> Option<String> {
+        self.generic_attribute(name)
+            .and_then(|x| x.value_as_string())
+    }
+
+    /// Returns the value of the attribute as integer, parsing string values if needed.
+    pub fn generic_attribute_as_int(&self, name: &str) -> Option<i64> {
+        self.generic_attribute(name).and_then(|x| x.value_as_int())
+    }
+
+    /// Returns the value of the attribute as double, parsing string values if needed.
+    pub fn generic_attribute_as_double(&self, name: &str) -> Option<f64> {
+        self.generic_attribute(name)
+            .and_then(|x| x.value_as_double())
+    }
+
+    /// Returns the value of the attribute dynamized by the given `attribute_ref` at a timestamp.
+    pub fn dynamized_value(
+        &self,
+        attribute_ref: &str,
+        time: &DateTime<Utc>,
+    ) -> Option<&TimeseriesValue> {
+        self.dynamizer
+            .iter()
+            .filter(|x| x.attribute_ref == attribute_ref)
+            .find_map(|x| x.value_at(time))
+    }
+
+    /// Returns the generic attributes keyed by their name.
+    ///
+    /// If multiple attributes share a name, the first one is kept.
+    pub fn generic_attribute_map(&self) -> BTreeMap<&str, &GenericAttribute> {
+        let mut map: BTreeMap<&str, &GenericAttribute> = BTreeMap::new();
+        for attribute in &self.generic_attributes {
+            map.entry(attribute.name()).or_insert(attribute);
+        }
+        map
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter, EnumString, Display)]
+#[strum(serialize_all = "camelCase")]
+pub enum RelativeToTerrain {
+    EntirelyAboveTerrain,
+    SubstantiallyAboveTerrain,
+    SubstantiallyAboveAndBelowTerrain,
+    SubstantiallyBelowTerrain,
+    EntirelyBelowTerrain,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter, EnumString, Display)]
+#[strum(serialize_all = "camelCase")]
+pub enum RelativeToWater {
+    EntirelyAboveWaterSurface,
+    SubstantiallyAboveWaterSurface,
+    SubstantiallyAboveAndBelowWaterSurface,
+    SubstantiallyBelowWaterSurface,
+    EntirelyBelowWaterSurface,
+    TemporarilyAboveAndBelowWaterSurface,
+}
+
+/// Reference to a corresponding object in another information system, for example a cadastre.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExternalReference {
+    pub target_resource: String,
+    pub information_system: Option<String>,
+    pub relation_type: Option<String>,
+}
+
+impl ExternalReference {
+    pub fn new(target_resource: String) -> Self {
+        Self {
+            target_resource,
+            information_system: None,
+            relation_type: None,
+        }
+    }
+}
+
+/// Position of a raw XML element relative to the interpreted child elements of its parent.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FragmentPosition {
+    /// Local name of the closest preceding sibling that is not kept as [`XmlFragment`], or
+    /// `None` if the element precedes all interpreted siblings.
+    pub preceding_element: Option<String>,
+    /// Number of siblings with the same local name before the preceding element.
+    pub occurrence: usize,
+}
+
+/// Raw XML element that is kept without being interpreted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XmlFragment {
+    pub element_name: String,
+    pub position: FragmentPosition,
+    /// Serialized element including its start and end tag.
+    pub content: String,
+}
+
+impl XmlFragment {
+    pub fn new(element_name: String, position: FragmentPosition, content: String) -> Self {
+        Self {
+            element_name,
+            position,
+            content,
         }
     }
 }
@@ -52,6 +203,8 @@ pub struct Space {
     pub lod0_multi_surface: Option<geometry::MultiSurface>,
     pub lod2_multi_surface: Option<geometry::MultiSurface>,
     pub lod3_multi_surface: Option<geometry::MultiSurface>,
+
+    pub point_cloud: Option<PointCloud>,
 }
 
 impl Space {
@@ -64,6 +217,7 @@ impl Space {
             lod0_multi_surface: None,
             lod2_multi_surface: None,
             lod3_multi_surface: None,
+            point_cloud: None,
         }
     }
 }
@@ -77,6 +231,7 @@ impl FeatureWithGeometry for Space {
             self.lod0_multi_surface.as_ref().map(|x| x.envelope()),
             self.lod2_multi_surface.as_ref().map(|x| x.envelope()),
             self.lod3_multi_surface.as_ref().map(|x| x.envelope()),
+            self.point_cloud.as_ref().and_then(|x| x.envelope()),
         ];
 
         Envelope::from_optional_envelopes(&envelopes).expect("should work")
@@ -105,6 +260,10 @@ impl FeatureWithGeometry for Space {
         if let Some(g) = &mut self.lod3_multi_surface {
             g.apply_transform(m);
         }
+
+        if let Some(g) = &mut self.point_cloud {
+            g.apply_transform(m);
+        }
     }
 }
 
@@ -227,11 +386,63 @@ pub struct DoubleAttribute {
     pub value: f64,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DateAttribute {
+    pub name: String,
+    pub value: NaiveDate,
+    /// Timezone of the xs:date value, if it has one.
+    pub timezone: Option<FixedOffset>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct UriAttribute {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct MeasureAttribute {
+    pub name: String,
+    pub value: f64,
+    pub uom: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct CodeAttribute {
+    pub name: String,
+    pub value: String,
+    pub code_space: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericAttributeSet {
+    pub name: String,
+    pub code_space: Option<String>,
+    pub generic_attribute: Vec<GenericAttribute>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd, EnumIter, Display)]
+pub enum GenericAttributeType {
+    String,
+    Int,
+    Double,
+    Date,
+    Uri,
+    Measure,
+    Code,
+    Set,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum GenericAttribute {
     String(StringAttribute),
     Int(IntAttribute),
     Double(DoubleAttribute),
+    Date(DateAttribute),
+    Uri(UriAttribute),
+    Measure(MeasureAttribute),
+    Code(CodeAttribute),
+    Set(GenericAttributeSet),
 }
 
 impl GenericAttribute {
@@ -240,6 +451,65 @@ impl GenericAttribute {
             GenericAttribute::String(attr) => &attr.name,
             GenericAttribute::Int(attr) => &attr.name,
             GenericAttribute::Double(attr) => &attr.name,
+            GenericAttribute::Date(attr) => &attr.name,
+            GenericAttribute::Uri(attr) => &attr.name,
+            GenericAttribute::Measure(attr) => &attr.name,
+            GenericAttribute::Code(attr) => &attr.name,
+            GenericAttribute::Set(attr) => &attr.name,
+        }
+    }
+
+    pub fn attribute_type(&self) -> GenericAttributeType {
+        match self {
+            GenericAttribute::String(_) => GenericAttributeType::String,
+            GenericAttribute::Int(_) => GenericAttributeType::Int,
+            GenericAttribute::Double(_) => GenericAttributeType::Double,
+            GenericAttribute::Date(_) => GenericAttributeType::Date,
+            GenericAttribute::Uri(_) => GenericAttributeType::Uri,
+            GenericAttribute::Measure(_) => GenericAttributeType::Measure,
+            GenericAttribute::Code(_) => GenericAttributeType::Code,
+            GenericAttribute::Set(_) => GenericAttributeType::Set,
+        }
+    }
+
+    /// Returns the value as string, or `None` for attribute sets.
+    pub fn value_as_string(&self) -> Option<String> {
+        match self {
+            GenericAttribute::String(attr) => Some(attr.value.clone()),
+            GenericAttribute::Int(attr) => Some(attr.value.to_string()),
+            GenericAttribute::Double(attr) => Some(attr.value.to_string()),
+            GenericAttribute::Date(attr) => Some(attr.value.to_string()),
+            GenericAttribute::Uri(attr) => Some(attr.value.clone()),
+            GenericAttribute::Measure(attr) => Some(attr.value.to_string()),
+            GenericAttribute::Code(attr) => Some(attr.value.clone()),
+            GenericAttribute::Set(_) => None,
+        }
+    }
+
+    /// Returns the value as integer.
+    ///
+    /// String and code values are parsed, doubles and measures are only converted if they
+    /// have no fractional part.
+    pub fn value_as_int(&self) -> Option<i64> {
+        match self {
+            GenericAttribute::Int(attr) => Some(attr.value),
+            GenericAttribute::String(attr) => attr.value.trim().parse().ok(),
+            GenericAttribute::Code(attr) => attr.value.trim().parse().ok(),
+            GenericAttribute::Double(attr) => f64_to_i64(attr.value),
+            GenericAttribute::Measure(attr) => f64_to_i64(attr.value),
+            GenericAttribute::Date(_) | GenericAttribute::Uri(_) | GenericAttribute::Set(_) => None,
+        }
+    }
+
+    /// Returns the value as double, parsing string and code values if needed.
+    pub fn value_as_double(&self) -> Option<f64> {
+        match self {
+            GenericAttribute::Double(attr) => Some(attr.value),
+            GenericAttribute::Measure(attr) => Some(attr.value),
+            GenericAttribute::Int(attr) => Some(attr.value as f64),
+            GenericAttribute::String(attr) => attr.value.trim().parse().ok(),
+            GenericAttribute::Code(attr) => attr.value.trim().parse().ok(),
+            GenericAttribute::Date(_) | GenericAttribute::Uri(_) | GenericAttribute::Set(_) => None,
         }
     }
 
@@ -266,6 +536,55 @@ impl GenericAttribute {
             None
         }
     }
+
+    pub fn as_date(&self) -> Option<&DateAttribute> {
+        if let GenericAttribute::Date(attr) = self {
+            Some(attr)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_uri(&self) -> Option<&UriAttribute> {
+        if let GenericAttribute::Uri(attr) = self {
+            Some(attr)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_measure(&self) -> Option<&MeasureAttribute> {
+        if let GenericAttribute::Measure(attr) = self {
+            Some(attr)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_code(&self) -> Option<&CodeAttribute> {
+        if let GenericAttribute::Code(attr) = self {
+            Some(attr)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_set(&self) -> Option<&GenericAttributeSet> {
+        if let GenericAttribute::Set(attr) = self {
+            Some(attr)
+        } else {
+            None
+        }
+    }
+}
+
+/// Converts the value without loss, where `i64::MAX as f64` is 2^63 and already out of range.
+fn f64_to_i64(value: f64) -> Option<i64> {
+    if value.fract() == 0.0 && value >= i64::MIN as f64 && value < i64::MAX as f64 {
+        Some(value as i64)
+    } else {
+        None
+    }
 }
 
 impl From<StringAttribute> for GenericAttribute {
@@ -285,3 +604,141 @@ impl From<DoubleAttribute> for GenericAttribute {
         GenericAttribute::Double(attr)
     }
 }
+
+impl From<DateAttribute> for GenericAttribute {
+    fn from(attr: DateAttribute) -> Self {
+        GenericAttribute::Date(attr)
+    }
+}
+
+impl From<UriAttribute> for GenericAttribute {
+    fn from(attr: UriAttribute) -> Self {
+        GenericAttribute::Uri(attr)
+    }
+}
+
+impl From<MeasureAttribute> for GenericAttribute {
+    fn from(attr: MeasureAttribute) -> Self {
+        GenericAttribute::Measure(attr)
+    }
+}
+
+impl From<CodeAttribute> for GenericAttribute {
+    fn from(attr: CodeAttribute) -> Self {
+        GenericAttribute::Code(attr)
+    }
+}
+
+impl From<GenericAttributeSet> for GenericAttribute {
+    fn from(attr: GenericAttributeSet) -> Self {
+        GenericAttribute::Set(attr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egml::model::base::Id;
+
+    fn city_object(generic_attributes: Vec<GenericAttribute>) -> CityObject {
+        CityObject::new(
+            AbstractGml::new(Id::try_from("building_1").expect("should work")),
+            generic_attributes,
+        )
+    }
+
+    #[test]
+    fn test_generic_attribute_conversions() {
+        let city_object = city_object(vec![
+            StringAttribute {
+                name: "storeys".to_string(),
+                value: " 3 ".to_string(),
+            }
+            .into(),
+            StringAttribute {
+                name: "height".to_string(),
+                value: "12.5".to_string(),
+            }
+            .into(),
+            DoubleAttribute {
+                name: "area".to_string(),
+                value: 120.0,
+            }
+            .into(),
+            DoubleAttribute {
+                name: "ratio".to_string(),
+                value: 0.75,
+            }
+            .into(),
+            IntAttribute {
+                name: "year".to_string(),
+                value: 1990,
+            }
+            .into(),
+            StringAttribute {
+                name: "storeys".to_string(),
+                value: "4".to_string(),
+            }
+            .into(),
+        ]);
+
+        assert_eq!(city_object.generic_attribute_as_int("storeys"), Some(3));
+        assert_eq!(
+            city_object.generic_attribute_as_double("storeys"),
+            Some(3.0)
+        );
+        assert_eq!(city_object.generic_attribute_as_int("height"), None);
+        assert_eq!(
+            city_object.generic_attribute_as_double("height"),
+            Some(12.5)
+        );
+        assert_eq!(city_object.generic_attribute_as_int("area"), Some(120));
+        assert_eq!(city_object.generic_attribute_as_int("ratio"), None);
+        assert_eq!(
+            city_object.generic_attribute_as_double("year"),
+            Some(1990.0)
+        );
+        assert_eq!(
+            city_object.generic_attribute_as_string("ratio"),
+            Some("0.75".to_string())
+        );
+        assert_eq!(
+            city_object.generic_attribute_as_string("year"),
+            Some("1990".to_string())
+        );
+        assert_eq!(city_object.generic_attribute_as_string("missing"), None);
+    }
+
+    #[test]
+    fn test_generic_attribute_set_has_no_scalar_value() {
+        let city_object = city_object(vec![
+            GenericAttributeSet {
+                name: "address".to_string(),
+                code_space: None,
+                generic_attribute: vec![
+                    IntAttribute {
+                        name: "number".to_string(),
+                        value: 1,
+                    }
+                    .into(),
+                ],
+            }
+            .into(),
+        ]);
+
+        assert_eq!(city_object.generic_attribute_as_string("address"), None);
+        assert_eq!(city_object.generic_attribute_as_int("address"), None);
+        assert_eq!(city_object.generic_attribute_as_double("address"), None);
+    }
+
+    #[test]
+    fn test_f64_to_i64() {
+        assert_eq!(f64_to_i64(42.0), Some(42));
+        assert_eq!(f64_to_i64(-7.0), Some(-7));
+        assert_eq!(f64_to_i64(42.5), None);
+        assert_eq!(f64_to_i64(f64::NAN), None);
+        assert_eq!(f64_to_i64(f64::INFINITY), None);
+        assert_eq!(f64_to_i64(i64::MIN as f64), Some(i64::MIN));
+        assert_eq!(f64_to_i64(i64::MAX as f64), None);
+    }
+}
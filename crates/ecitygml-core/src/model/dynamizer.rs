@@ -0,0 +1,160 @@
+use chrono::{DateTime, Utc};
+use egml::model::base::AbstractGml;
+
+/// Overrides the value of a city object attribute with time-varying values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dynamizer {
+    pub abstract_gml: AbstractGml,
+    /// XPath expression pointing to the dynamized attribute.
+    pub attribute_ref: String,
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub dynamic_data: Option<Timeseries>,
+}
+
+impl Dynamizer {
+    pub fn new(abstract_gml: AbstractGml, attribute_ref: String) -> Self {
+        Self {
+            abstract_gml,
+            attribute_ref,
+            start_time: None,
+            end_time: None,
+            dynamic_data: None,
+        }
+    }
+
+    /// Returns the value of the dynamized attribute at the given time.
+    ///
+    /// Returns `None` if the time is outside the validity of the dynamizer or if the values are
+    /// not available in-line.
+    pub fn value_at(&self, time: &DateTime<Utc>) -> Option<&TimeseriesValue> {
+        if self.start_time.is_some_and(|x| *time < x) || self.end_time.is_some_and(|x| *time > x) {
+            return None;
+        }
+
+        match self.dynamic_data.as_ref()? {
+            Timeseries::Atomic(x) => x.value_at(time),
+            Timeseries::TabulatedFile(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Timeseries {
+    Atomic(AtomicTimeseries),
+    TabulatedFile(Box<TabulatedFileTimeseries>),
+}
+
+/// Timeseries with in-line time/value pairs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtomicTimeseries {
+    pub abstract_gml: AbstractGml,
+    pub observation_property: Option<String>,
+    pub uom: Option<String>,
+    pub first_timestamp: Option<DateTime<Utc>>,
+    pub last_timestamp: Option<DateTime<Utc>>,
+    pub component: Vec<TimeValuePair>,
+}
+
+impl AtomicTimeseries {
+    pub fn new(abstract_gml: AbstractGml) -> Self {
+        Self {
+            abstract_gml,
+            observation_property: None,
+            uom: None,
+            first_timestamp: None,
+            last_timestamp: None,
+            component: Vec::new(),
+        }
+    }
+
+    /// Returns the value of the latest time/value pair not after the given time.
+    pub fn value_at(&self, time: &DateTime<Utc>) -> Option<&TimeseriesValue> {
+        if self.last_timestamp.is_some_and(|x| *time > x) {
+            return None;
+        }
+
+        self.component
+            .iter()
+            .filter(|x| x.timestamp <= *time)
+            .max_by_key(|x| x.timestamp)
+            .map(|x| &x.value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeValuePair {
+    pub timestamp: DateTime<Utc>,
+    pub value: TimeseriesValue,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeseriesValue {
+    Int(i64),
+    Double(f64),
+    String(String),
+    Bool(bool),
+    Uri(String),
+}
+
+impl TimeseriesValue {
+    pub fn as_double(&self) -> Option<f64> {
+        match self {
+            TimeseriesValue::Int(x) => Some(*x as f64),
+            TimeseriesValue::Double(x) => Some(*x),
+            _ => None,
+        }
+    }
+}
+
+/// Timeseries whose values are stored in an external tabulated file, e.g. CSV.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TabulatedFileTimeseries {
+    pub abstract_gml: AbstractGml,
+    pub observation_property: Option<String>,
+    pub uom: Option<String>,
+    pub first_timestamp: Option<DateTime<Utc>>,
+    pub last_timestamp: Option<DateTime<Utc>>,
+    pub file_location: String,
+    pub file_type: Option<String>,
+    pub mime_type: Option<String>,
+    pub value_type: Option<String>,
+    pub number_of_header_lines: Option<u32>,
+    pub field_separator: Option<String>,
+    pub decimal_symbol: Option<String>,
+    pub id_column_no: Option<u32>,
+    pub id_column_name: Option<String>,
+    pub id_value: Option<String>,
+    pub time_column_no: Option<u32>,
+    pub time_column_name: Option<String>,
+    pub time_format: Option<String>,
+    pub value_column_no: Option<u32>,
+    pub value_column_name: Option<String>,
+}
+
+impl TabulatedFileTimeseries {
+    pub fn new(abstract_gml: AbstractGml, file_location: String) -> Self {
+        Self {
+            abstract_gml,
+            observation_property: None,
+            uom: None,
+            first_timestamp: None,
+            last_timestamp: None,
+            file_location,
+            file_type: None,
+            mime_type: None,
+            value_type: None,
+            number_of_header_lines: None,
+            field_separator: None,
+            decimal_symbol: None,
+            id_column_no: None,
+            id_column_name: None,
+            id_value: None,
+            time_column_no: None,
+            time_column_name: None,
+            time_format: None,
+            value_column_no: None,
+            value_column_name: None,
+        }
+    }
+}
@@ -1,8 +1,12 @@
+pub mod ade;
 pub mod building;
 pub mod city_furniture;
 pub mod city_model;
 pub mod common;
 pub mod construction;
 pub mod core;
+pub mod dynamizer;
+pub mod point_cloud;
 pub mod solitary_vegetation_object;
 pub mod transportation;
+pub mod versioning;
@@ -0,0 +1,79 @@
+use crate::Error;
+use crate::operations::FeatureWithGeometry;
+use egml::model::base::AbstractGml;
+use egml::model::geometry::{DirectPosition, Envelope};
+use egml::operations::geometry::Geometry;
+use nalgebra::Isometry3;
+
+/// Aggregate of points as contained inline by a [`PointCloud`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiPoint {
+    point_member: Vec<DirectPosition>,
+}
+
+impl MultiPoint {
+    pub fn new(point_member: Vec<DirectPosition>) -> Result<Self, Error> {
+        if point_member.is_empty() {
+            return Err(Error::ContainsNoMembers("point_member".to_string()));
+        }
+
+        Ok(Self { point_member })
+    }
+
+    pub fn point_member(&self) -> &Vec<DirectPosition> {
+        &self.point_member
+    }
+}
+
+impl Geometry for MultiPoint {
+    fn points(&self) -> Vec<&DirectPosition> {
+        self.point_member.iter().collect()
+    }
+
+    fn apply_transform(&mut self, m: &Isometry3<f64>) {
+        self.point_member.iter_mut().for_each(|p| {
+            p.apply_transform(m);
+        });
+    }
+}
+
+/// Point cloud representation of a space, either given inline or as a
+/// reference to an external file (e.g. LAS/LAZ).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointCloud {
+    pub abstract_gml: AbstractGml,
+    pub mime_type: Option<String>,
+    pub point_file: Option<String>,
+    pub point_file_srs_name: Option<String>,
+    pub points: Option<MultiPoint>,
+}
+
+impl PointCloud {
+    pub fn new(abstract_gml: AbstractGml) -> Self {
+        Self {
+            abstract_gml,
+            mime_type: None,
+            point_file: None,
+            point_file_srs_name: None,
+            points: None,
+        }
+    }
+
+    /// Returns true, if the points are stored in an external file.
+    pub fn is_external(&self) -> bool {
+        self.point_file.is_some()
+    }
+}
+
+impl FeatureWithGeometry for PointCloud {
+    /// Envelope of the inline points; points of external files are not considered.
+    fn envelope(&self) -> Option<Envelope> {
+        self.points.as_ref().map(|x| x.envelope())
+    }
+
+    fn apply_transform(&mut self, m: &Isometry3<f64>) {
+        if let Some(g) = &mut self.points {
+            g.apply_transform(m);
+        }
+    }
+}
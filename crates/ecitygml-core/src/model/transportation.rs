@@ -1,5 +1,6 @@
 use crate::model::core::{Space, ThematicSurface};
 use crate::operations::{CityObjectVisitor, FeatureWithGeometry, Visitable};
+use egml::model::base::Id;
 use egml::model::geometry::Envelope;
 use nalgebra::Isometry3;
 
@@ -141,6 +142,10 @@ impl FeatureWithGeometry for Intersection {
 pub struct TrafficSpace {
     pub space: Space,
     pub traffic_area: Vec<TrafficArea>, // this should be located in boundaries the space struct
+    /// Traffic spaces, from which traffic flows into this traffic space.
+    pub predecessor: Vec<Id>,
+    /// Traffic spaces, into which traffic flows from this traffic space.
+    pub successor: Vec<Id>,
 }
 
 impl TrafficSpace {
@@ -148,6 +153,8 @@ impl TrafficSpace {
         Self {
             space,
             traffic_area: Vec::new(),
+            predecessor: Vec::new(),
+            successor: Vec::new(),
         }
     }
 }
@@ -0,0 +1,91 @@
+use egml::model::base::{AbstractGml, Id};
+use std::collections::HashSet;
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Named state of a city model, consisting of the referenced version members.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Version {
+    pub abstract_gml: AbstractGml,
+    pub tag: Vec<String>,
+    pub version_member: Vec<Id>,
+}
+
+impl Version {
+    pub fn new(abstract_gml: AbstractGml) -> Self {
+        Self {
+            abstract_gml,
+            tag: Vec::new(),
+            version_member: Vec::new(),
+        }
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tag.iter().any(|x| x == tag)
+    }
+
+    pub fn version_member_ids(&self) -> HashSet<&Id> {
+        self.version_member.iter().collect()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter, EnumString, Display)]
+#[strum(serialize_all = "camelCase")]
+pub enum TransitionType {
+    Planned,
+    Realized,
+    HistoricalSuccession,
+    Fork,
+    Merge,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter, EnumString, Display)]
+#[strum(serialize_all = "camelCase")]
+pub enum TransactionType {
+    Insert,
+    Delete,
+    Replace,
+}
+
+/// Change of a single feature as part of a [`VersionTransition`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Transaction {
+    pub transaction_type: TransactionType,
+    pub old_feature: Option<Id>,
+    pub new_feature: Option<Id>,
+}
+
+impl Transaction {
+    pub fn new(transaction_type: TransactionType) -> Self {
+        Self {
+            transaction_type,
+            old_feature: None,
+            new_feature: None,
+        }
+    }
+}
+
+/// Transition between two [`Version`]s with the list of performed transactions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionTransition {
+    pub abstract_gml: AbstractGml,
+    pub reason: Option<String>,
+    pub clone_predecessor: bool,
+    pub transition_type: Option<TransitionType>,
+    pub from: Option<Id>,
+    pub to: Option<Id>,
+    pub transaction: Vec<Transaction>,
+}
+
+impl VersionTransition {
+    pub fn new(abstract_gml: AbstractGml) -> Self {
+        Self {
+            abstract_gml,
+            reason: None,
+            clone_predecessor: false,
+            transition_type: None,
+            from: None,
+            to: None,
+            transaction: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,345 @@
+use crate::model::building::{Building, BuildingConstructiveElement};
+use crate::model::city_furniture::CityFurniture;
+use crate::model::city_model::CitygmlModel;
+use crate::model::common::CityObjectClass;
+use crate::model::construction::{
+    CeilingSurface, ClosureSurface, Door, DoorSurface, FloorSurface, GroundSurface,
+    InteriorWallSurface, OuterCeilingSurface, OuterFloorSurface, RoofSurface, WallSurface, Window,
+    WindowSurface,
+};
+use crate::model::core::{CityObject, GenericAttributeType};
+use crate::model::solitary_vegetation_object::SolitaryVegetationObject;
+use crate::model::transportation::{
+    AuxiliaryTrafficArea, AuxiliaryTrafficSpace, Intersection, Road, Section, TrafficArea,
+    TrafficSpace,
+};
+use crate::operations::{CityObjectVisitor, Visitable};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Statistics about a single generic attribute within a city object class.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AttributeStatistics {
+    /// Number of city objects containing the attribute at least once.
+    pub number_of_objects: usize,
+    pub observed_types: BTreeSet<GenericAttributeType>,
+}
+
+/// Generic attribute schema of all city objects of one class.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ClassAttributeSchema {
+    pub number_of_objects: usize,
+    pub attributes: BTreeMap<String, AttributeStatistics>,
+}
+
+impl ClassAttributeSchema {
+    /// Returns the share of city objects of this class containing the attribute.
+    pub fn fill_rate(&self, attribute_name: &str) -> f64 {
+        if self.number_of_objects == 0 {
+            return 0.0;
+        }
+
+        self.attributes.get(attribute_name).map_or(0.0, |x| {
+            x.number_of_objects as f64 / self.number_of_objects as f64
+        })
+    }
+}
+
+/// Extracts the generic attribute schema of a model per [`CityObjectClass`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AttributeSchemaCollector {
+    pub city_object_classes: HashMap<CityObjectClass, ClassAttributeSchema>,
+}
+
+impl AttributeSchemaCollector {
+    pub fn new() -> Self {
+        Self {
+            city_object_classes: HashMap::new(),
+        }
+    }
+
+    pub fn from_citygml_model(citygml_model: &CitygmlModel) -> Self {
+        let mut collector = Self::new();
+        citygml_model.accept(&mut collector);
+        collector
+    }
+
+    fn add_city_object(&mut self, class: CityObjectClass, city_object: &CityObject) {
+        let class_schema = self.city_object_classes.entry(class).or_default();
+        class_schema.number_of_objects += 1;
+
+        let mut seen_names: HashSet<&str> = HashSet::new();
+        for attribute in &city_object.generic_attributes {
+            let statistics = class_schema
+                .attributes
+                .entry(attribute.name().to_string())
+                .or_default();
+            statistics.observed_types.insert(attribute.attribute_type());
+            if seen_names.insert(attribute.name()) {
+                statistics.number_of_objects += 1;
+            }
+        }
+    }
+}
+
+impl CityObjectVisitor for AttributeSchemaCollector {
+    type Result = ();
+
+    fn visit_city_model(&mut self, _v: &CitygmlModel) -> Self::Result {}
+
+    fn visit_city_furniture(&mut self, v: &CityFurniture) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::CityFurniture,
+            &v.occupied_space.space.city_object,
+        );
+    }
+
+    fn visit_building(&mut self, v: &Building) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::Building,
+            &v.occupied_space.space.city_object,
+        );
+    }
+
+    fn visit_building_constructive_element(
+        &mut self,
+        v: &BuildingConstructiveElement,
+    ) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::BuildingConstructiveElement,
+            &v.occupied_space.space.city_object,
+        );
+    }
+
+    fn visit_roof_surface(&mut self, v: &RoofSurface) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::RoofSurface,
+            &v.thematic_surface.city_object,
+        );
+    }
+
+    fn visit_ground_surface(&mut self, v: &GroundSurface) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::GroundSurface,
+            &v.thematic_surface.city_object,
+        );
+    }
+
+    fn visit_wall_surface(&mut self, v: &WallSurface) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::WallSurface,
+            &v.thematic_surface.city_object,
+        );
+    }
+
+    fn visit_window_surface(&mut self, v: &WindowSurface) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::WindowSurface,
+            &v.thematic_surface.city_object,
+        );
+    }
+
+    fn visit_door_surface(&mut self, v: &DoorSurface) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::DoorSurface,
+            &v.thematic_surface.city_object,
+        );
+    }
+
+    fn visit_ceiling_surface(&mut self, v: &CeilingSurface) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::CeilingSurface,
+            &v.thematic_surface.city_object,
+        );
+    }
+
+    fn visit_floor_surface(&mut self, v: &FloorSurface) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::FloorSurface,
+            &v.thematic_surface.city_object,
+        );
+    }
+
+    fn visit_interior_wall_surface(&mut self, v: &InteriorWallSurface) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::InteriorWallSurface,
+            &v.thematic_surface.city_object,
+        );
+    }
+
+    fn visit_outer_ceiling_surface(&mut self, v: &OuterCeilingSurface) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::OuterCeilingSurface,
+            &v.thematic_surface.city_object,
+        );
+    }
+
+    fn visit_outer_floor_surface(&mut self, v: &OuterFloorSurface) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::OuterFloorSurface,
+            &v.thematic_surface.city_object,
+        );
+    }
+
+    fn visit_closure_surface(&mut self, v: &ClosureSurface) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::ClosureSurface,
+            &v.thematic_surface.city_object,
+        );
+    }
+
+    fn visit_window(&mut self, v: &Window) -> Self::Result {
+        self.add_city_object(CityObjectClass::Window, &v.occupied_space.space.city_object);
+    }
+
+    fn visit_door(&mut self, v: &Door) -> Self::Result {
+        self.add_city_object(CityObjectClass::Door, &v.occupied_space.space.city_object);
+    }
+
+    fn visit_solitary_vegetation_object(&mut self, v: &SolitaryVegetationObject) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::SolitaryVegetationObject,
+            &v.occupied_space.space.city_object,
+        );
+    }
+
+    fn visit_road(&mut self, v: &Road) -> Self::Result {
+        self.add_city_object(CityObjectClass::Road, &v.space.city_object);
+    }
+
+    fn visit_section(&mut self, v: &Section) -> Self::Result {
+        self.add_city_object(CityObjectClass::Section, &v.space.city_object);
+    }
+
+    fn visit_intersection(&mut self, v: &Intersection) -> Self::Result {
+        self.add_city_object(CityObjectClass::Intersection, &v.space.city_object);
+    }
+
+    fn visit_traffic_space(&mut self, v: &TrafficSpace) -> Self::Result {
+        self.add_city_object(CityObjectClass::TrafficSpace, &v.space.city_object);
+    }
+
+    fn visit_auxiliary_traffic_space(&mut self, v: &AuxiliaryTrafficSpace) -> Self::Result {
+        self.add_city_object(CityObjectClass::AuxiliaryTrafficSpace, &v.space.city_object);
+    }
+
+    fn visit_traffic_area(&mut self, v: &TrafficArea) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::TrafficArea,
+            &v.thematic_surface.city_object,
+        );
+    }
+
+    fn visit_auxiliary_traffic_area(&mut self, v: &AuxiliaryTrafficArea) -> Self::Result {
+        self.add_city_object(
+            CityObjectClass::AuxiliaryTrafficArea,
+            &v.thematic_surface.city_object,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::core::{
+        DoubleAttribute, GenericAttribute, IntAttribute, OccupiedSpace, Space, StringAttribute,
+        ThematicSurface,
+    };
+    use egml::model::base::{AbstractGml, Id};
+
+    fn city_object(id: &str, generic_attributes: Vec<GenericAttribute>) -> CityObject {
+        CityObject::new(
+            AbstractGml::new(Id::try_from(id).expect("should work")),
+            generic_attributes,
+        )
+    }
+
+    fn storeys(value: i64) -> GenericAttribute {
+        IntAttribute {
+            name: "storeys".to_string(),
+            value,
+        }
+        .into()
+    }
+
+    fn building(id: &str, generic_attributes: Vec<GenericAttribute>) -> Building {
+        Building::new(OccupiedSpace::new(Space::new(city_object(
+            id,
+            generic_attributes,
+        ))))
+    }
+
+    #[test]
+    fn test_attribute_schema_per_class() {
+        let mut first_building = building(
+            "building_1",
+            vec![
+                storeys(3),
+                storeys(4),
+                StringAttribute {
+                    name: "usage".to_string(),
+                    value: "residential".to_string(),
+                }
+                .into(),
+            ],
+        );
+        first_building.wall_surface = vec![WallSurface::new(ThematicSurface::new(city_object(
+            "wall_1",
+            vec![storeys(1)],
+        )))];
+        let second_building = building(
+            "building_2",
+            vec![
+                StringAttribute {
+                    name: "storeys".to_string(),
+                    value: "2".to_string(),
+                }
+                .into(),
+            ],
+        );
+        let third_building = building("building_3", Vec::new());
+        let city_furniture = CityFurniture::new(OccupiedSpace::new(Space::new(city_object(
+            "furniture_1",
+            vec![
+                DoubleAttribute {
+                    name: "height".to_string(),
+                    value: 0.8,
+                }
+                .into(),
+            ],
+        ))));
+        let citygml_model = CitygmlModel::new(
+            vec![first_building, second_building, third_building],
+            vec![city_furniture],
+            Vec::new(),
+            Vec::new(),
+        );
+
+        let collector = AttributeSchemaCollector::from_citygml_model(&citygml_model);
+
+        assert_eq!(collector.city_object_classes.len(), 3);
+        let building_schema = &collector.city_object_classes[&CityObjectClass::Building];
+        assert_eq!(building_schema.number_of_objects, 3);
+        // repeated attributes are counted once per city object
+        assert_eq!(building_schema.attributes["storeys"].number_of_objects, 2);
+        assert_eq!(
+            building_schema.attributes["storeys"].observed_types,
+            BTreeSet::from([GenericAttributeType::String, GenericAttributeType::Int])
+        );
+        assert!((building_schema.fill_rate("storeys") - 2.0 / 3.0).abs() < 1e-9);
+        assert!((building_schema.fill_rate("usage") - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(building_schema.fill_rate("height"), 0.0);
+
+        let wall_schema = &collector.city_object_classes[&CityObjectClass::WallSurface];
+        assert_eq!(wall_schema.number_of_objects, 1);
+        assert_eq!(wall_schema.fill_rate("storeys"), 1.0);
+
+        let furniture_schema = &collector.city_object_classes[&CityObjectClass::CityFurniture];
+        assert_eq!(furniture_schema.fill_rate("height"), 1.0);
+        assert_eq!(furniture_schema.fill_rate("storeys"), 0.0);
+    }
+
+    #[test]
+    fn test_fill_rate_of_empty_class() {
+        assert_eq!(ClassAttributeSchema::default().fill_rate("storeys"), 0.0);
+    }
+}
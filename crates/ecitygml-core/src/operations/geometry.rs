@@ -0,0 +1,111 @@
+use egml::model::geometry::{LinearRing, Polygon};
+use egml::operations::geometry::Geometry;
+use nalgebra::Vector3;
+
+/// Returns the points of the ring as vectors.
+pub fn ring_vectors(ring: &LinearRing) -> Vec<Vector3<f64>> {
+    ring.points().into_iter().map(Vector3::from).collect()
+}
+
+/// Computes the normal of a ring with Newell's method, whose length is twice the enclosed area.
+pub fn newell_vector(ring: &[Vector3<f64>]) -> Vector3<f64> {
+    (0..ring.len())
+        .map(|i| {
+            let (current, next) = (ring[i], ring[(i + 1) % ring.len()]);
+            Vector3::new(
+                (current.y - next.y) * (current.z + next.z),
+                (current.z - next.z) * (current.x + next.x),
+                (current.x - next.x) * (current.y + next.y),
+            )
+        })
+        .sum()
+}
+
+/// Returns the area enclosed by the ring.
+pub fn ring_area(ring: &LinearRing) -> f64 {
+    newell_vector(&ring_vectors(ring)).norm() / 2.0
+}
+
+/// Returns the area of the exterior ring reduced by the areas of the interior rings.
+pub fn polygon_area(polygon: &Polygon) -> f64 {
+    ring_area(&polygon.exterior) - polygon.interior.iter().map(ring_area).sum::<f64>()
+}
+
+/// Returns the unit normal of the exterior ring following its orientation, or `None` if the
+/// ring encloses no area.
+pub fn polygon_normal(polygon: &Polygon) -> Option<Vector3<f64>> {
+    newell_vector(&ring_vectors(&polygon.exterior)).try_normalize(f64::EPSILON)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egml::model::base::{AbstractGml, Id};
+    use egml::model::geometry::DirectPosition;
+
+    fn ring(id: &str, coords: &[[f64; 3]]) -> LinearRing {
+        LinearRing::new(
+            AbstractGml::new(Id::try_from(id).expect("should work")),
+            coords
+                .iter()
+                .map(|x| DirectPosition::new(x[0], x[1], x[2]).expect("should work"))
+                .collect(),
+        )
+        .expect("should work")
+    }
+
+    #[test]
+    fn test_polygon_area_subtracts_interior_rings() {
+        let exterior = ring(
+            "exterior",
+            &[
+                [0.0, 0.0, 5.0],
+                [4.0, 0.0, 5.0],
+                [4.0, 4.0, 5.0],
+                [0.0, 4.0, 5.0],
+            ],
+        );
+        let interior = ring(
+            "interior",
+            &[
+                [1.0, 1.0, 5.0],
+                [1.0, 2.0, 5.0],
+                [2.0, 2.0, 5.0],
+                [2.0, 1.0, 5.0],
+            ],
+        );
+        let polygon = Polygon::new(
+            AbstractGml::new(Id::try_from("polygon").expect("should work")),
+            exterior,
+            vec![interior],
+        )
+        .expect("should work");
+
+        assert!((polygon_area(&polygon) - 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_polygon_normal_follows_orientation_far_from_origin() {
+        let offset = [690_000.0, 5_336_000.0, 500.0];
+        let exterior = ring(
+            "exterior",
+            &[
+                [0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [1.0, 0.0, 0.0],
+            ]
+            .map(|x| [x[0] + offset[0], x[1] + offset[1], x[2] + offset[2]]),
+        );
+        let polygon = Polygon::new(
+            AbstractGml::new(Id::try_from("polygon").expect("should work")),
+            exterior,
+            Vec::new(),
+        )
+        .expect("should work");
+
+        let normal = polygon_normal(&polygon).expect("should have a normal");
+        assert!((normal - Vector3::new(0.0, 0.0, -1.0)).norm() < 1e-9);
+        assert!((polygon_area(&polygon) - 1.0).abs() < 1e-6);
+    }
+}
@@ -3,9 +3,12 @@ use crate::model::city_furniture::CityFurniture;
 use crate::model::city_model::CitygmlModel;
 use crate::model::common::{CityObjectClass, LevelOfDetail};
 use crate::model::construction::{
-    DoorSurface, GroundSurface, RoofSurface, WallSurface, WindowSurface,
+    CeilingSurface, ClosureSurface, Door, DoorSurface, FloorSurface, GroundSurface,
+    InteriorWallSurface, OuterCeilingSurface, OuterFloorSurface, RoofSurface, WallSurface, Window,
+    WindowSurface,
 };
 use crate::model::core::{ImplicitGeometry, OccupiedSpace, Space, ThematicSurface};
+use crate::model::point_cloud::PointCloud;
 use crate::model::solitary_vegetation_object::SolitaryVegetationObject;
 use crate::model::transportation::{
     AuxiliaryTrafficArea, AuxiliaryTrafficSpace, Intersection, Road, Section, TrafficArea,
@@ -23,6 +26,7 @@ pub struct CityObjectGeometryCollection {
     pub implicit_geometries: HashMap<LevelOfDetail, ImplicitGeometry>,
     pub multi_surfaces: HashMap<LevelOfDetail, MultiSurface>,
     pub solids: HashMap<LevelOfDetail, Solid>,
+    pub point_cloud: Option<PointCloud>,
 }
 
 impl CityObjectGeometryCollection {
@@ -55,6 +59,7 @@ impl CityObjectGeometryCollection {
             implicit_geometries: HashMap::new(),
             multi_surfaces,
             solids,
+            point_cloud: space.point_cloud.clone(),
         }
     }
     pub fn from_occupied_space(class: CityObjectClass, occupied_space: &OccupiedSpace) -> Self {
@@ -105,6 +110,7 @@ impl CityObjectGeometryCollection {
             implicit_geometries: HashMap::new(),
             multi_surfaces,
             solids: HashMap::new(),
+            point_cloud: None,
         }
     }
 }
@@ -125,7 +131,7 @@ impl GeometryCollector {
 impl CityObjectVisitor for GeometryCollector {
     type Result = ();
 
-    fn visit_city_model(&mut self, v: &CitygmlModel) -> Self::Result {}
+    fn visit_city_model(&mut self, _v: &CitygmlModel) -> Self::Result {}
 
     fn visit_city_furniture(&mut self, v: &CityFurniture) -> Self::Result {
         let city_object_geometry_collection = CityObjectGeometryCollection::from_occupied_space(
@@ -138,7 +144,7 @@ impl CityObjectVisitor for GeometryCollector {
         );
     }
 
-    fn visit_building(&mut self, v: &Building) -> Self::Result {}
+    fn visit_building(&mut self, _v: &Building) -> Self::Result {}
 
     fn visit_building_constructive_element(
         &mut self,
@@ -188,9 +194,9 @@ impl CityObjectVisitor for GeometryCollector {
     }
 
     fn visit_window_surface(&mut self, v: &WindowSurface) -> Self::Result {
-        let city_object_geometry_collection = CityObjectGeometryCollection::from_occupied_space(
+        let city_object_geometry_collection = CityObjectGeometryCollection::from_thematic_surface(
             CityObjectClass::WindowSurface,
-            &v.occupied_space,
+            &v.thematic_surface,
         );
         self.city_objects.insert(
             city_object_geometry_collection.abstract_gml.id.clone(),
@@ -199,8 +205,96 @@ impl CityObjectVisitor for GeometryCollector {
     }
 
     fn visit_door_surface(&mut self, v: &DoorSurface) -> Self::Result {
-        let city_object_geometry_collection = CityObjectGeometryCollection::from_occupied_space(
+        let city_object_geometry_collection = CityObjectGeometryCollection::from_thematic_surface(
             CityObjectClass::DoorSurface,
+            &v.thematic_surface,
+        );
+        self.city_objects.insert(
+            city_object_geometry_collection.abstract_gml.id.clone(),
+            city_object_geometry_collection,
+        );
+    }
+
+    fn visit_ceiling_surface(&mut self, v: &CeilingSurface) -> Self::Result {
+        let city_object_geometry_collection = CityObjectGeometryCollection::from_thematic_surface(
+            CityObjectClass::CeilingSurface,
+            &v.thematic_surface,
+        );
+        self.city_objects.insert(
+            city_object_geometry_collection.abstract_gml.id.clone(),
+            city_object_geometry_collection,
+        );
+    }
+
+    fn visit_floor_surface(&mut self, v: &FloorSurface) -> Self::Result {
+        let city_object_geometry_collection = CityObjectGeometryCollection::from_thematic_surface(
+            CityObjectClass::FloorSurface,
+            &v.thematic_surface,
+        );
+        self.city_objects.insert(
+            city_object_geometry_collection.abstract_gml.id.clone(),
+            city_object_geometry_collection,
+        );
+    }
+
+    fn visit_interior_wall_surface(&mut self, v: &InteriorWallSurface) -> Self::Result {
+        let city_object_geometry_collection = CityObjectGeometryCollection::from_thematic_surface(
+            CityObjectClass::InteriorWallSurface,
+            &v.thematic_surface,
+        );
+        self.city_objects.insert(
+            city_object_geometry_collection.abstract_gml.id.clone(),
+            city_object_geometry_collection,
+        );
+    }
+
+    fn visit_outer_ceiling_surface(&mut self, v: &OuterCeilingSurface) -> Self::Result {
+        let city_object_geometry_collection = CityObjectGeometryCollection::from_thematic_surface(
+            CityObjectClass::OuterCeilingSurface,
+            &v.thematic_surface,
+        );
+        self.city_objects.insert(
+            city_object_geometry_collection.abstract_gml.id.clone(),
+            city_object_geometry_collection,
+        );
+    }
+
+    fn visit_outer_floor_surface(&mut self, v: &OuterFloorSurface) -> Self::Result {
+        let city_object_geometry_collection = CityObjectGeometryCollection::from_thematic_surface(
+            CityObjectClass::OuterFloorSurface,
+            &v.thematic_surface,
+        );
+        self.city_objects.insert(
+            city_object_geometry_collection.abstract_gml.id.clone(),
+            city_object_geometry_collection,
+        );
+    }
+
+    fn visit_closure_surface(&mut self, v: &ClosureSurface) -> Self::Result {
+        let city_object_geometry_collection = CityObjectGeometryCollection::from_thematic_surface(
+            CityObjectClass::ClosureSurface,
+            &v.thematic_surface,
+        );
+        self.city_objects.insert(
+            city_object_geometry_collection.abstract_gml.id.clone(),
+            city_object_geometry_collection,
+        );
+    }
+
+    fn visit_window(&mut self, v: &Window) -> Self::Result {
+        let city_object_geometry_collection = CityObjectGeometryCollection::from_occupied_space(
+            CityObjectClass::Window,
+            &v.occupied_space,
+        );
+        self.city_objects.insert(
+            city_object_geometry_collection.abstract_gml.id.clone(),
+            city_object_geometry_collection,
+        );
+    }
+
+    fn visit_door(&mut self, v: &Door) -> Self::Result {
+        let city_object_geometry_collection = CityObjectGeometryCollection::from_occupied_space(
+            CityObjectClass::Door,
             &v.occupied_space,
         );
         self.city_objects.insert(
@@ -220,11 +314,11 @@ impl CityObjectVisitor for GeometryCollector {
         );
     }
 
-    fn visit_road(&mut self, v: &Road) -> Self::Result {}
+    fn visit_road(&mut self, _v: &Road) -> Self::Result {}
 
-    fn visit_section(&mut self, v: &Section) -> Self::Result {}
+    fn visit_section(&mut self, _v: &Section) -> Self::Result {}
 
-    fn visit_intersection(&mut self, v: &Intersection) -> Self::Result {}
+    fn visit_intersection(&mut self, _v: &Intersection) -> Self::Result {}
 
     fn visit_traffic_space(&mut self, v: &TrafficSpace) -> Self::Result {
         let city_object_geometry_collection =
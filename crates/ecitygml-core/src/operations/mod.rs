@@ -1,3 +1,5 @@
+mod attribute_schema;
+mod geometry;
 mod geometry_collector;
 mod traits;
 mod visitor;
@@ -20,6 +22,18 @@ pub use geometry_collector::GeometryCollector;
 #[doc(inline)]
 pub use geometry_collector::CityObjectGeometryCollection;
 
+#[doc(inline)]
+pub use geometry::{newell_vector, polygon_area, polygon_normal, ring_area, ring_vectors};
+
+#[doc(inline)]
+pub use attribute_schema::AttributeSchemaCollector;
+
+#[doc(inline)]
+pub use attribute_schema::ClassAttributeSchema;
+
+#[doc(inline)]
+pub use attribute_schema::AttributeStatistics;
+
 /*#[doc(inline)]
 pub use traits::SpaceFeature;
 
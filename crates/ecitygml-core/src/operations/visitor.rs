@@ -2,7 +2,9 @@ use crate::model::building::{Building, BuildingConstructiveElement};
 use crate::model::city_furniture::CityFurniture;
 use crate::model::city_model::CitygmlModel;
 use crate::model::construction::{
-    DoorSurface, GroundSurface, RoofSurface, WallSurface, WindowSurface,
+    CeilingSurface, ClosureSurface, Door, DoorSurface, FloorSurface, GroundSurface,
+    InteriorWallSurface, OuterCeilingSurface, OuterFloorSurface, RoofSurface, WallSurface, Window,
+    WindowSurface,
 };
 use crate::model::solitary_vegetation_object::SolitaryVegetationObject;
 use crate::model::transportation::{
@@ -31,6 +33,14 @@ pub trait CityObjectVisitor {
     fn visit_wall_surface(&mut self, v: &WallSurface) -> Self::Result;
     fn visit_window_surface(&mut self, v: &WindowSurface) -> Self::Result;
     fn visit_door_surface(&mut self, v: &DoorSurface) -> Self::Result;
+    fn visit_ceiling_surface(&mut self, v: &CeilingSurface) -> Self::Result;
+    fn visit_floor_surface(&mut self, v: &FloorSurface) -> Self::Result;
+    fn visit_interior_wall_surface(&mut self, v: &InteriorWallSurface) -> Self::Result;
+    fn visit_outer_ceiling_surface(&mut self, v: &OuterCeilingSurface) -> Self::Result;
+    fn visit_outer_floor_surface(&mut self, v: &OuterFloorSurface) -> Self::Result;
+    fn visit_closure_surface(&mut self, v: &ClosureSurface) -> Self::Result;
+    fn visit_window(&mut self, v: &Window) -> Self::Result;
+    fn visit_door(&mut self, v: &Door) -> Self::Result;
 
     fn visit_solitary_vegetation_object(&mut self, v: &SolitaryVegetationObject) -> Self::Result;
 
@@ -59,7 +69,7 @@ impl Interpreter {
 impl CityObjectVisitor for Interpreter {
     type Result = ();
 
-    fn visit_city_model(&mut self, v: &CitygmlModel) -> Self::Result {
+    fn visit_city_model(&mut self, _v: &CitygmlModel) -> Self::Result {
         println!("hello city_model");
     }
 
@@ -111,13 +121,69 @@ impl CityObjectVisitor for Interpreter {
     fn visit_window_surface(&mut self, v: &WindowSurface) -> Self::Result {
         println!(
             "hello window_surface {}",
-            v.occupied_space.space.city_object.abstract_gml.id
+            v.thematic_surface.city_object.abstract_gml.id
         );
     }
 
     fn visit_door_surface(&mut self, v: &DoorSurface) -> Self::Result {
         println!(
             "hello door_surface {}",
+            v.thematic_surface.city_object.abstract_gml.id
+        );
+    }
+
+    fn visit_ceiling_surface(&mut self, v: &CeilingSurface) -> Self::Result {
+        println!(
+            "hello ceiling_surface {}",
+            v.thematic_surface.city_object.abstract_gml.id
+        );
+    }
+
+    fn visit_floor_surface(&mut self, v: &FloorSurface) -> Self::Result {
+        println!(
+            "hello floor_surface {}",
+            v.thematic_surface.city_object.abstract_gml.id
+        );
+    }
+
+    fn visit_interior_wall_surface(&mut self, v: &InteriorWallSurface) -> Self::Result {
+        println!(
+            "hello interior_wall_surface {}",
+            v.thematic_surface.city_object.abstract_gml.id
+        );
+    }
+
+    fn visit_outer_ceiling_surface(&mut self, v: &OuterCeilingSurface) -> Self::Result {
+        println!(
+            "hello outer_ceiling_surface {}",
+            v.thematic_surface.city_object.abstract_gml.id
+        );
+    }
+
+    fn visit_outer_floor_surface(&mut self, v: &OuterFloorSurface) -> Self::Result {
+        println!(
+            "hello outer_floor_surface {}",
+            v.thematic_surface.city_object.abstract_gml.id
+        );
+    }
+
+    fn visit_closure_surface(&mut self, v: &ClosureSurface) -> Self::Result {
+        println!(
+            "hello closure_surface {}",
+            v.thematic_surface.city_object.abstract_gml.id
+        );
+    }
+
+    fn visit_window(&mut self, v: &Window) -> Self::Result {
+        println!(
+            "hello window {}",
+            v.occupied_space.space.city_object.abstract_gml.id
+        );
+    }
+
+    fn visit_door(&mut self, v: &Door) -> Self::Result {
+        println!(
+            "hello door {}",
             v.occupied_space.space.city_object.abstract_gml.id
         );
     }
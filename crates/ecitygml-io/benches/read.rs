@@ -0,0 +1,110 @@
+//! Measures the read throughput on a synthetic dataset of LoD2 buildings.
+//!
+//! The dataset size in megabytes is set with `ECITYGML_BENCH_SIZE_MB` (default: 64), e.g.
+//! `ECITYGML_BENCH_SIZE_MB=1024 cargo bench -p ecitygml-io --bench read` for a 1 GB document.
+//!
+//! The document is written to a temporary file and read from there, so that only the buffered
+//! document and the parsed model are held in memory. The peak memory is printed on Linux.
+
+use ecitygml_io::CitygmlReader;
+use std::time::Instant;
+
+const DEFAULT_SIZE_MB: usize = 64;
+const ITERATIONS: usize = 3;
+
+fn main() {
+    let size_mb: usize = std::env::var("ECITYGML_BENCH_SIZE_MB")
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(DEFAULT_SIZE_MB);
+    let path = std::env::temp_dir().join(format!("ecitygml_bench_{}.gml", std::process::id()));
+    let xml_document = generate_document(size_mb * 1024 * 1024);
+    let document_size_mb = xml_document.len() as f64 / (1024.0 * 1024.0);
+    std::fs::write(&path, xml_document).expect("should work");
+
+    let mut durations = Vec::with_capacity(ITERATIONS);
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        let citygml_model = CitygmlReader::from_path(&path)
+            .expect("should work")
+            .finish()
+            .expect("should work");
+        durations.push(start.elapsed());
+        assert!(!citygml_model.building.is_empty());
+    }
+    std::fs::remove_file(&path).expect("should work");
+
+    let best = durations.iter().min().expect("should contain durations");
+    println!(
+        "read {:.1} MB in {:.3} s (best of {}), {:.1} MB/s",
+        document_size_mb,
+        best.as_secs_f64(),
+        ITERATIONS,
+        document_size_mb / best.as_secs_f64()
+    );
+    if let Some(peak_memory_mb) = peak_memory_mb() {
+        println!("peak memory {peak_memory_mb:.1} MB");
+    }
+}
+
+/// Returns the peak resident memory of the process, which is only available on Linux.
+fn peak_memory_mb() -> Option<f64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let peak_kb: f64 = status
+        .lines()
+        .find_map(|x| x.strip_prefix("VmHWM:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+    Some(peak_kb / 1024.0)
+}
+
+fn generate_document(target_size: usize) -> String {
+    let mut xml_document = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<CityModel xmlns=\"http://www.opengis.net/citygml/3.0\" xmlns:gml=\"http://www.opengis.net/gml/3.2\" xmlns:bldg=\"http://www.opengis.net/citygml/building/3.0\" xmlns:con=\"http://www.opengis.net/citygml/construction/3.0\" xmlns:gen=\"http://www.opengis.net/citygml/generics/3.0\">\n",
+    );
+
+    let mut index: usize = 0;
+    while xml_document.len() < target_size {
+        xml_document.push_str(&generate_building(index));
+        index += 1;
+    }
+    xml_document.push_str("</CityModel>\n");
+
+    xml_document
+}
+
+fn generate_building(index: usize) -> String {
+    let x = (index % 1000) as f64 * 20.0;
+    let y = (index / 1000) as f64 * 20.0;
+
+    let surfaces: String = [
+        ("con:GroundSurface", [(0.0, 0.0, 0.0), (0.0, 10.0, 0.0), (10.0, 10.0, 0.0), (10.0, 0.0, 0.0)]),
+        ("con:WallSurface", [(0.0, 0.0, 0.0), (10.0, 0.0, 0.0), (10.0, 0.0, 8.0), (0.0, 0.0, 8.0)]),
+        ("con:WallSurface", [(10.0, 0.0, 0.0), (10.0, 10.0, 0.0), (10.0, 10.0, 8.0), (10.0, 0.0, 8.0)]),
+        ("con:WallSurface", [(10.0, 10.0, 0.0), (0.0, 10.0, 0.0), (0.0, 10.0, 8.0), (10.0, 10.0, 8.0)]),
+        ("con:WallSurface", [(0.0, 10.0, 0.0), (0.0, 0.0, 0.0), (0.0, 0.0, 8.0), (0.0, 10.0, 8.0)]),
+        ("con:RoofSurface", [(0.0, 0.0, 8.0), (10.0, 0.0, 8.0), (10.0, 10.0, 8.0), (0.0, 10.0, 8.0)]),
+    ]
+    .iter()
+    .enumerate()
+    .map(|(surface_index, (element_name, points))| {
+        let pos_list: String = points
+            .iter()
+            .chain(points.first())
+            .map(|(px, py, pz)| format!("{} {} {} ", x + px, y + py, pz))
+            .collect();
+        format!(
+            "<boundary><{element_name} gml:id=\"building_{index}_surface_{surface_index}\"><lod2MultiSurface><gml:MultiSurface><gml:surfaceMember><gml:Polygon><gml:exterior><gml:LinearRing><gml:posList srsDimension=\"3\">{}</gml:posList></gml:LinearRing></gml:exterior></gml:Polygon></gml:surfaceMember></gml:MultiSurface></lod2MultiSurface></{element_name}></boundary>\n",
+            pos_list.trim_end()
+        )
+    })
+    .collect();
+
+    format!(
+        "<cityObjectMember><bldg:Building gml:id=\"building_{index}\"><gml:name>Building {index}</gml:name><creationDate>2017-01-23T00:00:00Z</creationDate><genericAttribute><gen:StringAttribute><gen:name>source</gen:name><gen:value>synthetic</gen:value></gen:StringAttribute></genericAttribute>\n{surfaces}</bldg:Building></cityObjectMember>\n"
+    )
+}
@@ -0,0 +1,72 @@
+use crate::Error;
+use ecitygml_core::model::ade::AdeProperty;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Parses the properties of an Application Domain Extension (ADE).
+///
+/// Handlers are registered for a namespace URI at the [`CitygmlReader`](crate::CitygmlReader).
+/// Each child element of a city object with this namespace is passed to the handler and the
+/// result is stored in the `ade_extension` map of the city object.
+///
+/// The prefix of a property is resolved against the namespace declarations in scope, that is,
+/// those of the root element, of any enclosing element and of the property element itself.
+///
+/// Only ADE properties of the supported city objects are handled. Features defined by an ADE,
+/// such as a top-level `cityObjectMember` of an ADE class or an ADE feature nested in a
+/// property, are not parsed and skipped or, if unknown content is preserved, kept as raw XML
+/// of the enclosing city object.
+pub trait AdeHandler: Send + Sync {
+    /// Parses a property given as XML fragment including its start and end tag.
+    fn parse_property(
+        &self,
+        element_name: &str,
+        xml_fragment: &str,
+    ) -> Result<Box<dyn AdeProperty>, Error>;
+}
+
+impl<F> AdeHandler for F
+where
+    F: Fn(&str, &str) -> Result<Box<dyn AdeProperty>, Error> + Send + Sync,
+{
+    fn parse_property(
+        &self,
+        element_name: &str,
+        xml_fragment: &str,
+    ) -> Result<Box<dyn AdeProperty>, Error> {
+        self(element_name, xml_fragment)
+    }
+}
+
+/// Registered ADE handlers by namespace URI.
+#[derive(Clone, Default)]
+pub struct AdeHandlerRegistry {
+    handlers: BTreeMap<String, Arc<dyn AdeHandler>>,
+}
+
+impl AdeHandlerRegistry {
+    pub fn new() -> Self {
+        Self {
+            handlers: BTreeMap::new(),
+        }
+    }
+
+    pub fn register(&mut self, namespace: impl Into<String>, handler: impl AdeHandler + 'static) {
+        self.handlers.insert(namespace.into(), Arc::new(handler));
+    }
+
+    pub fn get(&self, namespace: &str) -> Option<&Arc<dyn AdeHandler>> {
+        self.handlers.get(namespace)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+}
+
+impl fmt::Debug for AdeHandlerRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.handlers.keys()).finish()
+    }
+}
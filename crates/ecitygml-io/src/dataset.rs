@@ -0,0 +1,422 @@
+use crate::ade::AdeHandler;
+use crate::error::Error;
+use crate::progress::{CancellationToken, ProgressCallback, ReadProgress};
+use crate::read::CitygmlReader;
+use crate::read_impl::ReadOptions;
+use crate::selection::FeatureSelection;
+use crate::source::{FileSource, SourceFormat, decompress_gzip, is_citygml_document_path};
+use crate::validate::{Report, ValidateOptions, ValidationProfile};
+use crate::validate_impl::{compile_dataset_reports, extract_information};
+use ecitygml_core::model::city_model::CitygmlModel;
+use itertools::Itertools;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+use walkdir::WalkDir;
+
+/// `CitygmlDatasetReader` reads datasets consisting of multiple CityGML documents.
+///
+/// Each source can be a directory, a zip archive or a single (gzip compressed) document.
+/// All contained documents are read in the order of their names and merged into one model.
+#[derive(Debug, Clone)]
+pub struct CitygmlDatasetReader {
+    paths: Vec<PathBuf>,
+    options: ReadOptions,
+    validate_options: ValidateOptions,
+}
+
+impl CitygmlDatasetReader {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::from_paths([path])
+    }
+
+    /// Creates a reader for a dataset spread over multiple sources, which are read in the given
+    /// order.
+    pub fn from_paths(paths: impl IntoIterator<Item = impl AsRef<Path>>) -> Result<Self, Error> {
+        let paths: Vec<PathBuf> = paths
+            .into_iter()
+            .map(|x| x.as_ref().to_path_buf())
+            .collect();
+        for path in paths.iter().filter(|x| !x.is_dir()) {
+            SourceFormat::from_path(path)?;
+        }
+
+        Ok(Self {
+            paths,
+            options: ReadOptions::default(),
+            validate_options: ValidateOptions::default(),
+        })
+    }
+
+    /// Registers a handler for the properties of an ADE with the given namespace URI.
+    pub fn with_ade_handler(
+        mut self,
+        namespace: impl Into<String>,
+        handler: impl AdeHandler + 'static,
+    ) -> Self {
+        self.options
+            .ade_handler_registry
+            .register(namespace, handler);
+        self
+    }
+
+    /// See [`CitygmlReader::with_preserve_unknown_content`].
+    pub fn with_preserve_unknown_content(mut self, preserve_unknown_content: bool) -> Self {
+        self.options.preserve_unknown_content = preserve_unknown_content;
+        self
+    }
+
+    /// Restricts reading to the selected features of each document.
+    pub fn with_feature_selection(mut self, feature_selection: FeatureSelection) -> Self {
+        self.options.feature_selection = feature_selection;
+        self
+    }
+
+    /// Calls `progress_callback` while a document is buffered and after each parsed top-level
+    /// feature.
+    ///
+    /// The progress refers to the document currently being read.
+    pub fn with_progress_callback(
+        mut self,
+        progress_callback: impl Fn(&ReadProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.options.progress_callback = Some(ProgressCallback::new(progress_callback));
+        self
+    }
+
+    /// Aborts reading with [`Error::Cancelled`], once the token is cancelled.
+    ///
+    /// The token is checked between the documents and between the features of each document.
+    pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.options.cancellation_token = Some(cancellation_token);
+        self
+    }
+
+    /// See [`CitygmlReader::with_structure_validation`].
+    pub fn with_structure_validation(mut self, structure_validation: bool) -> Self {
+        self.validate_options.structure_validation = structure_validation;
+        self
+    }
+
+    /// See [`CitygmlReader::with_geometry_validation`].
+    pub fn with_geometry_validation(mut self, geometry_validation: bool) -> Self {
+        self.validate_options.geometry_validation = geometry_validation;
+        self
+    }
+
+    /// See [`CitygmlReader::with_semantic_validation`].
+    pub fn with_semantic_validation(mut self, semantic_validation: bool) -> Self {
+        self.validate_options.semantic_validation = semantic_validation;
+        self
+    }
+
+    /// See [`CitygmlReader::with_topology_validation`].
+    ///
+    /// Links to traffic spaces in other documents are not checked.
+    pub fn with_topology_validation(mut self, topology_validation: bool) -> Self {
+        self.validate_options.topology_validation = topology_validation;
+        self
+    }
+
+    /// See [`CitygmlReader::with_profile`].
+    pub fn with_profile(mut self, profile: ValidationProfile) -> Self {
+        self.validate_options = ValidateOptions::from_profile(profile);
+        self
+    }
+
+    /// See [`CitygmlReader::with_validate_options`].
+    pub fn with_validate_options(mut self, validate_options: ValidateOptions) -> Self {
+        self.validate_options = validate_options;
+        self
+    }
+
+    pub fn finish(self) -> Result<CitygmlModel, Error> {
+        let mut citygml_models = Vec::new();
+        self.for_each_document(|name, reader| {
+            info!("Reading document: {name}");
+            let citygml_model = CitygmlReader::new(reader)
+                .with_options(self.options.clone())
+                .finish()?;
+            citygml_models.push(citygml_model);
+            Ok(())
+        })?;
+
+        if citygml_models.is_empty() {
+            return Err(Error::NoCitygmlDocuments(self.display_paths()));
+        }
+        Ok(CitygmlModel::from_citygml_models(&citygml_models))
+    }
+
+    /// Validates each document and returns a report per document.
+    ///
+    /// Predecessor, successor and object relation hrefs are resolved against the gml:ids of all
+    /// documents, so that references to features in other tiles are listed as cross-file
+    /// references instead of being reported as broken.
+    pub fn validate(self) -> Result<Vec<Report>, Error> {
+        let mut documents = Vec::new();
+        self.for_each_document(|name, reader| {
+            info!("Validating document: {name}");
+            let extracted_information =
+                extract_information(reader, &self.validate_options, &self.options)?;
+            documents.push((name.to_string(), extracted_information));
+            Ok(())
+        })?;
+
+        if documents.is_empty() {
+            return Err(Error::NoCitygmlDocuments(self.display_paths()));
+        }
+        Ok(compile_dataset_reports(
+            documents,
+            &self.validate_options.profile,
+        ))
+    }
+
+    fn display_paths(&self) -> String {
+        self.paths.iter().map(|x| x.display()).join(", ")
+    }
+
+    /// Calls `f` with the name and content of each document in the order of the sources.
+    fn for_each_document(
+        &self,
+        mut f: impl FnMut(&str, &mut dyn Read) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        for path in &self.paths {
+            if path.is_dir() {
+                self.for_each_directory_document(path, &mut f)?;
+                continue;
+            }
+
+            match SourceFormat::from_path(path)? {
+                SourceFormat::Zip => self.for_each_archive_document(path, &mut f)?,
+                SourceFormat::Citygml | SourceFormat::GzipCitygml => {
+                    self.options.check_cancelled()?;
+                    f(&path.display().to_string(), &mut FileSource::open(path)?)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn for_each_directory_document(
+        &self,
+        path: &Path,
+        f: &mut impl FnMut(&str, &mut dyn Read) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        for entry in WalkDir::new(path).sort_by_file_name() {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            if is_citygml_document_path(entry.path()) {
+                self.options.check_cancelled()?;
+                f(
+                    &entry.path().display().to_string(),
+                    &mut FileSource::open(entry.path())?,
+                )?;
+            } else {
+                info!("Skipping non-CityGML file: {}", entry.path().display());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn for_each_archive_document(
+        &self,
+        path: &Path,
+        f: &mut impl FnMut(&str, &mut dyn Read) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let mut archive = zip::ZipArchive::new(File::open(path)?)?;
+        let mut entry_names: Vec<String> = archive
+            .file_names()
+            .filter(|x| !x.ends_with('/'))
+            .map(|x| x.to_string())
+            .collect();
+        entry_names.sort();
+
+        for entry_name in entry_names {
+            let source_format = match SourceFormat::from_path(&entry_name) {
+                Ok(SourceFormat::Zip) => {
+                    warn!("Skipping nested zip archive: {}", entry_name);
+                    continue;
+                }
+                Ok(x) => x,
+                Err(_) => {
+                    info!("Skipping non-CityGML entry: {}", entry_name);
+                    continue;
+                }
+            };
+
+            self.options.check_cancelled()?;
+            let mut content = Vec::new();
+            archive.by_name(&entry_name)?.read_to_end(&mut content)?;
+            if source_format == SourceFormat::GzipCitygml {
+                content = decompress_gzip(content.as_slice())?;
+            }
+
+            f(
+                &format!("{}/{}", path.display(), entry_name),
+                &mut content.as_slice(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::report::{CrossFileReference, ReferenceKind};
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn document(id: &str) -> String {
+        format!(
+            "<CityModel xmlns:gml=\"http://www.opengis.net/gml/3.2\" xmlns:frn=\"http://www.opengis.net/citygml/cityfurniture/3.0\"><cityObjectMember><frn:CityFurniture gml:id=\"{id}\"><gml:name>{id}</gml:name></frn:CityFurniture></cityObjectMember></CityModel>"
+        )
+    }
+
+    fn gzip(content: &str) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(content.as_bytes()).expect("should work");
+        encoder.finish().expect("should work")
+    }
+
+    fn ids(citygml_model: &CitygmlModel) -> Vec<String> {
+        citygml_model
+            .city_furniture
+            .iter()
+            .map(|x| {
+                x.occupied_space
+                    .space
+                    .city_object
+                    .abstract_gml
+                    .id
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_read_directory_zip_and_gzip_sources() {
+        let directory_path =
+            std::env::temp_dir().join(format!("ecitygml-dataset-test-{}", std::process::id()));
+        std::fs::create_dir_all(&directory_path).expect("should work");
+
+        let tiles_path = directory_path.join("tiles");
+        std::fs::create_dir_all(&tiles_path).expect("should work");
+        std::fs::write(tiles_path.join("a.gml"), document("tile_a")).expect("should work");
+        std::fs::write(tiles_path.join("b.gml.gz"), gzip(&document("tile_b")))
+            .expect("should work");
+        std::fs::write(tiles_path.join("readme.txt"), "no CityGML").expect("should work");
+
+        let archive_path = directory_path.join("tiles.zip");
+        let mut zip_writer = zip::ZipWriter::new(File::create(&archive_path).expect("should work"));
+        zip_writer
+            .start_file("d.gml.gz", SimpleFileOptions::default())
+            .expect("should work");
+        zip_writer
+            .write_all(&gzip(&document("tile_d")))
+            .expect("should work");
+        zip_writer
+            .start_file("c.xml", SimpleFileOptions::default())
+            .expect("should work");
+        zip_writer
+            .write_all(document("tile_c").as_bytes())
+            .expect("should work");
+        zip_writer.finish().expect("should work");
+
+        let citygml_model = CitygmlDatasetReader::from_path(&tiles_path)
+            .expect("should work")
+            .finish()
+            .expect("should work");
+        assert_eq!(ids(&citygml_model), vec!["tile_a", "tile_b"]);
+
+        let citygml_model = CitygmlDatasetReader::from_path(&archive_path)
+            .expect("should work")
+            .finish()
+            .expect("should work");
+        assert_eq!(ids(&citygml_model), vec!["tile_c", "tile_d"]);
+
+        let citygml_model = CitygmlReader::from_path(tiles_path.join("b.gml.gz"))
+            .expect("should work")
+            .finish()
+            .expect("should work");
+        assert_eq!(ids(&citygml_model), vec!["tile_b"]);
+
+        assert!(matches!(
+            CitygmlReader::from_path(&archive_path),
+            Err(Error::ArchiveNotSupported(_))
+        ));
+        let empty_path = directory_path.join("empty");
+        std::fs::create_dir_all(&empty_path).expect("should work");
+        assert!(matches!(
+            CitygmlDatasetReader::from_path(&empty_path)
+                .expect("should work")
+                .finish(),
+            Err(Error::NoCitygmlDocuments(_))
+        ));
+
+        std::fs::remove_dir_all(&directory_path).expect("should work");
+    }
+
+    fn road_document(id: &str, predecessor: &str, successor: &str) -> String {
+        format!(
+            "<CityModel xmlns:gml=\"http://www.opengis.net/gml/3.2\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" xmlns:tran=\"http://www.opengis.net/citygml/transportation/3.0\"><cityObjectMember><tran:Road gml:id=\"{id}\"><tran:predecessor xlink:href=\"{predecessor}\"/><tran:successor xlink:href=\"{successor}\"/></tran:Road></cityObjectMember></CityModel>"
+        )
+    }
+
+    #[test]
+    fn test_validate_cross_file_references() {
+        let directory_path = std::env::temp_dir().join(format!(
+            "ecitygml-dataset-validate-test-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&directory_path).expect("should work");
+        let tile_a_path = directory_path.join("a.gml");
+        let tile_b_path = directory_path.join("b.gml");
+        std::fs::write(&tile_a_path, road_document("road_a", "#missing", "#road_b"))
+            .expect("should work");
+        std::fs::write(
+            &tile_b_path,
+            road_document("road_b", "a.gml#road_a", "#road_b"),
+        )
+        .expect("should work");
+
+        let reports = CitygmlDatasetReader::from_paths([&tile_a_path, &tile_b_path])
+            .expect("should work")
+            .validate()
+            .expect("should work");
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].broken_predecessor_hrefs, vec!["#missing"]);
+        assert!(reports[0].broken_successor_hrefs.is_empty());
+        assert_eq!(
+            reports[0].cross_file_references,
+            vec![CrossFileReference {
+                kind: ReferenceKind::Successor,
+                href: "#road_b".to_string(),
+                document: tile_b_path.display().to_string(),
+            }]
+        );
+        assert!(reports[1].broken_predecessor_hrefs.is_empty());
+        assert_eq!(reports[1].cross_file_references.len(), 1);
+        assert_eq!(
+            reports[1].source.as_deref(),
+            Some(tile_b_path.display().to_string().as_str())
+        );
+
+        let report = CitygmlReader::from_path(&tile_a_path)
+            .expect("should work")
+            .validate()
+            .expect("should work");
+        assert_eq!(report.broken_successor_hrefs, vec!["#road_b"]);
+
+        std::fs::remove_dir_all(&directory_path).expect("should work");
+    }
+}
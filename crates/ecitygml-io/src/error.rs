@@ -11,7 +11,19 @@ pub enum Error {
     #[error(transparent)]
     QuickXmlSeError(#[from] quick_xml::SeError),
     #[error(transparent)]
+    EcitygmlError(#[from] ecitygml_core::Error),
+    #[error(transparent)]
     GmlIoError(#[from] egml::io::Error),
+    #[error(transparent)]
+    ChronoParseError(#[from] chrono::ParseError),
+    #[error(transparent)]
+    ZipError(#[from] zip::result::ZipError),
+    #[error(transparent)]
+    WalkdirError(#[from] walkdir::Error),
+    #[error(transparent)]
+    SerdeYamlError(#[from] serde_yaml::Error),
+    #[error(transparent)]
+    SerdeJsonError(#[from] serde_json::Error),
 
     #[error("file extension is invalid")]
     NoFileExtension(),
@@ -21,4 +33,18 @@ pub enum Error {
     ElementNotFound(String),
     #[error("attribute has no name: `{0}")]
     AttributeWithoutName(String),
+    #[error("gzip content is invalid: {0}")]
+    InvalidGzipContent(String),
+    #[error("`{0}` contains no CityGML documents")]
+    NoCitygmlDocuments(String),
+    #[error("report format `{0}` is unknown")]
+    UnknownReportFormat(String),
+    #[error("validation rule `{0}` is unknown")]
+    UnknownValidationRule(String),
+    #[error("validation rule `{0}` has no parameter `{1}`")]
+    UnknownValidationRuleParameter(String, String),
+    #[error("reading was cancelled")]
+    Cancelled(),
+    #[error("`{0}` is a zip archive with multiple documents, use `CitygmlDatasetReader` instead")]
+    ArchiveNotSupported(String),
 }
@@ -1,15 +1,40 @@
+pub mod ade;
+mod dataset;
 mod error;
 mod parser;
+mod progress;
 mod read;
 mod read_impl;
+mod selection;
+mod source;
 pub mod validate;
 mod validate_impl;
+mod write;
+mod write_impl;
+mod writer;
 
 #[doc(inline)]
 pub use crate::read::CitygmlReader;
 
+#[doc(inline)]
+pub use crate::dataset::CitygmlDatasetReader;
+
+#[doc(inline)]
+pub use crate::progress::{CancellationToken, ProgressCallback, ReadProgress, ReadStage};
+
+#[doc(inline)]
+pub use crate::selection::FeatureSelection;
+
+#[doc(inline)]
+pub use crate::source::{FileSource, is_citygml_document_path};
+
+#[doc(inline)]
+pub use crate::write::CitygmlWriter;
+
 #[doc(inline)]
 pub use crate::error::Error;
 
 pub const FILE_EXTENSION_CITYGML_GML_FORMAT: &str = "gml";
 pub const FILE_EXTENSION_CITYGML_XML_FORMAT: &str = "xml";
+pub const FILE_EXTENSION_GZIP_FORMAT: &str = "gz";
+pub const FILE_EXTENSION_ZIP_FORMAT: &str = "zip";
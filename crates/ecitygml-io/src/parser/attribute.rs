@@ -1,5 +1,6 @@
 use crate::Error;
 use crate::Error::AttributeWithoutName;
+use chrono::{FixedOffset, NaiveDate};
 use ecitygml_core::model;
 use quick_xml::de;
 use serde::{Deserialize, Serialize};
@@ -67,15 +68,189 @@ impl TryFrom<DoubleAttribute> for model::core::DoubleAttribute {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+struct DateAttribute {
+    pub name: String,
+    pub value: String,
+}
+
+impl TryFrom<DateAttribute> for model::core::DateAttribute {
+    type Error = Error;
+
+    fn try_from(value: DateAttribute) -> Result<Self, Self::Error> {
+        if value.name.is_empty() {
+            return Err(AttributeWithoutName("date attribute".to_string()));
+        }
+
+        let (date, timezone) = parse_date(value.value.trim())?;
+        Ok(model::core::DateAttribute {
+            name: value.name,
+            value: date,
+            timezone,
+        })
+    }
+}
+
+/// Parses an xs:date value with an optional timezone, such as `2020-01-01`, `2020-01-01Z` or
+/// `2020-01-01+01:00`.
+pub(crate) fn parse_date(value: &str) -> Result<(NaiveDate, Option<FixedOffset>), Error> {
+    let (date, timezone) = match value.strip_suffix('Z') {
+        Some(date) => (
+            date,
+            Some(FixedOffset::east_opt(0).expect("should be in range")),
+        ),
+        None => match value
+            .len()
+            .checked_sub(6)
+            .and_then(|x| value.split_at_checked(x))
+        {
+            Some((date, timezone)) if timezone.starts_with(['+', '-']) && date.len() >= 10 => {
+                (date, Some(timezone.parse::<FixedOffset>()?))
+            }
+            _ => (value, None),
+        },
+    };
+
+    Ok((NaiveDate::parse_from_str(date, "%Y-%m-%d")?, timezone))
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+struct UriAttribute {
+    pub name: String,
+    pub value: String,
+}
+
+impl TryFrom<UriAttribute> for model::core::UriAttribute {
+    type Error = Error;
+
+    fn try_from(value: UriAttribute) -> Result<Self, Self::Error> {
+        if value.name.is_empty() {
+            return Err(AttributeWithoutName("uri attribute".to_string()));
+        }
+
+        Ok(model::core::UriAttribute {
+            name: value.name,
+            value: value.value,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct MeasureAttribute {
+    pub name: String,
+    pub value: MeasureValue,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct MeasureValue {
+    #[serde(rename = "@uom", default)]
+    pub uom: String,
+    #[serde(rename = "$text")]
+    pub value: f64,
+}
+
+impl TryFrom<MeasureAttribute> for model::core::MeasureAttribute {
+    type Error = Error;
+
+    fn try_from(value: MeasureAttribute) -> Result<Self, Self::Error> {
+        if value.name.is_empty() {
+            return Err(AttributeWithoutName("measure attribute".to_string()));
+        }
+
+        Ok(model::core::MeasureAttribute {
+            name: value.name,
+            value: value.value.value,
+            uom: value.value.uom,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+struct CodeAttribute {
+    pub name: String,
+    pub value: CodeValue,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+struct CodeValue {
+    #[serde(rename = "@codeSpace", default)]
+    pub code_space: Option<String>,
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+
+impl TryFrom<CodeAttribute> for model::core::CodeAttribute {
+    type Error = Error;
+
+    fn try_from(value: CodeAttribute) -> Result<Self, Self::Error> {
+        if value.name.is_empty() {
+            return Err(AttributeWithoutName("code attribute".to_string()));
+        }
+
+        Ok(model::core::CodeAttribute {
+            name: value.name,
+            value: value.value.value,
+            code_space: value.value.code_space,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct GenericAttributeSet {
+    pub name: String,
+    #[serde(rename = "codeSpace", default)]
+    pub code_space: Option<String>,
+    #[serde(rename = "genericAttribute", default)]
+    pub generic_attribute: Vec<GenericAttributeProperty>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct GenericAttributeProperty {
+    #[serde(rename = "$value")]
+    pub generic_attribute: GenericAttribute,
+}
+
+impl TryFrom<GenericAttributeSet> for model::core::GenericAttributeSet {
+    type Error = Error;
+
+    fn try_from(value: GenericAttributeSet) -> Result<Self, Self::Error> {
+        if value.name.is_empty() {
+            return Err(AttributeWithoutName("generic attribute set".to_string()));
+        }
+
+        let generic_attribute = value
+            .generic_attribute
+            .into_iter()
+            .map(|x| model::core::GenericAttribute::try_from(x.generic_attribute))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(model::core::GenericAttributeSet {
+            name: value.name,
+            code_space: value.code_space,
+            generic_attribute,
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-pub enum GenericAttribute {
+enum GenericAttribute {
     #[serde(rename = "StringAttribute")]
     String(StringAttribute),
     #[serde(rename = "IntAttribute")]
     Int(IntAttribute),
     #[serde(rename = "DoubleAttribute")]
     Double(DoubleAttribute),
+    #[serde(rename = "DateAttribute")]
+    Date(DateAttribute),
+    #[serde(rename = "UriAttribute")]
+    Uri(UriAttribute),
+    #[serde(rename = "MeasureAttribute")]
+    Measure(MeasureAttribute),
+    #[serde(rename = "CodeAttribute")]
+    Code(CodeAttribute),
+    #[serde(rename = "GenericAttributeSet")]
+    Set(GenericAttributeSet),
 }
 
 impl TryFrom<GenericAttribute> for model::core::GenericAttribute {
@@ -95,13 +270,31 @@ impl TryFrom<GenericAttribute> for model::core::GenericAttribute {
                 let core_attr = model::core::DoubleAttribute::try_from(double_attr)?;
                 Ok(model::core::GenericAttribute::Double(core_attr))
             }
+            GenericAttribute::Date(date_attr) => {
+                let core_attr = model::core::DateAttribute::try_from(date_attr)?;
+                Ok(model::core::GenericAttribute::Date(core_attr))
+            }
+            GenericAttribute::Uri(uri_attr) => {
+                let core_attr = model::core::UriAttribute::try_from(uri_attr)?;
+                Ok(model::core::GenericAttribute::Uri(core_attr))
+            }
+            GenericAttribute::Measure(measure_attr) => {
+                let core_attr = model::core::MeasureAttribute::try_from(measure_attr)?;
+                Ok(model::core::GenericAttribute::Measure(core_attr))
+            }
+            GenericAttribute::Code(code_attr) => {
+                let core_attr = model::core::CodeAttribute::try_from(code_attr)?;
+                Ok(model::core::GenericAttribute::Code(core_attr))
+            }
+            GenericAttribute::Set(set_attr) => {
+                let core_attr = model::core::GenericAttributeSet::try_from(set_attr)?;
+                Ok(model::core::GenericAttribute::Set(core_attr))
+            }
         }
     }
 }
 
-pub fn parse_generic_attribute(
-    xml_document: &String,
-) -> Result<model::core::GenericAttribute, Error> {
+pub fn parse_generic_attribute(xml_document: &str) -> Result<model::core::GenericAttribute, Error> {
     let parsed_attribute: GenericAttribute = de::from_str(xml_document)?;
     let attribute = model::core::GenericAttribute::try_from(parsed_attribute)?;
 
@@ -189,4 +382,153 @@ mod tests {
             other => panic!("Expected Double attribute, got {:?}", other),
         }
     }
+
+    #[test]
+    fn test_parse_date_attribute_basic() {
+        let xml_document = String::from(
+            "<gen:DateAttribute>
+          <gen:name>attribute_name</gen:name>
+          <gen:value>2023-04-17</gen:value>
+        </gen:DateAttribute>",
+        );
+
+        let generic_attribute = parse_generic_attribute(&xml_document).expect("should work");
+
+        let date_attribute = generic_attribute.as_date().expect("must be date");
+        assert_eq!(date_attribute.name, "attribute_name");
+        assert_eq!(
+            date_attribute.value,
+            NaiveDate::from_ymd_opt(2023, 4, 17).expect("should work")
+        );
+        assert_eq!(date_attribute.timezone, None);
+    }
+
+    #[test]
+    fn test_parse_date_attribute_with_timezone() {
+        let xml_document = String::from(
+            "<gen:DateAttribute>
+          <gen:name>attribute_name</gen:name>
+          <gen:value>2020-01-01+01:00</gen:value>
+        </gen:DateAttribute>",
+        );
+
+        let generic_attribute = parse_generic_attribute(&xml_document).expect("should work");
+
+        let date_attribute = generic_attribute.as_date().expect("must be date");
+        assert_eq!(
+            date_attribute.value,
+            NaiveDate::from_ymd_opt(2020, 1, 1).expect("should work")
+        );
+        assert_eq!(date_attribute.timezone, FixedOffset::east_opt(3600));
+    }
+
+    #[test]
+    fn test_parse_date() {
+        let date = NaiveDate::from_ymd_opt(2020, 1, 1).expect("should work");
+
+        assert_eq!(
+            parse_date("2020-01-01Z").expect("should work"),
+            (date, FixedOffset::east_opt(0))
+        );
+        assert_eq!(
+            parse_date("2020-01-01-05:30").expect("should work"),
+            (date, FixedOffset::west_opt(5 * 3600 + 30 * 60))
+        );
+        assert!(parse_date("2020-01-01+1").is_err());
+        assert!(parse_date("2020-13-01").is_err());
+    }
+
+    #[test]
+    fn test_parse_uri_attribute_basic() {
+        let xml_document = String::from(
+            "<gen:UriAttribute>
+          <gen:name>attribute_name</gen:name>
+          <gen:value>https://example.com/parcel/42</gen:value>
+        </gen:UriAttribute>",
+        );
+
+        let generic_attribute = parse_generic_attribute(&xml_document).expect("should work");
+
+        let uri_attribute = generic_attribute.as_uri().expect("must be uri");
+        assert_eq!(uri_attribute.name, "attribute_name");
+        assert_eq!(uri_attribute.value, "https://example.com/parcel/42");
+    }
+
+    #[test]
+    fn test_parse_measure_attribute_basic() {
+        let xml_document = String::from(
+            "<gen:MeasureAttribute>
+          <gen:name>attribute_name</gen:name>
+          <gen:value uom=\"urn:adv:uom:m\">12.5</gen:value>
+        </gen:MeasureAttribute>",
+        );
+
+        let generic_attribute = parse_generic_attribute(&xml_document).expect("should work");
+
+        let measure_attribute = generic_attribute.as_measure().expect("must be measure");
+        assert_eq!(measure_attribute.name, "attribute_name");
+        assert_eq!(measure_attribute.value, 12.5);
+        assert_eq!(measure_attribute.uom, "urn:adv:uom:m");
+    }
+
+    #[test]
+    fn test_parse_code_attribute_basic() {
+        let xml_document = String::from(
+            "<gen:CodeAttribute>
+          <gen:name>attribute_name</gen:name>
+          <gen:value codeSpace=\"http://example.com/codelist\">1000</gen:value>
+        </gen:CodeAttribute>",
+        );
+
+        let generic_attribute = parse_generic_attribute(&xml_document).expect("should work");
+
+        let code_attribute = generic_attribute.as_code().expect("must be code");
+        assert_eq!(code_attribute.name, "attribute_name");
+        assert_eq!(code_attribute.value, "1000");
+        assert_eq!(
+            code_attribute.code_space.as_deref(),
+            Some("http://example.com/codelist")
+        );
+    }
+
+    #[test]
+    fn test_parse_generic_attribute_set_nested() {
+        let xml_document = String::from(
+            "<gen:GenericAttributeSet>
+          <gen:name>set_name</gen:name>
+          <gen:genericAttribute>
+            <gen:StringAttribute>
+              <gen:name>attribute_name_one</gen:name>
+              <gen:value>value</gen:value>
+            </gen:StringAttribute>
+          </gen:genericAttribute>
+          <gen:genericAttribute>
+            <gen:GenericAttributeSet>
+              <gen:name>inner_set_name</gen:name>
+              <gen:genericAttribute>
+                <gen:IntAttribute>
+                  <gen:name>attribute_name_two</gen:name>
+                  <gen:value>3</gen:value>
+                </gen:IntAttribute>
+              </gen:genericAttribute>
+            </gen:GenericAttributeSet>
+          </gen:genericAttribute>
+        </gen:GenericAttributeSet>",
+        );
+
+        let generic_attribute = parse_generic_attribute(&xml_document).expect("should work");
+
+        let set = generic_attribute.as_set().expect("must be set");
+        assert_eq!(set.name, "set_name");
+        assert_eq!(set.generic_attribute.len(), 2);
+        let inner_set = set.generic_attribute[1].as_set().expect("must be set");
+        assert_eq!(inner_set.name, "inner_set_name");
+        assert_eq!(
+            inner_set.generic_attribute[0]
+                .as_int()
+                .expect("must be int")
+                .value,
+            3
+        );
+    }
 }
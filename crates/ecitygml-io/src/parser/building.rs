@@ -1,132 +1,475 @@
 use crate::Error;
-use crate::parser::space::{parse_occupied_space, parse_thematic_surface};
-use crate::parser::util::extract_xml_element_attributes;
+use crate::parser::context::ParseContext;
+use crate::parser::feature::{
+    FeatureParser, parse_feature, parse_feature_member, parse_property_members,
+};
 use ecitygml_core::model::building::{Building, BuildingConstructiveElement};
 use ecitygml_core::model::construction::{
-    DoorSurface, GroundSurface, RoofSurface, WallSurface, WindowSurface,
+    CeilingSurface, ClosureSurface, Door, DoorSurface, FloorSurface, GroundSurface,
+    InteriorWallSurface, OuterCeilingSurface, OuterFloorSurface, RoofSurface, WallSurface, Window,
+    WindowSurface,
 };
+use ecitygml_core::model::core::{CityObject, OccupiedSpace, ThematicSurface};
 use egml::model::base::Id;
 use quick_xml::Reader;
-use quick_xml::events::Event;
-use std::collections::HashMap;
-
-pub fn parse_building(id: &Id, xml_document: &String) -> Result<Building, Error> {
-    let occupied_space = parse_occupied_space(id, xml_document)?;
-    let mut building = Building::new(occupied_space);
-
-    let mut reader = Reader::from_str(xml_document.as_str());
-    reader.config_mut().trim_text(true);
-
-    let mut txt = Vec::new();
-    let mut buf = Vec::new();
-
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => {
-                let extracted_attributes: HashMap<String, String> =
-                    extract_xml_element_attributes(&reader, &e);
-                let id: Option<Id> = extracted_attributes
-                    .get("id")
-                    .and_then(|x| Id::try_from(x.as_str()).ok());
-
-                match e.name().as_ref() {
-                    b"con:GroundSurface" => {
-                        let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                        let id: Id = id.unwrap_or(Id::from_hashed_string(&xml_snippet));
-
-                        let thematic_surface = parse_thematic_surface(&id, &xml_snippet)?;
-                        let ground_surface = GroundSurface::new(thematic_surface);
-
-                        building.ground_surface.push(ground_surface);
-                    }
-                    b"bldg:BuildingConstructiveElement" => {
-                        let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                        let id: Id = id.unwrap_or(Id::from_hashed_string(&xml_snippet));
-
-                        let thematic_surface = parse_occupied_space(&id, &xml_snippet)?;
-                        let building_constructive_element =
-                            BuildingConstructiveElement::new(thematic_surface);
-
-                        building
-                            .building_constructive_element
-                            .push(building_constructive_element);
-                    }
-                    b"con:RoofSurface" => {
-                        let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                        let id: Id = id.unwrap_or(Id::from_hashed_string(&xml_snippet));
-
-                        let thematic_surface = parse_thematic_surface(&id, &xml_snippet)?;
-                        let roof_surface = RoofSurface::new(thematic_surface);
-
-                        building.roof_surface.push(roof_surface);
-                    }
-                    b"con:WallSurface" => {
-                        let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                        let id: Id = id.unwrap_or(Id::from_hashed_string(&xml_snippet));
-
-                        let wall_surface = parse_wall_surface(&id, &xml_snippet)?;
-                        building.wall_surface.push(wall_surface);
-                    }
-                    _ => {}
+use quick_xml::events::BytesStart;
+
+impl FeatureParser for Building {
+    fn from_city_object(city_object: CityObject) -> Self {
+        Building::new(OccupiedSpace::from_city_object(city_object))
+    }
+
+    fn city_object_mut(&mut self) -> &mut CityObject {
+        self.occupied_space.city_object_mut()
+    }
+
+    fn parse_property<'a>(
+        &mut self,
+        id: &Id,
+        reader: &mut Reader<&'a [u8]>,
+        start: &BytesStart<'a>,
+        context: &ParseContext,
+    ) -> Result<bool, Error> {
+        match start.name().as_ref() {
+            b"boundary" | b"bldg:buildingConstructiveElement" => {
+                parse_property_members(reader, start, |reader, member| {
+                    parse_building_member(self, reader, member, context)
+                })?;
+                Ok(true)
+            }
+            _ => {
+                if parse_building_member(self, reader, start, context)? {
+                    return Ok(true);
                 }
+                self.occupied_space
+                    .parse_property(id, reader, start, context)
             }
-            Ok(Event::Eof) => break,
-            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
-            Ok(Event::Text(e)) => txt.push(e.decode().unwrap().into_owned()),
-            _ => (),
         }
     }
+}
 
-    Ok(building)
+fn parse_building_member<'a>(
+    building: &mut Building,
+    reader: &mut Reader<&'a [u8]>,
+    start: &BytesStart<'a>,
+    context: &ParseContext,
+) -> Result<bool, Error> {
+    match start.name().as_ref() {
+        b"con:GroundSurface" => {
+            let thematic_surface: ThematicSurface = parse_feature_member(reader, start, context)?;
+            building
+                .ground_surface
+                .push(GroundSurface::new(thematic_surface));
+        }
+        b"bldg:BuildingConstructiveElement" => {
+            let building_constructive_element = parse_feature_member(reader, start, context)?;
+            building
+                .building_constructive_element
+                .push(building_constructive_element);
+        }
+        b"con:RoofSurface" => {
+            let thematic_surface: ThematicSurface = parse_feature_member(reader, start, context)?;
+            building
+                .roof_surface
+                .push(RoofSurface::new(thematic_surface));
+        }
+        b"con:WallSurface" => {
+            let wall_surface = parse_feature_member(reader, start, context)?;
+            building.wall_surface.push(wall_surface);
+        }
+        b"con:CeilingSurface" => {
+            let thematic_surface: ThematicSurface = parse_feature_member(reader, start, context)?;
+            building
+                .ceiling_surface
+                .push(CeilingSurface::new(thematic_surface));
+        }
+        b"con:FloorSurface" => {
+            let thematic_surface: ThematicSurface = parse_feature_member(reader, start, context)?;
+            building
+                .floor_surface
+                .push(FloorSurface::new(thematic_surface));
+        }
+        b"con:InteriorWallSurface" => {
+            let interior_wall_surface = parse_feature_member(reader, start, context)?;
+            building.interior_wall_surface.push(interior_wall_surface);
+        }
+        b"con:OuterCeilingSurface" => {
+            let thematic_surface: ThematicSurface = parse_feature_member(reader, start, context)?;
+            building
+                .outer_ceiling_surface
+                .push(OuterCeilingSurface::new(thematic_surface));
+        }
+        b"con:OuterFloorSurface" => {
+            let thematic_surface: ThematicSurface = parse_feature_member(reader, start, context)?;
+            building
+                .outer_floor_surface
+                .push(OuterFloorSurface::new(thematic_surface));
+        }
+        // the core module is the default namespace of documents written by this crate
+        b"core:ClosureSurface" | b"ClosureSurface" => {
+            let thematic_surface: ThematicSurface = parse_feature_member(reader, start, context)?;
+            building
+                .closure_surface
+                .push(ClosureSurface::new(thematic_surface));
+        }
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+impl FeatureParser for BuildingConstructiveElement {
+    fn from_city_object(city_object: CityObject) -> Self {
+        BuildingConstructiveElement::new(OccupiedSpace::from_city_object(city_object))
+    }
+
+    fn city_object_mut(&mut self) -> &mut CityObject {
+        self.occupied_space.city_object_mut()
+    }
+
+    fn parse_property<'a>(
+        &mut self,
+        id: &Id,
+        reader: &mut Reader<&'a [u8]>,
+        start: &BytesStart<'a>,
+        context: &ParseContext,
+    ) -> Result<bool, Error> {
+        match start.name().as_ref() {
+            b"con:filling" => {
+                parse_property_members(reader, start, |reader, member| {
+                    parse_building_constructive_element_member(self, reader, member, context)
+                })?;
+                Ok(true)
+            }
+            _ => {
+                if parse_building_constructive_element_member(self, reader, start, context)? {
+                    return Ok(true);
+                }
+                self.occupied_space
+                    .parse_property(id, reader, start, context)
+            }
+        }
+    }
 }
 
-pub fn parse_wall_surface(id: &Id, xml_document: &String) -> Result<WallSurface, Error> {
-    let thematic_surface = parse_thematic_surface(id, xml_document)?;
-    let mut wall_surface = WallSurface::new(thematic_surface);
-
-    let mut reader = Reader::from_str(xml_document.as_str());
-    reader.config_mut().trim_text(true);
-
-    let mut txt = Vec::new();
-    let mut buf = Vec::new();
-
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => {
-                let extracted_attributes: HashMap<String, String> =
-                    extract_xml_element_attributes(&reader, &e);
-                let id: Option<Id> = extracted_attributes
-                    .get("id")
-                    .and_then(|x| Id::try_from(x.as_str()).ok());
-
-                match e.name().as_ref() {
-                    b"con:DoorSurface" => {
-                        let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                        let id: Id = id.unwrap_or(Id::from_hashed_string(&xml_snippet));
-
-                        let occupied_space = parse_occupied_space(&id, &xml_snippet)?;
-                        let door_surface = DoorSurface::new(occupied_space);
-
-                        wall_surface.door_surface.push(door_surface);
-                    }
-                    b"con:WindowSurface" => {
-                        let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                        let id: Id = id.unwrap_or(Id::from_hashed_string(&xml_snippet));
-
-                        let occupied_space = parse_occupied_space(&id, &xml_snippet)?;
-                        let window_surface = WindowSurface::new(occupied_space);
-
-                        wall_surface.window_surface.push(window_surface);
-                    }
-                    _ => {}
+fn parse_building_constructive_element_member<'a>(
+    building_constructive_element: &mut BuildingConstructiveElement,
+    reader: &mut Reader<&'a [u8]>,
+    start: &BytesStart<'a>,
+    context: &ParseContext,
+) -> Result<bool, Error> {
+    match start.name().as_ref() {
+        b"con:Door" => {
+            let door = parse_feature_member(reader, start, context)?;
+            building_constructive_element.door.push(door);
+        }
+        b"con:Window" => {
+            let window = parse_feature_member(reader, start, context)?;
+            building_constructive_element.window.push(window);
+        }
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+impl FeatureParser for WallSurface {
+    fn from_city_object(city_object: CityObject) -> Self {
+        WallSurface::new(ThematicSurface::new(city_object))
+    }
+
+    fn city_object_mut(&mut self) -> &mut CityObject {
+        &mut self.thematic_surface.city_object
+    }
+
+    fn parse_property<'a>(
+        &mut self,
+        id: &Id,
+        reader: &mut Reader<&'a [u8]>,
+        start: &BytesStart<'a>,
+        context: &ParseContext,
+    ) -> Result<bool, Error> {
+        match start.name().as_ref() {
+            b"con:fillingSurface" => {
+                parse_property_members(reader, start, |reader, member| {
+                    parse_filling_surface_member(
+                        &mut self.door_surface,
+                        &mut self.window_surface,
+                        reader,
+                        member,
+                        context,
+                    )
+                })?;
+                Ok(true)
+            }
+            _ => {
+                if parse_filling_surface_member(
+                    &mut self.door_surface,
+                    &mut self.window_surface,
+                    reader,
+                    start,
+                    context,
+                )? {
+                    return Ok(true);
                 }
+                self.thematic_surface
+                    .parse_property(id, reader, start, context)
             }
-            Ok(Event::Eof) => break,
-            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
-            Ok(Event::Text(e)) => txt.push(e.decode().unwrap().into_owned()),
-            _ => (),
         }
     }
+}
+
+impl FeatureParser for InteriorWallSurface {
+    fn from_city_object(city_object: CityObject) -> Self {
+        InteriorWallSurface::new(ThematicSurface::new(city_object))
+    }
 
-    Ok(wall_surface)
+    fn city_object_mut(&mut self) -> &mut CityObject {
+        &mut self.thematic_surface.city_object
+    }
+
+    fn parse_property<'a>(
+        &mut self,
+        id: &Id,
+        reader: &mut Reader<&'a [u8]>,
+        start: &BytesStart<'a>,
+        context: &ParseContext,
+    ) -> Result<bool, Error> {
+        match start.name().as_ref() {
+            b"con:fillingSurface" => {
+                parse_property_members(reader, start, |reader, member| {
+                    parse_filling_surface_member(
+                        &mut self.door_surface,
+                        &mut self.window_surface,
+                        reader,
+                        member,
+                        context,
+                    )
+                })?;
+                Ok(true)
+            }
+            _ => {
+                if parse_filling_surface_member(
+                    &mut self.door_surface,
+                    &mut self.window_surface,
+                    reader,
+                    start,
+                    context,
+                )? {
+                    return Ok(true);
+                }
+                self.thematic_surface
+                    .parse_property(id, reader, start, context)
+            }
+        }
+    }
+}
+
+fn parse_filling_surface_member<'a>(
+    door_surface: &mut Vec<DoorSurface>,
+    window_surface: &mut Vec<WindowSurface>,
+    reader: &mut Reader<&'a [u8]>,
+    start: &BytesStart<'a>,
+    context: &ParseContext,
+) -> Result<bool, Error> {
+    match start.name().as_ref() {
+        b"con:DoorSurface" => {
+            let thematic_surface: ThematicSurface = parse_feature_member(reader, start, context)?;
+            door_surface.push(DoorSurface::new(thematic_surface));
+        }
+        b"con:WindowSurface" => {
+            let thematic_surface: ThematicSurface = parse_feature_member(reader, start, context)?;
+            window_surface.push(WindowSurface::new(thematic_surface));
+        }
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+impl FeatureParser for Door {
+    fn from_city_object(city_object: CityObject) -> Self {
+        Door::new(OccupiedSpace::from_city_object(city_object))
+    }
+
+    fn city_object_mut(&mut self) -> &mut CityObject {
+        self.occupied_space.city_object_mut()
+    }
+
+    fn parse_property<'a>(
+        &mut self,
+        id: &Id,
+        reader: &mut Reader<&'a [u8]>,
+        start: &BytesStart<'a>,
+        context: &ParseContext,
+    ) -> Result<bool, Error> {
+        let mut window_surface = Vec::new();
+        let parsed = match start.name().as_ref() {
+            b"boundary" => {
+                parse_property_members(reader, start, |reader, member| {
+                    parse_filling_surface_member(
+                        &mut self.door_surface,
+                        &mut window_surface,
+                        reader,
+                        member,
+                        context,
+                    )
+                })?;
+                true
+            }
+            _ => parse_filling_surface_member(
+                &mut self.door_surface,
+                &mut window_surface,
+                reader,
+                start,
+                context,
+            )?,
+        };
+
+        if parsed {
+            return Ok(true);
+        }
+        self.occupied_space
+            .parse_property(id, reader, start, context)
+    }
+}
+
+impl FeatureParser for Window {
+    fn from_city_object(city_object: CityObject) -> Self {
+        Window::new(OccupiedSpace::from_city_object(city_object))
+    }
+
+    fn city_object_mut(&mut self) -> &mut CityObject {
+        self.occupied_space.city_object_mut()
+    }
+
+    fn parse_property<'a>(
+        &mut self,
+        id: &Id,
+        reader: &mut Reader<&'a [u8]>,
+        start: &BytesStart<'a>,
+        context: &ParseContext,
+    ) -> Result<bool, Error> {
+        let mut door_surface = Vec::new();
+        let parsed = match start.name().as_ref() {
+            b"boundary" => {
+                parse_property_members(reader, start, |reader, member| {
+                    parse_filling_surface_member(
+                        &mut door_surface,
+                        &mut self.window_surface,
+                        reader,
+                        member,
+                        context,
+                    )
+                })?;
+                true
+            }
+            _ => parse_filling_surface_member(
+                &mut door_surface,
+                &mut self.window_surface,
+                reader,
+                start,
+                context,
+            )?,
+        };
+
+        if parsed {
+            return Ok(true);
+        }
+        self.occupied_space
+            .parse_property(id, reader, start, context)
+    }
+}
+
+pub fn parse_building(
+    id: &Id,
+    xml_document: &str,
+    context: &ParseContext,
+) -> Result<Building, Error> {
+    let mut building: Building = parse_feature(id, xml_document, context)?;
+
+    // a vector grows to a capacity of four surfaces on the first push, which would double the
+    // memory of buildings with a single ground and roof surface
+    building.wall_surface.shrink_to_fit();
+    building.roof_surface.shrink_to_fit();
+    building.ground_surface.shrink_to_fit();
+    building.ceiling_surface.shrink_to_fit();
+    building.floor_surface.shrink_to_fit();
+    building.interior_wall_surface.shrink_to_fit();
+    building.outer_ceiling_surface.shrink_to_fit();
+    building.outer_floor_surface.shrink_to_fit();
+    building.closure_surface.shrink_to_fit();
+    building.building_constructive_element.shrink_to_fit();
+
+    Ok(building)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_building_with_openings() {
+        let xml_document = String::from(
+            "<boundary>
+    <con:WallSurface gml:id=\"wall_1\">
+      <con:fillingSurface>
+        <con:WindowSurface gml:id=\"window_surface_1\"><gml:name>window_surface_1</gml:name></con:WindowSurface>
+      </con:fillingSurface>
+      <con:fillingSurface>
+        <con:DoorSurface gml:id=\"door_surface_1\"><gml:name>door_surface_1</gml:name></con:DoorSurface>
+      </con:fillingSurface>
+    </con:WallSurface>
+  </boundary>
+  <boundary>
+    <con:InteriorWallSurface gml:id=\"interior_wall_1\"><gml:name>interior_wall_1</gml:name></con:InteriorWallSurface>
+  </boundary>
+  <boundary>
+    <con:FloorSurface gml:id=\"floor_1\"><gml:name>floor_1</gml:name></con:FloorSurface>
+  </boundary>
+  <boundary>
+    <core:ClosureSurface gml:id=\"closure_1\"><gml:name>closure_1</gml:name></core:ClosureSurface>
+  </boundary>
+  <bldg:buildingConstructiveElement>
+    <bldg:BuildingConstructiveElement gml:id=\"element_1\">
+      <con:filling>
+        <con:Door gml:id=\"door_1\">
+          <boundary>
+            <con:DoorSurface gml:id=\"door_surface_2\"><gml:name>door_surface_2</gml:name></con:DoorSurface>
+          </boundary>
+        </con:Door>
+      </con:filling>
+      <con:filling>
+        <con:Window gml:id=\"window_1\"><gml:name>window_1</gml:name></con:Window>
+      </con:filling>
+    </bldg:BuildingConstructiveElement>
+  </bldg:buildingConstructiveElement>",
+        );
+        let id = Id::try_from("building_1").unwrap();
+
+        let building =
+            parse_building(&id, &xml_document, &ParseContext::default()).expect("should work");
+
+        assert_eq!(building.wall_surface.len(), 1);
+        assert_eq!(building.wall_surface[0].window_surface.len(), 1);
+        assert_eq!(building.wall_surface[0].door_surface.len(), 1);
+        assert_eq!(
+            building.wall_surface[0].door_surface[0]
+                .thematic_surface
+                .city_object
+                .abstract_gml
+                .id,
+            Id::try_from("door_surface_1").unwrap()
+        );
+        assert_eq!(building.interior_wall_surface.len(), 1);
+        assert_eq!(building.floor_surface.len(), 1);
+        assert_eq!(building.closure_surface.len(), 1);
+        assert_eq!(building.closure_surface.capacity(), 1);
+
+        assert_eq!(building.building_constructive_element.len(), 1);
+        let building_constructive_element = &building.building_constructive_element[0];
+        assert_eq!(building_constructive_element.door.len(), 1);
+        assert_eq!(building_constructive_element.door[0].door_surface.len(), 1);
+        assert_eq!(building_constructive_element.window.len(), 1);
+    }
 }
@@ -1,49 +1,223 @@
 use crate::Error;
 use crate::parser::attribute::parse_generic_attribute;
-use ecitygml_core::model::core::CityObject;
-use egml::io::parse_abstract_gml;
+use crate::parser::context::ParseContext;
+use crate::parser::dynamizer::parse_dynamizer;
+use crate::parser::external_reference::parse_external_reference;
+use crate::parser::feature::FeatureParser;
+use crate::parser::util::parse_date_time;
+use crate::parser::util::read_xml_fragment;
+use chrono::{DateTime, Utc};
+use ecitygml_core::model::core::{CityObject, FragmentPosition, XmlFragment};
 use egml::model::base::Id;
 use quick_xml::Reader;
-use quick_xml::events::Event;
-
-pub fn parse_city_object(id: &Id, xml_document: &String) -> Result<CityObject, Error> {
-    let abstract_gml = parse_abstract_gml(xml_document, id.clone())?;
-    let mut city_object = CityObject::new(abstract_gml, Vec::new());
-
-    let mut reader = Reader::from_str(xml_document.as_str());
-    reader.config_mut().trim_text(true);
-
-    let mut txt = Vec::new();
-    let mut buf = Vec::new();
-
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => match e.name().as_ref() {
-                b"genericAttribute" => {
-                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                    let generic_attribute = parse_generic_attribute(&xml_snippet).ok();
-                    if let Some(generic_attribute) = generic_attribute {
-                        city_object.generic_attributes.push(generic_attribute);
+use quick_xml::events::BytesStart;
+use tracing::warn;
+
+impl FeatureParser for CityObject {
+    fn from_city_object(city_object: CityObject) -> Self {
+        city_object
+    }
+
+    fn city_object_mut(&mut self) -> &mut CityObject {
+        self
+    }
+}
+
+/// Parses a property of the city object started by `start`.
+///
+/// Properties not interpreted by any feature type end up here and are either passed to a
+/// registered ADE handler, preserved as [`XmlFragment`] or skipped.
+pub fn parse_city_object_property<'a>(
+    city_object: &mut CityObject,
+    reader: &mut Reader<&'a [u8]>,
+    start: &BytesStart<'a>,
+    position: &FragmentPosition,
+    context: &ParseContext,
+) -> Result<(), Error> {
+    let id = city_object.abstract_gml.id.clone();
+    let id = &id;
+
+    match start.name().as_ref() {
+        b"gml:name" => {
+            let text = reader.read_text(start.name())?;
+            city_object.abstract_gml.name.push(text.into_owned());
+        }
+        b"genericAttribute" => {
+            let xml_snippet = reader.read_text(start.name())?;
+            let generic_attribute = parse_generic_attribute(&xml_snippet)
+                .map_err(|e| {
+                    warn!(
+                        "generic attribute of feature (id={}) could not be parsed: {}",
+                        id,
+                        e.to_string()
+                    );
+                })
+                .ok();
+            if let Some(generic_attribute) = generic_attribute {
+                city_object.generic_attributes.push(generic_attribute);
+            }
+        }
+        b"creationDate" => {
+            let text = reader.read_text(start.name())?;
+            city_object.creation_date = parse_lifespan_date_time(id, "creationDate", &text);
+        }
+        b"terminationDate" => {
+            let text = reader.read_text(start.name())?;
+            city_object.termination_date = parse_lifespan_date_time(id, "terminationDate", &text);
+        }
+        b"validFrom" => {
+            let text = reader.read_text(start.name())?;
+            city_object.valid_from = parse_lifespan_date_time(id, "validFrom", &text);
+        }
+        b"validTo" => {
+            let text = reader.read_text(start.name())?;
+            city_object.valid_to = parse_lifespan_date_time(id, "validTo", &text);
+        }
+        b"relativeToTerrain" => {
+            let text = reader.read_text(start.name())?;
+            city_object.relative_to_terrain = text
+                .trim()
+                .parse()
+                .map_err(|_| {
+                    warn!(
+                        "relativeToTerrain of feature (id={}) has invalid value: {}",
+                        id, text
+                    );
+                })
+                .ok();
+        }
+        b"relativeToWater" => {
+            let text = reader.read_text(start.name())?;
+            city_object.relative_to_water = text
+                .trim()
+                .parse()
+                .map_err(|_| {
+                    warn!(
+                        "relativeToWater of feature (id={}) has invalid value: {}",
+                        id, text
+                    );
+                })
+                .ok();
+        }
+        b"externalReference" => {
+            let xml_snippet = reader.read_text(start.name())?;
+            let external_reference = parse_external_reference(&xml_snippet)
+                .map_err(|e| {
+                    warn!(
+                        "external reference of feature (id={}) could not be parsed: {}",
+                        id,
+                        e.to_string()
+                    );
+                })
+                .ok();
+            if let Some(external_reference) = external_reference {
+                city_object.external_reference.push(external_reference);
+            }
+        }
+        b"dynamizer" => {
+            let xml_snippet = reader.read_text(start.name())?;
+            let dynamizer = parse_dynamizer(&xml_snippet)
+                .map_err(|e| {
+                    warn!(
+                        "dynamizer of feature (id={}) could not be parsed: {}",
+                        id,
+                        e.to_string()
+                    );
+                })
+                .ok();
+            if let Some(dynamizer) = dynamizer {
+                city_object.dynamizer.push(dynamizer);
+            }
+        }
+        _ => {
+            let context = context.in_scope_of(start);
+            if let Some((namespace, ade_handler)) = context.ade_handler(start) {
+                let (element_name, xml_fragment) = read_xml_fragment(reader, start)?;
+
+                match ade_handler.parse_property(&element_name, &xml_fragment) {
+                    Ok(property) => city_object.ade_extension.insert(namespace, property),
+                    Err(e) => {
+                        warn!(
+                            "ADE property {} of feature (id={}) could not be parsed: {}",
+                            element_name,
+                            id,
+                            e.to_string()
+                        );
+                        if context.preserve_unknown_content() {
+                            city_object.unknown_content.push(XmlFragment::new(
+                                element_name,
+                                position.clone(),
+                                xml_fragment,
+                            ));
+                        }
                     }
                 }
-                _ => {
-                    reader.read_to_end(e.name())?;
-                }
-            },
-            Ok(Event::Eof) => break,
-            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
-            Ok(Event::Text(e)) => txt.push(e.decode().unwrap().into_owned()),
-            _ => (),
+            } else if context.preserve_unknown_content() {
+                let (element_name, xml_fragment) = read_xml_fragment(reader, start)?;
+                city_object.unknown_content.push(XmlFragment::new(
+                    element_name,
+                    position.clone(),
+                    xml_fragment,
+                ));
+            } else {
+                reader.read_to_end(start.name())?;
+            }
         }
     }
 
-    Ok(city_object)
+    Ok(())
+}
+
+/// Handles a self-closing child element of the city object, which is only kept as
+/// [`XmlFragment`] if unknown content is preserved.
+pub fn parse_city_object_empty_property(
+    city_object: &mut CityObject,
+    reader: &Reader<&[u8]>,
+    start: &BytesStart,
+    position: &FragmentPosition,
+    context: &ParseContext,
+) -> Result<(), Error> {
+    if !context.preserve_unknown_content() {
+        return Ok(());
+    }
+
+    let decoder = reader.decoder();
+    let element_name = decoder
+        .decode(start.name().as_ref())
+        .map_err(quick_xml::Error::from)?
+        .into_owned();
+    let start_tag = decoder.decode(start).map_err(quick_xml::Error::from)?;
+    city_object.unknown_content.push(XmlFragment::new(
+        element_name,
+        position.clone(),
+        format!("<{start_tag}/>"),
+    ));
+
+    Ok(())
+}
+
+fn parse_lifespan_date_time(id: &Id, element_name: &str, text: &str) -> Option<DateTime<Utc>> {
+    parse_date_time(text)
+        .map_err(|e| {
+            warn!(
+                "{} of feature (id={}) has invalid value: {}",
+                element_name, id, e
+            );
+        })
+        .ok()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ade::AdeHandlerRegistry;
+    use crate::parser::feature::parse_feature;
+    use chrono::TimeZone;
+    use ecitygml_core::model::ade::AdeProperty;
+    use ecitygml_core::model::core::{RelativeToTerrain, RelativeToWater};
     use egml::model::base::Id;
+    use std::any::Any;
+    use std::collections::HashMap;
 
     #[test]
     fn test_parse_city_object_basic() {
@@ -63,7 +237,8 @@ mod tests {
       </genericAttribute>",
         );
 
-        let city_object = parse_city_object(&id, &xml_document).expect("should work");
+        let city_object: CityObject =
+            parse_feature(&id, &xml_document, &ParseContext::default()).expect("should work");
 
         assert_eq!(city_object.abstract_gml.id, id);
         assert_eq!(city_object.generic_attributes.len(), 2);
@@ -95,10 +270,194 @@ mod tests {
       ",
         );
 
-        let city_object = parse_city_object(&id, &xml_document).expect("should work");
+        let city_object: CityObject =
+            parse_feature(&id, &xml_document, &ParseContext::default()).expect("should work");
 
         assert_eq!(city_object.abstract_gml.id, id);
         assert!(city_object.abstract_gml.name.is_empty());
         assert_eq!(city_object.generic_attributes.len(), 3);
     }
+
+    #[test]
+    fn test_parse_city_object_with_lifespan_and_external_reference() {
+        let id = Id::try_from("metadata-test").expect("should work");
+        let xml_document = String::from(
+            "<creationDate>2017-01-23T00:00:00Z</creationDate>
+      <validFrom>2018-05-01T00:00:00Z</validFrom>
+      <externalReference>
+        <ExternalReference>
+          <targetResource>DEBY_4959457</targetResource>
+          <informationSystem>https://www.adv-online.de/alkis</informationSystem>
+        </ExternalReference>
+      </externalReference>
+      <relativeToTerrain>entirelyAboveTerrain</relativeToTerrain>
+      <relativeToWater>temporarilyAboveAndBelowWaterSurface</relativeToWater>",
+        );
+
+        let city_object: CityObject =
+            parse_feature(&id, &xml_document, &ParseContext::default()).expect("should work");
+
+        assert_eq!(
+            city_object.creation_date,
+            Some(Utc.with_ymd_and_hms(2017, 1, 23, 0, 0, 0).unwrap())
+        );
+        assert!(city_object.termination_date.is_none());
+        assert!(city_object.valid_from.is_some());
+        assert!(city_object.valid_to.is_none());
+        assert_eq!(
+            city_object.relative_to_terrain,
+            Some(RelativeToTerrain::EntirelyAboveTerrain)
+        );
+        assert_eq!(
+            city_object.relative_to_water,
+            Some(RelativeToWater::TemporarilyAboveAndBelowWaterSurface)
+        );
+        assert_eq!(city_object.external_reference.len(), 1);
+        assert_eq!(
+            city_object.external_reference[0].target_resource,
+            "DEBY_4959457"
+        );
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct VolumeProperty {
+        value: f64,
+    }
+
+    impl AdeProperty for VolumeProperty {
+        fn to_xml(&self) -> String {
+            format!("<energy:volume>{}</energy:volume>", self.value)
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn clone_box(&self) -> Box<dyn AdeProperty> {
+            Box::new(self.clone())
+        }
+    }
+
+    const ENERGY_NAMESPACE: &str = "http://www.sig3d.org/citygml/3.0/energy/2.0";
+
+    fn energy_ade_handler_registry() -> AdeHandlerRegistry {
+        let mut ade_handler_registry = AdeHandlerRegistry::new();
+        ade_handler_registry.register(
+            ENERGY_NAMESPACE,
+            |element_name: &str, xml_fragment: &str| -> Result<Box<dyn AdeProperty>, Error> {
+                if !element_name.ends_with(":volume") {
+                    return Err(Error::ElementNotFound(element_name.to_string()));
+                }
+                let value: String = quick_xml::de::from_str(xml_fragment)?;
+                let value = value
+                    .parse()
+                    .map_err(|_| Error::ElementNotFound(xml_fragment.to_string()))?;
+                Ok(Box::new(VolumeProperty { value }))
+            },
+        );
+        ade_handler_registry
+    }
+
+    #[test]
+    fn test_parse_city_object_with_ade_property() {
+        let namespace = ENERGY_NAMESPACE;
+        let ade_handler_registry = energy_ade_handler_registry();
+        let namespace_declarations = HashMap::from([("energy".to_string(), namespace.to_string())]);
+        let context = ParseContext::new(&ade_handler_registry, &namespace_declarations);
+
+        let id = Id::try_from("ade-test").expect("should work");
+        let xml_document = String::from(
+            "<energy:volume uom=\"m3\">1250.5</energy:volume>
+      <energy:unknownProperty>abc</energy:unknownProperty>
+      <other:volume>1.0</other:volume>",
+        );
+
+        let city_object: CityObject =
+            parse_feature(&id, &xml_document, &context).expect("should work");
+
+        assert_eq!(city_object.ade_extension.get(namespace).len(), 1);
+        assert_eq!(
+            city_object
+                .ade_extension
+                .get_typed::<VolumeProperty>(namespace),
+            vec![&VolumeProperty { value: 1250.5 }]
+        );
+        assert_eq!(city_object.ade_extension.namespaces().count(), 1);
+    }
+
+    #[test]
+    fn test_parse_city_object_with_ade_prefix_declared_in_scope() {
+        let namespace_declarations =
+            HashMap::from([("energy".to_string(), ENERGY_NAMESPACE.to_string())]);
+        let context = ParseContext::new(&energy_ade_handler_registry(), &namespace_declarations);
+
+        let id = Id::try_from("ade-test").expect("should work");
+        let xml_document = format!(
+            "<nrg:volume xmlns:nrg=\"{ENERGY_NAMESPACE}\">1250.5</nrg:volume>
+      <energy:volume xmlns:energy=\"http://example.com/other\">1.0</energy:volume>
+      <energy:volume>2.0</energy:volume>"
+        );
+
+        let city_object: CityObject =
+            parse_feature(&id, &xml_document, &context).expect("should work");
+
+        assert_eq!(
+            city_object
+                .ade_extension
+                .get_typed::<VolumeProperty>(ENERGY_NAMESPACE),
+            vec![
+                &VolumeProperty { value: 1250.5 },
+                &VolumeProperty { value: 2.0 }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_city_object_with_preserved_unknown_content() {
+        let context = ParseContext::default().with_preserve_unknown_content(true);
+
+        let id = Id::try_from("unknown-content-test").expect("should work");
+        let xml_document = String::from(
+            "<gml:name>Building 1</gml:name>
+      <app:appearance><app:Appearance><app:theme>rgb</app:theme></app:Appearance></app:appearance>
+      <creationDate>2017-01-23T00:00:00Z</creationDate>
+      <adv:flag value=\"1\"/>",
+        );
+
+        let city_object: CityObject =
+            parse_feature(&id, &xml_document, &context).expect("should work");
+
+        assert!(city_object.creation_date.is_some());
+        assert_eq!(city_object.unknown_content.len(), 2);
+        assert_eq!(
+            city_object.unknown_content[0].element_name,
+            "app:appearance"
+        );
+        assert_eq!(
+            city_object.unknown_content[0].position,
+            FragmentPosition {
+                preceding_element: Some("name".to_string()),
+                occurrence: 0
+            }
+        );
+        assert_eq!(
+            city_object.unknown_content[0].content,
+            "<app:appearance><app:Appearance><app:theme>rgb</app:theme></app:Appearance></app:appearance>"
+        );
+        assert_eq!(
+            city_object.unknown_content[1].position,
+            FragmentPosition {
+                preceding_element: Some("creationDate".to_string()),
+                occurrence: 0
+            }
+        );
+        assert_eq!(
+            city_object.unknown_content[1].content,
+            "<adv:flag value=\"1\"/>"
+        );
+
+        let city_object: CityObject =
+            parse_feature(&id, &xml_document, &ParseContext::default()).expect("should work");
+        assert!(city_object.unknown_content.is_empty());
+    }
 }
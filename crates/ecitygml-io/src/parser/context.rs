@@ -0,0 +1,101 @@
+use crate::ade::{AdeHandler, AdeHandlerRegistry};
+use quick_xml::events::BytesStart;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// State shared by the feature parsers while reading a document.
+#[derive(Clone, Default)]
+pub struct ParseContext {
+    ade_handler_registry: AdeHandlerRegistry,
+    /// ADE handlers by namespace prefix in scope, together with the namespace URI.
+    ade_handlers: HashMap<String, (String, Arc<dyn AdeHandler>)>,
+    preserve_unknown_content: bool,
+}
+
+impl ParseContext {
+    /// Resolves the registered ADE handlers against the namespace declarations
+    /// (prefix to URI) of the document.
+    pub fn new(
+        ade_handler_registry: &AdeHandlerRegistry,
+        namespace_declarations: &HashMap<String, String>,
+    ) -> Self {
+        Self {
+            ade_handler_registry: ade_handler_registry.clone(),
+            ade_handlers: HashMap::new(),
+            preserve_unknown_content: false,
+        }
+        .with_namespace_declarations(namespace_declarations)
+    }
+
+    /// Applies namespace declarations of nested elements, which may bind further prefixes to
+    /// ADE namespaces or rebind prefixes of the enclosing scope.
+    pub fn with_namespace_declarations(
+        mut self,
+        namespace_declarations: &HashMap<String, String>,
+    ) -> Self {
+        for (prefix, namespace) in namespace_declarations {
+            match self.ade_handler_registry.get(namespace) {
+                Some(handler) => {
+                    let ade_handler = (namespace.clone(), handler.clone());
+                    self.ade_handlers.insert(prefix.clone(), ade_handler);
+                }
+                None => {
+                    self.ade_handlers.remove(prefix);
+                }
+            }
+        }
+        self
+    }
+
+    /// Returns the context for the content of `element` with the namespace declarations of the
+    /// element applied.
+    pub fn in_scope_of(&self, element: &BytesStart) -> Cow<'_, Self> {
+        let namespace_declarations = extract_namespace_declarations(element);
+        if namespace_declarations.is_empty() {
+            Cow::Borrowed(self)
+        } else {
+            Cow::Owned(
+                self.clone()
+                    .with_namespace_declarations(&namespace_declarations),
+            )
+        }
+    }
+
+    pub fn with_preserve_unknown_content(mut self, preserve_unknown_content: bool) -> Self {
+        self.preserve_unknown_content = preserve_unknown_content;
+        self
+    }
+
+    /// Returns `true` if uninterpreted child elements of city objects are kept as raw XML.
+    pub fn preserve_unknown_content(&self) -> bool {
+        self.preserve_unknown_content
+    }
+
+    /// Returns the namespace URI and handler responsible for the element, if any.
+    ///
+    /// Declarations on the element itself are not considered, see
+    /// [`in_scope_of`](Self::in_scope_of).
+    pub fn ade_handler(&self, element: &BytesStart) -> Option<(&str, &dyn AdeHandler)> {
+        let prefix = element.name().prefix()?;
+        let prefix = std::str::from_utf8(prefix.as_ref()).ok()?;
+
+        self.ade_handlers
+            .get(prefix)
+            .map(|(namespace, handler)| (namespace.as_str(), handler.as_ref()))
+    }
+}
+
+/// Extracts the namespace declarations (prefix to URI) of an element.
+pub fn extract_namespace_declarations(element: &BytesStart) -> HashMap<String, String> {
+    element
+        .attributes()
+        .filter_map(Result::ok)
+        .filter_map(|a| {
+            let key = std::str::from_utf8(a.key.as_ref()).ok()?;
+            let prefix = key.strip_prefix("xmlns:")?;
+            let value = a.unescape_value().ok()?;
+            Some((prefix.to_string(), value.into_owned()))
+        })
+        .collect()
+}
@@ -0,0 +1,332 @@
+use crate::Error;
+use crate::Error::ElementNotFound;
+use crate::parser::util::parse_date_time;
+use ecitygml_core::model::dynamizer::{
+    AtomicTimeseries, Dynamizer, TabulatedFileTimeseries, TimeValuePair, Timeseries,
+    TimeseriesValue,
+};
+use egml::model::base::{AbstractGml, Id};
+use quick_xml::de;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct GmlDynamizer {
+    #[serde(rename = "@id", default)]
+    pub id: String,
+    #[serde(rename = "attributeRef", default)]
+    pub attribute_ref: String,
+    #[serde(rename = "startTime", default)]
+    pub start_time: Option<String>,
+    #[serde(rename = "endTime", default)]
+    pub end_time: Option<String>,
+    #[serde(rename = "dynamicData", default)]
+    pub dynamic_data: Option<GmlDynamicData>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct GmlDynamicData {
+    #[serde(rename = "$value")]
+    pub timeseries: GmlTimeseries,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+enum GmlTimeseries {
+    AtomicTimeseries(GmlAtomicTimeseries),
+    TabulatedFileTimeseries(Box<GmlTabulatedFileTimeseries>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct GmlAtomicTimeseries {
+    #[serde(rename = "@id", default)]
+    pub id: String,
+    #[serde(rename = "observationProperty", default)]
+    pub observation_property: Option<String>,
+    #[serde(default)]
+    pub uom: Option<String>,
+    #[serde(rename = "firstTimestamp", default)]
+    pub first_timestamp: Option<String>,
+    #[serde(rename = "lastTimestamp", default)]
+    pub last_timestamp: Option<String>,
+    #[serde(default)]
+    pub component: Vec<GmlTimeValuePairProperty>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct GmlTimeValuePairProperty {
+    #[serde(rename = "TimeValuePair")]
+    pub time_value_pair: GmlTimeValuePair,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct GmlTimeValuePair {
+    pub timestamp: String,
+    #[serde(rename = "intValue", default)]
+    pub int_value: Option<i64>,
+    #[serde(rename = "doubleValue", default)]
+    pub double_value: Option<f64>,
+    #[serde(rename = "stringValue", default)]
+    pub string_value: Option<String>,
+    #[serde(rename = "boolValue", default)]
+    pub bool_value: Option<bool>,
+    #[serde(rename = "uriValue", default)]
+    pub uri_value: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct GmlTabulatedFileTimeseries {
+    #[serde(rename = "@id", default)]
+    pub id: String,
+    #[serde(rename = "observationProperty", default)]
+    pub observation_property: Option<String>,
+    #[serde(default)]
+    pub uom: Option<String>,
+    #[serde(rename = "firstTimestamp", default)]
+    pub first_timestamp: Option<String>,
+    #[serde(rename = "lastTimestamp", default)]
+    pub last_timestamp: Option<String>,
+    #[serde(rename = "fileLocation", default)]
+    pub file_location: String,
+    #[serde(rename = "fileType", default)]
+    pub file_type: Option<String>,
+    #[serde(rename = "mimeType", default)]
+    pub mime_type: Option<String>,
+    #[serde(rename = "valueType", default)]
+    pub value_type: Option<String>,
+    #[serde(rename = "numberOfHeaderLines", default)]
+    pub number_of_header_lines: Option<u32>,
+    #[serde(rename = "fieldSeparator", default)]
+    pub field_separator: Option<String>,
+    #[serde(rename = "decimalSymbol", default)]
+    pub decimal_symbol: Option<String>,
+    #[serde(rename = "idColumnNo", default)]
+    pub id_column_no: Option<u32>,
+    #[serde(rename = "idColumnName", default)]
+    pub id_column_name: Option<String>,
+    #[serde(rename = "idValue", default)]
+    pub id_value: Option<String>,
+    #[serde(rename = "timeColumnNo", default)]
+    pub time_column_no: Option<u32>,
+    #[serde(rename = "timeColumnName", default)]
+    pub time_column_name: Option<String>,
+    #[serde(rename = "timeFormat", default)]
+    pub time_format: Option<String>,
+    #[serde(rename = "valueColumnNo", default)]
+    pub value_column_no: Option<u32>,
+    #[serde(rename = "valueColumnName", default)]
+    pub value_column_name: Option<String>,
+}
+
+impl TryFrom<GmlTimeValuePair> for TimeValuePair {
+    type Error = Error;
+
+    fn try_from(value: GmlTimeValuePair) -> Result<Self, Self::Error> {
+        let timestamp = parse_date_time(&value.timestamp)?;
+        let time_value = if let Some(x) = value.int_value {
+            TimeseriesValue::Int(x)
+        } else if let Some(x) = value.double_value {
+            TimeseriesValue::Double(x)
+        } else if let Some(x) = value.bool_value {
+            TimeseriesValue::Bool(x)
+        } else if let Some(x) = value.uri_value {
+            TimeseriesValue::Uri(x)
+        } else if let Some(x) = value.string_value {
+            TimeseriesValue::String(x)
+        } else {
+            return Err(ElementNotFound("value of TimeValuePair".to_string()));
+        };
+
+        Ok(TimeValuePair {
+            timestamp,
+            value: time_value,
+        })
+    }
+}
+
+impl TryFrom<GmlAtomicTimeseries> for AtomicTimeseries {
+    type Error = Error;
+
+    fn try_from(value: GmlAtomicTimeseries) -> Result<Self, Self::Error> {
+        let id = Id::try_from(value.id.as_str())
+            .unwrap_or_else(|_| Id::from_hashed_string(&format!("{value:?}")));
+        let mut timeseries = AtomicTimeseries::new(AbstractGml::new(id));
+        timeseries.observation_property = value.observation_property;
+        timeseries.uom = value.uom;
+        timeseries.first_timestamp = value
+            .first_timestamp
+            .map(|x| parse_date_time(&x))
+            .transpose()?;
+        timeseries.last_timestamp = value
+            .last_timestamp
+            .map(|x| parse_date_time(&x))
+            .transpose()?;
+        timeseries.component = value
+            .component
+            .into_iter()
+            .map(|x| TimeValuePair::try_from(x.time_value_pair))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(timeseries)
+    }
+}
+
+impl TryFrom<GmlTabulatedFileTimeseries> for TabulatedFileTimeseries {
+    type Error = Error;
+
+    fn try_from(value: GmlTabulatedFileTimeseries) -> Result<Self, Self::Error> {
+        if value.file_location.is_empty() {
+            return Err(ElementNotFound("fileLocation".to_string()));
+        }
+        let id = Id::try_from(value.id.as_str())
+            .unwrap_or_else(|_| Id::from_hashed_string(&format!("{value:?}")));
+
+        let mut timeseries =
+            TabulatedFileTimeseries::new(AbstractGml::new(id), value.file_location);
+        timeseries.observation_property = value.observation_property;
+        timeseries.uom = value.uom;
+        timeseries.first_timestamp = value
+            .first_timestamp
+            .map(|x| parse_date_time(&x))
+            .transpose()?;
+        timeseries.last_timestamp = value
+            .last_timestamp
+            .map(|x| parse_date_time(&x))
+            .transpose()?;
+        timeseries.file_type = value.file_type;
+        timeseries.mime_type = value.mime_type;
+        timeseries.value_type = value.value_type;
+        timeseries.number_of_header_lines = value.number_of_header_lines;
+        timeseries.field_separator = value.field_separator;
+        timeseries.decimal_symbol = value.decimal_symbol;
+        timeseries.id_column_no = value.id_column_no;
+        timeseries.id_column_name = value.id_column_name;
+        timeseries.id_value = value.id_value;
+        timeseries.time_column_no = value.time_column_no;
+        timeseries.time_column_name = value.time_column_name;
+        timeseries.time_format = value.time_format;
+        timeseries.value_column_no = value.value_column_no;
+        timeseries.value_column_name = value.value_column_name;
+
+        Ok(timeseries)
+    }
+}
+
+impl TryFrom<GmlDynamizer> for Dynamizer {
+    type Error = Error;
+
+    fn try_from(value: GmlDynamizer) -> Result<Self, Self::Error> {
+        if value.attribute_ref.is_empty() {
+            return Err(ElementNotFound("attributeRef".to_string()));
+        }
+        let id = Id::try_from(value.id.as_str())
+            .unwrap_or_else(|_| Id::from_hashed_string(&format!("{value:?}")));
+
+        let mut dynamizer = Dynamizer::new(AbstractGml::new(id), value.attribute_ref);
+        dynamizer.start_time = value.start_time.map(|x| parse_date_time(&x)).transpose()?;
+        dynamizer.end_time = value.end_time.map(|x| parse_date_time(&x)).transpose()?;
+        dynamizer.dynamic_data = value
+            .dynamic_data
+            .map(|x| match x.timeseries {
+                GmlTimeseries::AtomicTimeseries(x) => x.try_into().map(Timeseries::Atomic),
+                GmlTimeseries::TabulatedFileTimeseries(x) => (*x)
+                    .try_into()
+                    .map(|x| Timeseries::TabulatedFile(Box::new(x))),
+            })
+            .transpose()?;
+
+        Ok(dynamizer)
+    }
+}
+
+pub fn parse_dynamizer(xml_document: &str) -> Result<Dynamizer, Error> {
+    let parsed_dynamizer: GmlDynamizer = de::from_str(xml_document)?;
+    parsed_dynamizer.try_into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_parse_dynamizer_with_atomic_timeseries() {
+        let xml_document = String::from(
+            "<dyn:Dynamizer gml:id=\"dynamizer_1\">
+          <dyn:attributeRef>//bldg:Building[@gml:id='building_1']/energyConsumption</dyn:attributeRef>
+          <dyn:startTime>2024-01-01T00:00:00Z</dyn:startTime>
+          <dyn:endTime>2024-12-31T23:59:59Z</dyn:endTime>
+          <dyn:dynamicData>
+            <dyn:AtomicTimeseries gml:id=\"timeseries_1\">
+              <dyn:observationProperty>energyConsumption</dyn:observationProperty>
+              <dyn:uom>kWh</dyn:uom>
+              <dyn:component>
+                <dyn:TimeValuePair>
+                  <dyn:timestamp>2024-01-01T00:00:00Z</dyn:timestamp>
+                  <dyn:doubleValue>120.5</dyn:doubleValue>
+                </dyn:TimeValuePair>
+              </dyn:component>
+              <dyn:component>
+                <dyn:TimeValuePair>
+                  <dyn:timestamp>2024-02-01T00:00:00Z</dyn:timestamp>
+                  <dyn:doubleValue>98.0</dyn:doubleValue>
+                </dyn:TimeValuePair>
+              </dyn:component>
+            </dyn:AtomicTimeseries>
+          </dyn:dynamicData>
+        </dyn:Dynamizer>",
+        );
+
+        let dynamizer = parse_dynamizer(&xml_document).expect("should work");
+
+        assert_eq!(
+            dynamizer.abstract_gml.id,
+            Id::try_from("dynamizer_1").unwrap()
+        );
+        let Some(Timeseries::Atomic(timeseries)) = &dynamizer.dynamic_data else {
+            panic!("Expected atomic timeseries");
+        };
+        assert_eq!(timeseries.uom.as_deref(), Some("kWh"));
+        assert_eq!(timeseries.component.len(), 2);
+
+        let time = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        assert_eq!(
+            dynamizer.value_at(&time),
+            Some(&TimeseriesValue::Double(120.5))
+        );
+        let time = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            dynamizer.value_at(&time),
+            Some(&TimeseriesValue::Double(98.0))
+        );
+        let time = Utc.with_ymd_and_hms(2023, 12, 1, 0, 0, 0).unwrap();
+        assert_eq!(dynamizer.value_at(&time), None);
+    }
+
+    #[test]
+    fn test_parse_dynamizer_with_tabulated_file_timeseries() {
+        let xml_document = String::from(
+            "<dyn:Dynamizer>
+          <dyn:attributeRef>//bldg:Building[@gml:id='building_1']/energyConsumption</dyn:attributeRef>
+          <dyn:dynamicData>
+            <dyn:TabulatedFileTimeseries>
+              <dyn:fileLocation>readings/building_1.csv</dyn:fileLocation>
+              <dyn:fileType>csv</dyn:fileType>
+              <dyn:numberOfHeaderLines>1</dyn:numberOfHeaderLines>
+              <dyn:fieldSeparator>;</dyn:fieldSeparator>
+              <dyn:timeColumnNo>0</dyn:timeColumnNo>
+              <dyn:valueColumnNo>1</dyn:valueColumnNo>
+            </dyn:TabulatedFileTimeseries>
+          </dyn:dynamicData>
+        </dyn:Dynamizer>",
+        );
+
+        let dynamizer = parse_dynamizer(&xml_document).expect("should work");
+
+        let Some(Timeseries::TabulatedFile(timeseries)) = &dynamizer.dynamic_data else {
+            panic!("Expected tabulated file timeseries");
+        };
+        assert_eq!(timeseries.file_location, "readings/building_1.csv");
+        assert_eq!(timeseries.number_of_header_lines, Some(1));
+        assert_eq!(timeseries.field_separator.as_deref(), Some(";"));
+        assert_eq!(timeseries.value_column_no, Some(1));
+    }
+}
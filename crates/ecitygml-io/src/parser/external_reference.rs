@@ -0,0 +1,77 @@
+use crate::Error;
+use crate::Error::ElementNotFound;
+use ecitygml_core::model;
+use quick_xml::de;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+struct ExternalReference {
+    #[serde(rename = "targetResource", default)]
+    pub target_resource: String,
+    #[serde(rename = "informationSystem", default)]
+    pub information_system: Option<String>,
+    #[serde(rename = "relationType", default)]
+    pub relation_type: Option<String>,
+}
+
+impl TryFrom<ExternalReference> for model::core::ExternalReference {
+    type Error = Error;
+
+    fn try_from(value: ExternalReference) -> Result<Self, Self::Error> {
+        if value.target_resource.is_empty() {
+            return Err(ElementNotFound("targetResource".to_string()));
+        }
+
+        Ok(model::core::ExternalReference {
+            target_resource: value.target_resource,
+            information_system: value.information_system,
+            relation_type: value.relation_type,
+        })
+    }
+}
+
+pub fn parse_external_reference(
+    xml_document: &str,
+) -> Result<model::core::ExternalReference, Error> {
+    let parsed_reference: ExternalReference = de::from_str(xml_document)?;
+    let external_reference = model::core::ExternalReference::try_from(parsed_reference)?;
+
+    Ok(external_reference)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_external_reference_basic() {
+        let xml_document = String::from(
+            "<ExternalReference>
+          <targetResource>DEBY_LOD2_4959457</targetResource>
+          <informationSystem>https://www.adv-online.de/alkis</informationSystem>
+          <relationType>http://www.opengis.net/def/relation/sameAs</relationType>
+        </ExternalReference>",
+        );
+
+        let external_reference = parse_external_reference(&xml_document).expect("should work");
+
+        assert_eq!(external_reference.target_resource, "DEBY_LOD2_4959457");
+        assert_eq!(
+            external_reference.information_system.as_deref(),
+            Some("https://www.adv-online.de/alkis")
+        );
+        assert_eq!(
+            external_reference.relation_type.as_deref(),
+            Some("http://www.opengis.net/def/relation/sameAs")
+        );
+    }
+
+    #[test]
+    fn test_parse_external_reference_without_target_resource() {
+        let xml_document = String::from(
+            "<ExternalReference><informationSystem>x</informationSystem></ExternalReference>",
+        );
+
+        assert!(parse_external_reference(&xml_document).is_err());
+    }
+}
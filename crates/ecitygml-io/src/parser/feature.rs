@@ -0,0 +1,169 @@
+use crate::Error;
+use crate::parser::city_object::{parse_city_object_empty_property, parse_city_object_property};
+use crate::parser::context::ParseContext;
+use crate::parser::util::extract_xml_element_attributes;
+use ecitygml_core::model::core::{CityObject, FragmentPosition};
+use egml::model::base::{AbstractGml, Id};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use std::collections::HashMap;
+
+/// Feature types, whose properties are parsed in a single pass over the feature content.
+///
+/// Each implementation handles the properties of its own type and delegates all others to its
+/// supertype. Properties of the city object itself are handled by the shared dispatcher.
+pub trait FeatureParser: Sized {
+    fn from_city_object(city_object: CityObject) -> Self;
+
+    fn city_object_mut(&mut self) -> &mut CityObject;
+
+    /// Parses the property started by `start` and returns `false`, if the property was not
+    /// consumed from the reader.
+    fn parse_property<'a>(
+        &mut self,
+        _id: &Id,
+        _reader: &mut Reader<&'a [u8]>,
+        _start: &BytesStart<'a>,
+        _context: &ParseContext,
+    ) -> Result<bool, Error> {
+        Ok(false)
+    }
+
+    /// Parses the empty property element `start`, such as an `xlink:href` reference, and returns
+    /// `false`, if the property is not handled by the feature type.
+    fn parse_empty_property(
+        &mut self,
+        _reader: &Reader<&[u8]>,
+        _start: &BytesStart,
+    ) -> Result<bool, Error> {
+        Ok(false)
+    }
+}
+
+/// Parses a feature from the content of its element.
+pub fn parse_feature<T: FeatureParser>(
+    id: &Id,
+    xml_document: &str,
+    context: &ParseContext,
+) -> Result<T, Error> {
+    let mut reader = Reader::from_str(xml_document);
+    reader.config_mut().trim_text(true);
+
+    let city_object = CityObject::new(AbstractGml::new(id.clone()), Vec::new());
+    let mut feature = T::from_city_object(city_object);
+    parse_feature_properties(&mut feature, &mut reader, context)?;
+
+    Ok(feature)
+}
+
+/// Parses a feature, whose start tag `start` has just been read, and consumes the reader until
+/// its end tag.
+pub fn parse_feature_member<'a, T: FeatureParser>(
+    reader: &mut Reader<&'a [u8]>,
+    start: &BytesStart<'a>,
+    context: &ParseContext,
+) -> Result<T, Error> {
+    let id: Option<Id> = extract_xml_element_attributes(reader, start)
+        .get("id")
+        .and_then(|x| Id::try_from(x.as_str()).ok());
+    let context = &context.in_scope_of(start);
+
+    match id {
+        Some(id) => {
+            let city_object = CityObject::new(AbstractGml::new(id), Vec::new());
+            let mut feature = T::from_city_object(city_object);
+            parse_feature_properties(&mut feature, reader, context)?;
+            Ok(feature)
+        }
+        None => {
+            // features without gml:id get an id derived from their content
+            let xml_snippet = reader.read_text(start.name())?;
+            let id = Id::from_hashed_string(&xml_snippet);
+            parse_feature(&id, &xml_snippet, context)
+        }
+    }
+}
+
+/// Reads the members of the property started by `start`, such as the feature within a
+/// `boundary`, and passes each of them to `parse_member`.
+///
+/// Members, for which `parse_member` returns `false`, are skipped.
+pub fn parse_property_members<'a>(
+    reader: &mut Reader<&'a [u8]>,
+    start: &BytesStart<'a>,
+    mut parse_member: impl FnMut(&mut Reader<&'a [u8]>, &BytesStart<'a>) -> Result<bool, Error>,
+) -> Result<(), Error> {
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                let parsed = parse_member(reader, &e)?;
+                if !parsed {
+                    reader.read_to_end(e.name())?;
+                }
+            }
+            Event::End(e) if e.name() == start.name() => break,
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+/// Dispatches the child elements of a feature to its property parsers until the end tag of the
+/// feature or the end of the document is reached.
+fn parse_feature_properties<T: FeatureParser>(
+    feature: &mut T,
+    reader: &mut Reader<&[u8]>,
+    context: &ParseContext,
+) -> Result<(), Error> {
+    let id = feature.city_object_mut().abstract_gml.id.clone();
+    let mut position = FragmentPosition::default();
+    let mut occurrences: HashMap<String, usize> = HashMap::new();
+
+    loop {
+        let number_of_fragments = feature.city_object_mut().unknown_content.len();
+        let element_name = match reader.read_event()? {
+            Event::Start(e) => {
+                if !feature.parse_property(&id, reader, &e, context)? {
+                    parse_city_object_property(
+                        feature.city_object_mut(),
+                        reader,
+                        &e,
+                        &position,
+                        context,
+                    )?;
+                }
+                e.local_name().as_ref().to_vec()
+            }
+            Event::Empty(e) => {
+                if !feature.parse_empty_property(reader, &e)? {
+                    parse_city_object_empty_property(
+                        feature.city_object_mut(),
+                        reader,
+                        &e,
+                        &position,
+                        context,
+                    )?;
+                }
+                e.local_name().as_ref().to_vec()
+            }
+            // all child elements are consumed by the property parsers
+            Event::End(_) | Event::Eof => break,
+            _ => continue,
+        };
+
+        // preserved elements are positioned after the closest interpreted sibling
+        if feature.city_object_mut().unknown_content.len() == number_of_fragments {
+            let element_name = String::from_utf8_lossy(&element_name).into_owned();
+            let occurrence = occurrences.entry(element_name.clone()).or_default();
+            position = FragmentPosition {
+                preceding_element: Some(element_name),
+                occurrence: *occurrence,
+            };
+            *occurrence += 1;
+        }
+    }
+
+    Ok(())
+}
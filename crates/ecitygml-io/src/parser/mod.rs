@@ -1,6 +1,12 @@
-mod attribute;
+pub(crate) mod attribute;
 pub mod building;
 mod city_object;
+pub mod context;
+mod dynamizer;
+mod external_reference;
+pub mod feature;
+mod point_cloud;
 pub mod space;
 pub mod transportation;
 pub mod util;
+pub mod versioning;
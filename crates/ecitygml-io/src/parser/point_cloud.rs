@@ -0,0 +1,167 @@
+use crate::error::Error;
+use crate::parser::util::extract_xml_element_attributes;
+use ecitygml_core::model::point_cloud::{MultiPoint, PointCloud};
+use egml::model::base::{AbstractGml, Id};
+use egml::model::geometry::DirectPosition;
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use tracing::warn;
+
+pub fn parse_point_cloud(id: &Id, xml_document: &str) -> Result<PointCloud, Error> {
+    let mut point_cloud = PointCloud::new(AbstractGml::new(id.clone()));
+
+    let mut reader = Reader::from_str(xml_document);
+    reader.config_mut().trim_text(true);
+
+    let mut txt = Vec::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"pcl:PointCloud" => {
+                    let extracted_attributes = extract_xml_element_attributes(&reader, &e);
+                    if let Some(point_cloud_id) = extracted_attributes
+                        .get("id")
+                        .and_then(|x| Id::try_from(x.as_str()).ok())
+                    {
+                        point_cloud.abstract_gml.id = point_cloud_id;
+                    }
+                }
+                b"pcl:mimeType" => {
+                    point_cloud.mime_type = Some(reader.read_text(e.name())?.trim().to_string());
+                }
+                b"pcl:pointFile" => {
+                    point_cloud.point_file = Some(reader.read_text(e.name())?.trim().to_string());
+                }
+                b"pcl:pointFileSrsName" => {
+                    point_cloud.point_file_srs_name =
+                        Some(reader.read_text(e.name())?.trim().to_string());
+                }
+                b"pcl:points" => {
+                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
+                    point_cloud.points = parse_multi_point(&xml_snippet)
+                        .map_err(|e| {
+                            warn!(
+                                "points of point cloud (id={}) contain invalid geometry: {}",
+                                id,
+                                e.to_string()
+                            );
+                        })
+                        .ok();
+                }
+                _ => {
+                    reader.read_to_end(e.name())?;
+                }
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
+            Ok(Event::Text(e)) => txt.push(e.decode().unwrap().into_owned()),
+            _ => (),
+        }
+    }
+
+    Ok(point_cloud)
+}
+
+/// Parses a `gml:MultiPoint` given either by `gml:pointMember` or `gml:pointMembers`.
+pub fn parse_multi_point(xml_document: &str) -> Result<MultiPoint, Error> {
+    let mut point_member: Vec<DirectPosition> = Vec::new();
+
+    let mut reader = Reader::from_str(xml_document);
+    reader.config_mut().trim_text(true);
+
+    let mut txt = Vec::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) if e.name().as_ref() == b"gml:Point" => {
+                let xml_snippet = reader.read_text(e.name())?.into_owned();
+                let point = egml::io::parse_point(
+                    format!("<gml:Point>{xml_snippet}</gml:Point>").as_bytes(),
+                )?;
+                point_member.push(point);
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
+            Ok(Event::Text(e)) => txt.push(e.decode().unwrap().into_owned()),
+            _ => (),
+        }
+    }
+
+    let multi_point = MultiPoint::new(point_member)?;
+    Ok(multi_point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_point_cloud_with_inline_points() {
+        let xml_document = String::from(
+            "<pcl:PointCloud gml:id=\"point_cloud_1\">
+  <pcl:points>
+    <gml:MultiPoint>
+      <gml:pointMember>
+        <gml:Point>
+          <gml:pos srsDimension=\"3\">678000.0 5403659.0 417.0</gml:pos>
+        </gml:Point>
+      </gml:pointMember>
+      <gml:pointMembers>
+        <gml:Point>
+          <gml:pos srsDimension=\"3\">678001.0 5403660.0 418.5</gml:pos>
+        </gml:Point>
+        <gml:Point>
+          <gml:pos srsDimension=\"3\">678002.0 5403658.0 416.0</gml:pos>
+        </gml:Point>
+      </gml:pointMembers>
+    </gml:MultiPoint>
+  </pcl:points>
+</pcl:PointCloud>",
+        );
+        let id = Id::from_hashed_string("fallback");
+
+        let point_cloud = parse_point_cloud(&id, &xml_document).expect("should work");
+
+        assert_eq!(
+            point_cloud.abstract_gml.id,
+            Id::try_from("point_cloud_1").unwrap()
+        );
+        assert!(!point_cloud.is_external());
+        let points = point_cloud.points.expect("should contain points");
+        assert_eq!(points.point_member().len(), 3);
+        assert_eq!(points.point_member()[1].z(), 418.5);
+    }
+
+    #[test]
+    fn test_parse_point_cloud_with_external_file() {
+        let xml_document = String::from(
+            "<pcl:PointCloud>
+  <pcl:mimeType>application/vnd.laszip</pcl:mimeType>
+  <pcl:pointFile>point_clouds/building_1.laz</pcl:pointFile>
+  <pcl:pointFileSrsName>EPSG:25832</pcl:pointFileSrsName>
+</pcl:PointCloud>",
+        );
+        let id = Id::from_hashed_string("fallback");
+
+        let point_cloud = parse_point_cloud(&id, &xml_document).expect("should work");
+
+        assert_eq!(point_cloud.abstract_gml.id, id);
+        assert!(point_cloud.is_external());
+        assert_eq!(
+            point_cloud.mime_type.as_deref(),
+            Some("application/vnd.laszip")
+        );
+        assert_eq!(
+            point_cloud.point_file.as_deref(),
+            Some("point_clouds/building_1.laz")
+        );
+        assert_eq!(
+            point_cloud.point_file_srs_name.as_deref(),
+            Some("EPSG:25832")
+        );
+        assert_eq!(point_cloud.points, None);
+    }
+}
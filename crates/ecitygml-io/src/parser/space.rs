@@ -1,268 +1,291 @@
 use crate::error::Error;
+use crate::parser::context::ParseContext;
+use crate::parser::feature::{FeatureParser, parse_feature};
 use egml::io::{parse_multi_surface, parse_solid};
 use egml::model::base::Id;
+use egml::model::geometry::MultiSurface;
 
-use crate::parser::city_object::parse_city_object;
-use ecitygml_core::model::core::{ImplicitGeometry, OccupiedSpace, Space, ThematicSurface};
+use crate::parser::point_cloud::parse_point_cloud;
+use ecitygml_core::model::core::{
+    CityObject, ImplicitGeometry, OccupiedSpace, Space, ThematicSurface,
+};
 use quick_xml::Reader;
-use quick_xml::events::Event;
+use quick_xml::events::{BytesStart, Event};
 use tracing::warn;
 
-pub fn parse_space(id: &Id, xml_document: &String) -> Result<Space, Error> {
-    let city_object = parse_city_object(id, xml_document)?;
-    let mut space = Space::new(city_object);
-
-    let mut reader = Reader::from_str(xml_document.as_str());
-    reader.config_mut().trim_text(true);
+impl FeatureParser for Space {
+    fn from_city_object(city_object: CityObject) -> Self {
+        Space::new(city_object)
+    }
 
-    let mut txt = Vec::new();
-    let mut buf = Vec::new();
+    fn city_object_mut(&mut self) -> &mut CityObject {
+        &mut self.city_object
+    }
 
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => match e.name().as_ref() {
-                b"lod1Solid" => {
-                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                    space.lod1_solid = parse_solid(&xml_snippet)
-                        .map_err(|e| {
-                            warn!(
-                                "lod1_solid of feature (id={}) contains invalid geometry: {}",
-                                id,
-                                e.to_string()
-                            );
-                        })
-                        .ok();
-                }
-                b"lod2Solid" => {
-                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                    space.lod2_solid = parse_solid(&xml_snippet)
-                        .map_err(|e| {
-                            warn!(
-                                "lod2_solid of feature (id={}) contains invalid geometry: {}",
-                                id,
-                                e.to_string()
-                            );
-                        })
-                        .ok();
-                }
-                b"lod3Solid" => {
-                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                    space.lod3_solid = parse_solid(&xml_snippet)
-                        .map_err(|e| {
-                            warn!(
-                                "lod3_solid of feature (id={}) contains invalid geometry: {}",
-                                id,
-                                e.to_string()
-                            );
-                        })
-                        .ok();
-                }
-                b"lod0MultiSurface" => {
-                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                    space.lod0_multi_surface = parse_multi_surface(&xml_snippet)
-                        .map_err(|e| {
-                            warn!(
-                                "lod0_multi_surface of feature (id={}) contains invalid geometry: {}",
-                                id,
-                                e.to_string()
-                            );
-                        })
-                        .ok();
-                }
-                b"lod2MultiSurface" => {
-                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                    space.lod2_multi_surface = parse_multi_surface(&xml_snippet)
-                        .map_err(|e| {
-                            warn!(
-                                "lod2_multi_surface of feature (id={}) contains invalid geometry: {}",
-                                id,
-                                e.to_string()
-                            );
-                        })
-                        .ok();
-                }
-                b"lod3MultiSurface" => {
-                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                    space.lod3_multi_surface = parse_multi_surface(&xml_snippet)
-                        .map_err(|e| {
-                            warn!(
-                                "lod3_multi_surface of feature (id={}) contains invalid geometry: {}",
-                                id,
-                                e.to_string()
-                            );
-                        })
-                        .ok();
-                }
-                _ => {
-                    reader.read_to_end(e.name())?;
-                }
-            },
-            Ok(Event::Eof) => break,
-            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
-            Ok(Event::Text(e)) => txt.push(e.decode().unwrap().into_owned()),
-            _ => (),
+    fn parse_property<'a>(
+        &mut self,
+        id: &Id,
+        reader: &mut Reader<&'a [u8]>,
+        start: &BytesStart<'a>,
+        _context: &ParseContext,
+    ) -> Result<bool, Error> {
+        match start.name().as_ref() {
+            b"lod1Solid" => {
+                let xml_snippet = reader.read_text(start.name())?;
+                self.lod1_solid = parse_solid(&xml_snippet)
+                    .map_err(|e| {
+                        warn!(
+                            "lod1_solid of feature (id={}) contains invalid geometry: {}",
+                            id,
+                            e.to_string()
+                        );
+                    })
+                    .ok();
+            }
+            b"lod2Solid" => {
+                let xml_snippet = reader.read_text(start.name())?;
+                self.lod2_solid = parse_solid(&xml_snippet)
+                    .map_err(|e| {
+                        warn!(
+                            "lod2_solid of feature (id={}) contains invalid geometry: {}",
+                            id,
+                            e.to_string()
+                        );
+                    })
+                    .ok();
+            }
+            b"lod3Solid" => {
+                let xml_snippet = reader.read_text(start.name())?;
+                self.lod3_solid = parse_solid(&xml_snippet)
+                    .map_err(|e| {
+                        warn!(
+                            "lod3_solid of feature (id={}) contains invalid geometry: {}",
+                            id,
+                            e.to_string()
+                        );
+                    })
+                    .ok();
+            }
+            b"lod0MultiSurface" => {
+                let xml_snippet = reader.read_text(start.name())?;
+                self.lod0_multi_surface = parse_compact_multi_surface(&xml_snippet)
+                    .map_err(|e| {
+                        warn!(
+                            "lod0_multi_surface of feature (id={}) contains invalid geometry: {}",
+                            id,
+                            e.to_string()
+                        );
+                    })
+                    .ok();
+            }
+            b"lod2MultiSurface" => {
+                let xml_snippet = reader.read_text(start.name())?;
+                self.lod2_multi_surface = parse_compact_multi_surface(&xml_snippet)
+                    .map_err(|e| {
+                        warn!(
+                            "lod2_multi_surface of feature (id={}) contains invalid geometry: {}",
+                            id,
+                            e.to_string()
+                        );
+                    })
+                    .ok();
+            }
+            b"lod3MultiSurface" => {
+                let xml_snippet = reader.read_text(start.name())?;
+                self.lod3_multi_surface = parse_compact_multi_surface(&xml_snippet)
+                    .map_err(|e| {
+                        warn!(
+                            "lod3_multi_surface of feature (id={}) contains invalid geometry: {}",
+                            id,
+                            e.to_string()
+                        );
+                    })
+                    .ok();
+            }
+            b"pointCloud" => {
+                let xml_snippet = reader.read_text(start.name())?;
+                let point_cloud_id = Id::from_hashed_bytes(xml_snippet.as_bytes());
+                self.point_cloud = parse_point_cloud(&point_cloud_id, &xml_snippet)
+                    .map_err(|e| {
+                        warn!(
+                            "point_cloud of feature (id={}) could not be parsed: {}",
+                            id,
+                            e.to_string()
+                        );
+                    })
+                    .ok();
+            }
+            _ => return Ok(false),
         }
-    }
 
-    Ok(space)
+        Ok(true)
+    }
 }
 
-pub fn parse_occupied_space(id: &Id, xml_document: &String) -> Result<OccupiedSpace, Error> {
-    let space = parse_space(id, xml_document)?;
-    let mut occupied_space = OccupiedSpace::new(space);
-
-    let mut reader = Reader::from_str(xml_document.as_str());
-    reader.config_mut().trim_text(true);
+impl FeatureParser for OccupiedSpace {
+    fn from_city_object(city_object: CityObject) -> Self {
+        OccupiedSpace::new(Space::new(city_object))
+    }
 
-    let mut txt = Vec::new();
-    let mut buf = Vec::new();
+    fn city_object_mut(&mut self) -> &mut CityObject {
+        &mut self.space.city_object
+    }
 
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => match e.name().as_ref() {
-                b"lod1ImplicitRepresentation" => {
-                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                    occupied_space.lod1_implicit_representation =
-                        parse_implicit_geometry(&xml_snippet)
-                            .map_err(|e| {
-                                warn!(
-                                    "lod1_implicit_representation of feature (id={}) contains invalid geometry: {}",
-                                    id,
-                                    e.to_string()
-                                );
-                            })
-                            .ok();
-                }
-                b"lod2ImplicitRepresentation" => {
-                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                    occupied_space.lod2_implicit_representation =
-                        parse_implicit_geometry(&xml_snippet)
-                            .map_err(|e| {
-                                warn!(
-                                    "lod2_implicit_representation of feature (id={}) contains invalid geometry: {}",
-                                    id,
-                                    e.to_string()
-                                );
-                            })
-                            .ok();
-                }
-                b"lod3ImplicitRepresentation" => {
-                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                    occupied_space.lod3_implicit_representation =
-                        parse_implicit_geometry(&xml_snippet)
-                            .map_err(|e| {
-                                warn!(
-                                    "lod3_implicit_representation of feature (id={}) contains invalid geometry: {}",
-                                    id,
-                                    e.to_string()
-                                );
-                            })
-                            .ok();
-                }
-                _ => {
-                    reader.read_to_end(e.name())?;
-                }
-            },
-            Ok(Event::Eof) => break,
-            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
-            Ok(Event::Text(e)) => txt.push(e.decode().unwrap().into_owned()),
-            _ => (),
+    fn parse_property<'a>(
+        &mut self,
+        id: &Id,
+        reader: &mut Reader<&'a [u8]>,
+        start: &BytesStart<'a>,
+        context: &ParseContext,
+    ) -> Result<bool, Error> {
+        match start.name().as_ref() {
+            b"lod1ImplicitRepresentation" => {
+                let xml_snippet = reader.read_text(start.name())?;
+                self.lod1_implicit_representation = parse_implicit_geometry(&xml_snippet)
+                    .map_err(|e| {
+                        warn!(
+                            "lod1_implicit_representation of feature (id={}) contains invalid geometry: {}",
+                            id,
+                            e.to_string()
+                        );
+                    })
+                    .ok();
+            }
+            b"lod2ImplicitRepresentation" => {
+                let xml_snippet = reader.read_text(start.name())?;
+                self.lod2_implicit_representation = parse_implicit_geometry(&xml_snippet)
+                    .map_err(|e| {
+                        warn!(
+                            "lod2_implicit_representation of feature (id={}) contains invalid geometry: {}",
+                            id,
+                            e.to_string()
+                        );
+                    })
+                    .ok();
+            }
+            b"lod3ImplicitRepresentation" => {
+                let xml_snippet = reader.read_text(start.name())?;
+                self.lod3_implicit_representation = parse_implicit_geometry(&xml_snippet)
+                    .map_err(|e| {
+                        warn!(
+                            "lod3_implicit_representation of feature (id={}) contains invalid geometry: {}",
+                            id,
+                            e.to_string()
+                        );
+                    })
+                    .ok();
+            }
+            _ => return self.space.parse_property(id, reader, start, context),
         }
-    }
 
-    Ok(occupied_space)
+        Ok(true)
+    }
 }
 
-pub fn parse_thematic_surface(id: &Id, xml_document: &String) -> Result<ThematicSurface, Error> {
-    let city_object = parse_city_object(id, xml_document)?;
-    let mut thematic_surface = ThematicSurface::new(city_object);
-
-    let mut reader = Reader::from_str(xml_document.as_str());
-    reader.config_mut().trim_text(true);
+impl FeatureParser for ThematicSurface {
+    fn from_city_object(city_object: CityObject) -> Self {
+        ThematicSurface::new(city_object)
+    }
 
-    let mut txt = Vec::new();
-    let mut buf = Vec::new();
+    fn city_object_mut(&mut self) -> &mut CityObject {
+        &mut self.city_object
+    }
 
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => match e.name().as_ref() {
-                b"lod0MultiSurface" => {
-                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                    thematic_surface.lod0_multi_surface = parse_multi_surface(&xml_snippet)
-                        .map_err(|e| {
-                            warn!(
-                                "lod0_multi_surface of feature (id={}) contains invalid geometry: {}",
-                                id,
-                                e.to_string()
-                            );
-                        })
-                        .ok();
-                }
-                b"lod1MultiSurface" => {
-                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                    thematic_surface.lod1_multi_surface = parse_multi_surface(&xml_snippet)
-                        .map_err(|e| {
-                            warn!(
-                                "lod1_multi_surface of feature (id={}) contains invalid geometry: {}",
-                                id,
-                                e.to_string()
-                            );
-                        })
-                        .ok();
-                }
-                b"lod2MultiSurface" => {
-                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                    thematic_surface.lod2_multi_surface = parse_multi_surface(&xml_snippet)
-                        .map_err(|e| {
-                            warn!(
-                                "lod2_multi_surface of feature (id={}) contains invalid geometry: {}",
-                                id,
-                                e.to_string()
-                            );
-                        })
-                        .ok();
-                }
-                b"lod3MultiSurface" => {
-                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                    thematic_surface.lod3_multi_surface = parse_multi_surface(&xml_snippet)
-                        .map_err(|e| {
-                            warn!(
-                                "lod3_multi_surface of feature (id={}) contains invalid geometry: {}",id,
-                                e.to_string()
-                            );
-                        })
-                        .ok();
-                }
-                _ => {
-                    reader.read_to_end(e.name())?;
-                }
-            },
-            Ok(Event::Eof) => break,
-            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
-            Ok(Event::Text(e)) => txt.push(e.decode().unwrap().into_owned()),
-            _ => (),
+    fn parse_property<'a>(
+        &mut self,
+        id: &Id,
+        reader: &mut Reader<&'a [u8]>,
+        start: &BytesStart<'a>,
+        _context: &ParseContext,
+    ) -> Result<bool, Error> {
+        match start.name().as_ref() {
+            b"lod0MultiSurface" => {
+                let xml_snippet = reader.read_text(start.name())?;
+                self.lod0_multi_surface = parse_compact_multi_surface(&xml_snippet)
+                    .map_err(|e| {
+                        warn!(
+                            "lod0_multi_surface of feature (id={}) contains invalid geometry: {}",
+                            id,
+                            e.to_string()
+                        );
+                    })
+                    .ok();
+            }
+            b"lod1MultiSurface" => {
+                let xml_snippet = reader.read_text(start.name())?;
+                self.lod1_multi_surface = parse_compact_multi_surface(&xml_snippet)
+                    .map_err(|e| {
+                        warn!(
+                            "lod1_multi_surface of feature (id={}) contains invalid geometry: {}",
+                            id,
+                            e.to_string()
+                        );
+                    })
+                    .ok();
+            }
+            b"lod2MultiSurface" => {
+                let xml_snippet = reader.read_text(start.name())?;
+                self.lod2_multi_surface = parse_compact_multi_surface(&xml_snippet)
+                    .map_err(|e| {
+                        warn!(
+                            "lod2_multi_surface of feature (id={}) contains invalid geometry: {}",
+                            id,
+                            e.to_string()
+                        );
+                    })
+                    .ok();
+            }
+            b"lod3MultiSurface" => {
+                let xml_snippet = reader.read_text(start.name())?;
+                self.lod3_multi_surface = parse_compact_multi_surface(&xml_snippet)
+                    .map_err(|e| {
+                        warn!(
+                            "lod3_multi_surface of feature (id={}) contains invalid geometry: {}",
+                            id,
+                            e.to_string()
+                        );
+                    })
+                    .ok();
+            }
+            _ => return Ok(false),
         }
+
+        Ok(true)
     }
+}
+
+/// Parses a multi surface and reallocates its polygons and points with their exact length.
+///
+/// The geometry parser leaves the vectors with spare capacity, which took more than a quarter of
+/// the memory of LoD2 models with one polygon per boundary surface.
+fn parse_compact_multi_surface(xml_snippet: &str) -> Result<MultiSurface, egml::io::Error> {
+    let mut multi_surface = parse_multi_surface(xml_snippet)?;
+    let surface_member = multi_surface.surface_member().clone();
+    multi_surface.set_surface_member(surface_member)?;
+    Ok(multi_surface)
+}
 
-    Ok(thematic_surface)
+pub fn parse_occupied_space(
+    id: &Id,
+    xml_document: &str,
+    context: &ParseContext,
+) -> Result<OccupiedSpace, Error> {
+    parse_feature(id, xml_document, context)
 }
 
-pub fn parse_implicit_geometry(xml_document: &String) -> Result<ImplicitGeometry, Error> {
+pub fn parse_implicit_geometry(xml_document: &str) -> Result<ImplicitGeometry, Error> {
     let mut implicit_geometry = ImplicitGeometry::default();
 
-    let mut reader = Reader::from_str(xml_document.as_str());
+    let mut reader = Reader::from_str(xml_document);
     reader.config_mut().trim_text(true);
 
     let mut txt = Vec::new();
     let mut buf = Vec::new();
     loop {
         match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => {
-                if e.name().as_ref() == b"referencePoint" {
-                    let xml_snippet = reader.read_text(e.name())?.into_owned();
-                    implicit_geometry.reference_point =
-                        egml::io::parse_point(xml_snippet.as_bytes())?;
-                }
+            Ok(Event::Start(e)) if e.name().as_ref() == b"referencePoint" => {
+                let xml_snippet = reader.read_text(e.name())?.into_owned();
+                implicit_geometry.reference_point = egml::io::parse_point(xml_snippet.as_bytes())?;
             }
             Ok(Event::Eof) => break,
             Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
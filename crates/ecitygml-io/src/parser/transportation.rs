@@ -1,240 +1,352 @@
 use crate::Error;
-use crate::parser::space::{parse_space, parse_thematic_surface};
-use crate::parser::util::extract_xml_element_attributes;
+use crate::parser::context::ParseContext;
+use crate::parser::feature::{
+    FeatureParser, parse_feature, parse_feature_member, parse_property_members,
+};
+use crate::parser::util::{extract_xml_element_attributes, parse_href};
+use ecitygml_core::model::core::{CityObject, Space, ThematicSurface};
 use ecitygml_core::model::transportation::{
     AuxiliaryTrafficArea, AuxiliaryTrafficSpace, Intersection, Road, Section, TrafficArea,
     TrafficSpace,
 };
 use egml::model::base::Id;
 use quick_xml::Reader;
-use quick_xml::events::Event;
-use std::collections::HashMap;
-
-pub fn parse_road(id: &Id, xml_document: &String) -> Result<Road, Error> {
-    let space = parse_space(id, xml_document)?;
-    let mut road = Road::new(space);
-
-    let mut reader = Reader::from_str(xml_document.as_str());
-    reader.config_mut().trim_text(true);
-
-    let mut txt = Vec::new();
-    let mut buf = Vec::new();
-
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => {
-                let extracted_attributes: HashMap<String, String> =
-                    extract_xml_element_attributes(&reader, &e);
-                let id: Option<Id> = extracted_attributes
-                    .get("id")
-                    .and_then(|x| Id::try_from(x.as_str()).ok());
-
-                match e.name().as_ref() {
-                    b"tran:Section" => {
-                        let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                        let id: Id = id.unwrap_or(Id::from_hashed_string(&xml_snippet));
-
-                        let section = parse_section(&id, &xml_snippet)?;
-                        road.section.push(section);
-                    }
-                    b"tran:Intersection" => {
-                        let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                        let id: Id = id.unwrap_or(Id::from_hashed_string(&xml_snippet));
-
-                        let intersection = parse_intersection(&id, &xml_snippet)?;
-                        road.intersection.push(intersection);
-                    }
-                    _ => {}
+use quick_xml::events::BytesStart;
+
+impl FeatureParser for Road {
+    fn from_city_object(city_object: CityObject) -> Self {
+        Road::new(Space::new(city_object))
+    }
+
+    fn city_object_mut(&mut self) -> &mut CityObject {
+        &mut self.space.city_object
+    }
+
+    fn parse_property<'a>(
+        &mut self,
+        id: &Id,
+        reader: &mut Reader<&'a [u8]>,
+        start: &BytesStart<'a>,
+        context: &ParseContext,
+    ) -> Result<bool, Error> {
+        match start.name().as_ref() {
+            b"tran:section" | b"tran:intersection" => {
+                parse_property_members(reader, start, |reader, member| {
+                    parse_road_member(self, reader, member, context)
+                })?;
+                Ok(true)
+            }
+            _ => {
+                if parse_road_member(self, reader, start, context)? {
+                    return Ok(true);
                 }
+                self.space.parse_property(id, reader, start, context)
             }
-            Ok(Event::Eof) => break,
-            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
-            Ok(Event::Text(e)) => txt.push(e.decode().unwrap().into_owned()),
-            _ => (),
         }
     }
+}
+
+fn parse_road_member<'a>(
+    road: &mut Road,
+    reader: &mut Reader<&'a [u8]>,
+    start: &BytesStart<'a>,
+    context: &ParseContext,
+) -> Result<bool, Error> {
+    match start.name().as_ref() {
+        b"tran:Section" => {
+            let section = parse_feature_member(reader, start, context)?;
+            road.section.push(section);
+        }
+        b"tran:Intersection" => {
+            let intersection = parse_feature_member(reader, start, context)?;
+            road.intersection.push(intersection);
+        }
+        _ => return Ok(false),
+    }
 
-    Ok(road)
+    Ok(true)
 }
 
-pub fn parse_section(id: &Id, xml_document: &String) -> Result<Section, Error> {
-    let space = parse_space(id, xml_document)?;
-    let mut section = Section::new(space);
-
-    let mut reader = Reader::from_str(xml_document.as_str());
-    reader.config_mut().trim_text(true);
-
-    let mut txt = Vec::new();
-    let mut buf = Vec::new();
-
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => {
-                let extracted_attributes: HashMap<String, String> =
-                    extract_xml_element_attributes(&reader, &e);
-                let id: Option<Id> = extracted_attributes
-                    .get("id")
-                    .and_then(|x| Id::try_from(x.as_str()).ok());
-
-                match e.name().as_ref() {
-                    b"tran:TrafficSpace" => {
-                        let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                        let id: Id = id.unwrap_or(Id::from_hashed_string(&xml_snippet));
-
-                        let traffic_space = parse_traffic_space(&id, &xml_snippet)?;
-                        section.traffic_space.push(traffic_space);
-                    }
-                    b"tran:AuxiliaryTrafficSpace" => {
-                        let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                        let id: Id = id.unwrap_or(Id::from_hashed_string(&xml_snippet));
-
-                        let auxiliary_traffic_space =
-                            parse_auxiliary_traffic_space(&id, &xml_snippet)?;
-                        section
-                            .auxiliary_traffic_space
-                            .push(auxiliary_traffic_space);
-                    }
-                    _ => {}
+impl FeatureParser for Section {
+    fn from_city_object(city_object: CityObject) -> Self {
+        Section::new(Space::new(city_object))
+    }
+
+    fn city_object_mut(&mut self) -> &mut CityObject {
+        &mut self.space.city_object
+    }
+
+    fn parse_property<'a>(
+        &mut self,
+        id: &Id,
+        reader: &mut Reader<&'a [u8]>,
+        start: &BytesStart<'a>,
+        context: &ParseContext,
+    ) -> Result<bool, Error> {
+        match start.name().as_ref() {
+            b"tran:trafficSpace" | b"tran:auxiliaryTrafficSpace" => {
+                parse_property_members(reader, start, |reader, member| {
+                    parse_traffic_space_member(
+                        &mut self.traffic_space,
+                        &mut self.auxiliary_traffic_space,
+                        reader,
+                        member,
+                        context,
+                    )
+                })?;
+                Ok(true)
+            }
+            _ => {
+                if parse_traffic_space_member(
+                    &mut self.traffic_space,
+                    &mut self.auxiliary_traffic_space,
+                    reader,
+                    start,
+                    context,
+                )? {
+                    return Ok(true);
                 }
+                self.space.parse_property(id, reader, start, context)
             }
-            Ok(Event::Eof) => break,
-            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
-            Ok(Event::Text(e)) => txt.push(e.decode().unwrap().into_owned()),
-            _ => (),
         }
     }
-
-    Ok(section)
 }
 
-pub fn parse_intersection(id: &Id, xml_document: &String) -> Result<Intersection, Error> {
-    let space = parse_space(id, xml_document)?;
-    let mut intersection = Intersection::new(space);
-
-    let mut reader = Reader::from_str(xml_document.as_str());
-    reader.config_mut().trim_text(true);
-
-    let mut txt = Vec::new();
-    let mut buf = Vec::new();
-
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => {
-                let extracted_attributes: HashMap<String, String> =
-                    extract_xml_element_attributes(&reader, &e);
-                let id: Option<Id> = extracted_attributes
-                    .get("id")
-                    .and_then(|x| Id::try_from(x.as_str()).ok());
-
-                match e.name().as_ref() {
-                    b"tran:TrafficSpace" => {
-                        let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                        let id: Id = id.unwrap_or(Id::from_hashed_string(&xml_snippet));
-
-                        let traffic_space = parse_traffic_space(&id, &xml_snippet)?;
-                        intersection.traffic_space.push(traffic_space);
-                    }
-                    b"tran:AuxiliaryTrafficSpace" => {
-                        let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                        let id: Id = id.unwrap_or(Id::from_hashed_string(&xml_snippet));
-
-                        let auxiliary_traffic_space =
-                            parse_auxiliary_traffic_space(&id, &xml_snippet)?;
-                        intersection
-                            .auxiliary_traffic_space
-                            .push(auxiliary_traffic_space);
-                    }
-                    _ => {}
+impl FeatureParser for Intersection {
+    fn from_city_object(city_object: CityObject) -> Self {
+        Intersection::new(Space::new(city_object))
+    }
+
+    fn city_object_mut(&mut self) -> &mut CityObject {
+        &mut self.space.city_object
+    }
+
+    fn parse_property<'a>(
+        &mut self,
+        id: &Id,
+        reader: &mut Reader<&'a [u8]>,
+        start: &BytesStart<'a>,
+        context: &ParseContext,
+    ) -> Result<bool, Error> {
+        match start.name().as_ref() {
+            b"tran:trafficSpace" | b"tran:auxiliaryTrafficSpace" => {
+                parse_property_members(reader, start, |reader, member| {
+                    parse_traffic_space_member(
+                        &mut self.traffic_space,
+                        &mut self.auxiliary_traffic_space,
+                        reader,
+                        member,
+                        context,
+                    )
+                })?;
+                Ok(true)
+            }
+            _ => {
+                if parse_traffic_space_member(
+                    &mut self.traffic_space,
+                    &mut self.auxiliary_traffic_space,
+                    reader,
+                    start,
+                    context,
+                )? {
+                    return Ok(true);
                 }
+                self.space.parse_property(id, reader, start, context)
             }
-            Ok(Event::Eof) => break,
-            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
-            Ok(Event::Text(e)) => txt.push(e.decode().unwrap().into_owned()),
-            _ => (),
         }
     }
-
-    Ok(intersection)
 }
 
-pub fn parse_traffic_space(id: &Id, xml_document: &String) -> Result<TrafficSpace, Error> {
-    let space = parse_space(id, xml_document)?;
-    let mut traffic_space = TrafficSpace::new(space);
-
-    let mut reader = Reader::from_str(xml_document.as_str());
-    reader.config_mut().trim_text(true);
+fn parse_traffic_space_member<'a>(
+    traffic_space: &mut Vec<TrafficSpace>,
+    auxiliary_traffic_space: &mut Vec<AuxiliaryTrafficSpace>,
+    reader: &mut Reader<&'a [u8]>,
+    start: &BytesStart<'a>,
+    context: &ParseContext,
+) -> Result<bool, Error> {
+    match start.name().as_ref() {
+        b"tran:TrafficSpace" => {
+            traffic_space.push(parse_feature_member(reader, start, context)?);
+        }
+        b"tran:AuxiliaryTrafficSpace" => {
+            auxiliary_traffic_space.push(parse_feature_member(reader, start, context)?);
+        }
+        _ => return Ok(false),
+    }
 
-    let mut txt = Vec::new();
-    let mut buf = Vec::new();
+    Ok(true)
+}
 
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => {
-                let extracted_attributes: HashMap<String, String> =
-                    extract_xml_element_attributes(&reader, &e);
-                let id: Option<Id> = extracted_attributes
-                    .get("id")
-                    .and_then(|x| Id::try_from(x.as_str()).ok());
+impl FeatureParser for TrafficSpace {
+    fn from_city_object(city_object: CityObject) -> Self {
+        TrafficSpace::new(Space::new(city_object))
+    }
 
-                if e.name().as_ref() == b"tran:TrafficArea" {
-                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                    let id: Id = id.unwrap_or(Id::from_hashed_string(&xml_snippet));
+    fn city_object_mut(&mut self) -> &mut CityObject {
+        &mut self.space.city_object
+    }
 
-                    let thematic_surface = parse_thematic_surface(&id, &xml_snippet)?;
-                    let traffic_area = TrafficArea::new(thematic_surface);
-                    traffic_space.traffic_area.push(traffic_area);
+    fn parse_property<'a>(
+        &mut self,
+        id: &Id,
+        reader: &mut Reader<&'a [u8]>,
+        start: &BytesStart<'a>,
+        context: &ParseContext,
+    ) -> Result<bool, Error> {
+        let mut parse_member = |reader: &mut Reader<&'a [u8]>, member: &BytesStart<'a>| {
+            if member.name().as_ref() != b"tran:TrafficArea" {
+                return Ok(false);
+            }
+            let thematic_surface: ThematicSurface = parse_feature_member(reader, member, context)?;
+            self.traffic_area.push(TrafficArea::new(thematic_surface));
+            Ok(true)
+        };
+
+        match start.name().as_ref() {
+            b"boundary" => {
+                parse_property_members(reader, start, parse_member)?;
+                Ok(true)
+            }
+            b"tran:predecessor" | b"tran:successor" => {
+                self.parse_empty_property(reader, start)?;
+                reader.read_to_end(start.name())?;
+                Ok(true)
+            }
+            _ => {
+                if parse_member(reader, start)? {
+                    return Ok(true);
                 }
+                self.space.parse_property(id, reader, start, context)
             }
-            Ok(Event::Eof) => break,
-            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
-            Ok(Event::Text(e)) => txt.push(e.decode().unwrap().into_owned()),
-            _ => (),
         }
     }
 
-    Ok(traffic_space)
+    fn parse_empty_property(
+        &mut self,
+        reader: &Reader<&[u8]>,
+        start: &BytesStart,
+    ) -> Result<bool, Error> {
+        let links = match start.name().as_ref() {
+            b"tran:predecessor" => &mut self.predecessor,
+            b"tran:successor" => &mut self.successor,
+            _ => return Ok(false),
+        };
+        if let Some(id) = extract_xml_element_attributes(reader, start)
+            .get("href")
+            .and_then(|x| parse_href(x))
+        {
+            links.push(id);
+        }
+        Ok(true)
+    }
 }
 
-pub fn parse_auxiliary_traffic_space(
-    id: &Id,
-    xml_document: &String,
-) -> Result<AuxiliaryTrafficSpace, Error> {
-    let space = parse_space(id, xml_document)?;
-    let mut auxiliary_traffic_space = AuxiliaryTrafficSpace::new(space);
-
-    let mut reader = Reader::from_str(xml_document.as_str());
-    reader.config_mut().trim_text(true);
-
-    let mut txt = Vec::new();
-    let mut buf = Vec::new();
-
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => {
-                let extracted_attributes: HashMap<String, String> =
-                    extract_xml_element_attributes(&reader, &e);
-                let id: Option<Id> = extracted_attributes
-                    .get("id")
-                    .and_then(|x| Id::try_from(x.as_str()).ok());
-
-                if e.name().as_ref() == b"tran:AuxiliaryTrafficArea" {
-                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                    let id: Id = id.unwrap_or(Id::from_hashed_string(&xml_snippet));
-
-                    let thematic_surface = parse_thematic_surface(&id, &xml_snippet)?;
-                    let auxiliary_traffic_area = AuxiliaryTrafficArea::new(thematic_surface);
-
-                    auxiliary_traffic_space
-                        .auxiliary_traffic_area
-                        .push(auxiliary_traffic_area);
+impl FeatureParser for AuxiliaryTrafficSpace {
+    fn from_city_object(city_object: CityObject) -> Self {
+        AuxiliaryTrafficSpace::new(Space::new(city_object))
+    }
+
+    fn city_object_mut(&mut self) -> &mut CityObject {
+        &mut self.space.city_object
+    }
+
+    fn parse_property<'a>(
+        &mut self,
+        id: &Id,
+        reader: &mut Reader<&'a [u8]>,
+        start: &BytesStart<'a>,
+        context: &ParseContext,
+    ) -> Result<bool, Error> {
+        let mut parse_member = |reader: &mut Reader<&'a [u8]>, member: &BytesStart<'a>| {
+            if member.name().as_ref() != b"tran:AuxiliaryTrafficArea" {
+                return Ok(false);
+            }
+            let thematic_surface: ThematicSurface = parse_feature_member(reader, member, context)?;
+            self.auxiliary_traffic_area
+                .push(AuxiliaryTrafficArea::new(thematic_surface));
+            Ok(true)
+        };
+
+        match start.name().as_ref() {
+            b"boundary" => {
+                parse_property_members(reader, start, parse_member)?;
+                Ok(true)
+            }
+            _ => {
+                if parse_member(reader, start)? {
+                    return Ok(true);
                 }
+                self.space.parse_property(id, reader, start, context)
             }
-            Ok(Event::Eof) => break,
-            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
-            Ok(Event::Text(e)) => txt.push(e.decode().unwrap().into_owned()),
-            _ => (),
         }
     }
+}
 
-    Ok(auxiliary_traffic_space)
+pub fn parse_road(id: &Id, xml_document: &str, context: &ParseContext) -> Result<Road, Error> {
+    parse_feature(id, xml_document, context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_road_with_nested_spaces() {
+        let xml_document = String::from(
+            "<gml:name>Main Street</gml:name>
+  <tran:section>
+    <tran:Section gml:id=\"section_1\">
+      <tran:trafficSpace>
+        <tran:TrafficSpace gml:id=\"traffic_space_1\">
+          <boundary>
+            <tran:TrafficArea gml:id=\"traffic_area_1\"><gml:name>lane</gml:name></tran:TrafficArea>
+          </boundary>
+          <tran:predecessor xlink:href=\"#traffic_space_0\"/>
+          <tran:successor xlink:href=\"#traffic_space_2\"/>
+          <tran:successor xlink:href=\"#traffic_space_3\"></tran:successor>
+        </tran:TrafficSpace>
+      </tran:trafficSpace>
+      <tran:auxiliaryTrafficSpace>
+        <tran:AuxiliaryTrafficSpace gml:id=\"auxiliary_traffic_space_1\"><gml:name>sidewalk</gml:name></tran:AuxiliaryTrafficSpace>
+      </tran:auxiliaryTrafficSpace>
+    </tran:Section>
+  </tran:section>
+  <tran:intersection>
+    <tran:Intersection gml:id=\"intersection_1\"><gml:name>crossing</gml:name></tran:Intersection>
+  </tran:intersection>",
+        );
+        let id = Id::try_from("road_1").unwrap();
+
+        let road = parse_road(&id, &xml_document, &ParseContext::default()).expect("should work");
+
+        assert_eq!(
+            road.space.city_object.abstract_gml.name,
+            vec!["Main Street"]
+        );
+        assert_eq!(road.section.len(), 1);
+        assert_eq!(road.intersection.len(), 1);
+        let section = &road.section[0];
+        assert_eq!(section.traffic_space.len(), 1);
+        assert_eq!(section.auxiliary_traffic_space.len(), 1);
+        assert_eq!(section.traffic_space[0].traffic_area.len(), 1);
+        assert_eq!(
+            section.traffic_space[0].predecessor,
+            vec![Id::try_from("traffic_space_0").unwrap()]
+        );
+        assert_eq!(
+            section.traffic_space[0].successor,
+            vec![
+                Id::try_from("traffic_space_2").unwrap(),
+                Id::try_from("traffic_space_3").unwrap()
+            ]
+        );
+        assert_eq!(
+            section.traffic_space[0].traffic_area[0]
+                .thematic_surface
+                .city_object
+                .abstract_gml
+                .id,
+            Id::try_from("traffic_area_1").unwrap()
+        );
+    }
 }
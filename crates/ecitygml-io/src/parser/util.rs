@@ -1,3 +1,6 @@
+use crate::Error;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use egml::model::base::Id;
 use quick_xml::Reader;
 use quick_xml::events::BytesStart;
 use std::collections::HashMap;
@@ -38,3 +41,76 @@ pub fn extract_xml_element_attributes(
 
     extracted_attributes
 }
+
+/// Returns the referenced gml:id of a local `xlink:href` such as `#UUID_1234`.
+pub fn parse_href(href: &str) -> Option<Id> {
+    let href = href.trim();
+    Id::try_from(href.strip_prefix('#').unwrap_or(href)).ok()
+}
+
+/// Parses an `xs:dateTime` value.
+///
+/// Values without time zone are interpreted as UTC and plain dates as midnight.
+pub fn parse_date_time(value: &str) -> Result<DateTime<Utc>, Error> {
+    let value = value.trim();
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date_time.with_timezone(&Utc));
+    }
+    if let Ok(date_time) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f") {
+        return Ok(date_time.and_utc());
+    }
+
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")?;
+    Ok(date.and_time(Default::default()).and_utc())
+}
+
+/// Reads the element started by `start` including its start and end tag.
+///
+/// Returns the qualified element name and the XML fragment.
+pub fn read_xml_fragment(
+    reader: &mut Reader<&[u8]>,
+    start: &BytesStart,
+) -> Result<(String, String), Error> {
+    let decoder = reader.decoder();
+    let element_name = decoder
+        .decode(start.name().as_ref())
+        .map_err(quick_xml::Error::from)?
+        .into_owned();
+    let start_tag = decoder
+        .decode(start)
+        .map_err(quick_xml::Error::from)?
+        .into_owned();
+    let content = reader.read_text(start.name())?;
+    let xml_fragment = format!("<{start_tag}>{content}</{element_name}>");
+
+    Ok((element_name, xml_fragment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_date_time_variants() {
+        let expected = Utc.with_ymd_and_hms(2017, 1, 23, 10, 0, 0).unwrap();
+
+        assert_eq!(
+            parse_date_time("2017-01-23T10:00:00Z").expect("should work"),
+            expected
+        );
+        assert_eq!(
+            parse_date_time("2017-01-23T12:00:00+02:00").expect("should work"),
+            expected
+        );
+        assert_eq!(
+            parse_date_time("2017-01-23T10:00:00").expect("should work"),
+            expected
+        );
+        assert_eq!(
+            parse_date_time("2017-01-23").expect("should work"),
+            Utc.with_ymd_and_hms(2017, 1, 23, 0, 0, 0).unwrap()
+        );
+        assert!(parse_date_time("yesterday").is_err());
+    }
+}
@@ -0,0 +1,303 @@
+use crate::Error;
+use crate::parser::util::{extract_xml_element_attributes, parse_href};
+use ecitygml_core::model::versioning::{Transaction, TransactionType, Version, VersionTransition};
+use egml::io::parse_abstract_gml;
+use egml::model::base::Id;
+use quick_xml::Reader;
+use quick_xml::de;
+use quick_xml::events::{BytesStart, Event};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+pub fn parse_version(id: &Id, xml_document: &str) -> Result<Version, Error> {
+    let abstract_gml = parse_abstract_gml(xml_document, id.clone())?;
+    let mut version = Version::new(abstract_gml);
+
+    let mut reader = Reader::from_str(xml_document);
+    reader.config_mut().trim_text(true);
+
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"vers:tag" => {
+                    let text = reader.read_text(e.name())?;
+                    version.tag.push(text.trim().to_string());
+                }
+                b"vers:versionMember" => {
+                    let href = extract_xml_element_attributes(&reader, &e)
+                        .get("href")
+                        .and_then(|x| parse_href(x));
+                    // the inline feature itself is parsed as a top-level feature
+                    let inline_member_id = parse_inline_member_id(&mut reader)?;
+                    match href.or(inline_member_id) {
+                        Some(member_id) => version.version_member.push(member_id),
+                        None => warn!(
+                            "inline member of version (id={}) has no gml:id and cannot be referenced",
+                            id
+                        ),
+                    }
+                }
+                _ => {
+                    reader.read_to_end(e.name())?;
+                }
+            },
+            Ok(Event::Empty(e)) if e.name().as_ref() == b"vers:versionMember" => {
+                let extracted_attributes = extract_xml_element_attributes(&reader, &e);
+                if let Some(member_id) =
+                    extracted_attributes.get("href").and_then(|x| parse_href(x))
+                {
+                    version.version_member.push(member_id);
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
+            _ => (),
+        }
+    }
+
+    Ok(version)
+}
+
+/// Reads the content of a version member and returns the gml:id of its inline feature.
+fn parse_inline_member_id(reader: &mut Reader<&[u8]>) -> Result<Option<Id>, Error> {
+    let mut member_id: Option<Id> = None;
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                if member_id.is_none() {
+                    member_id = parse_id(reader, &e);
+                }
+                reader.read_to_end(e.name())?;
+            }
+            Event::Empty(e) if member_id.is_none() => {
+                member_id = parse_id(reader, &e);
+            }
+            Event::End(_) | Event::Eof => return Ok(member_id),
+            _ => {}
+        }
+    }
+}
+
+fn parse_id(reader: &Reader<&[u8]>, e: &BytesStart) -> Option<Id> {
+    extract_xml_element_attributes(reader, e)
+        .get("id")
+        .and_then(|x| Id::try_from(x.as_str()).ok())
+}
+
+pub fn parse_version_transition(id: &Id, xml_document: &str) -> Result<VersionTransition, Error> {
+    let abstract_gml = parse_abstract_gml(xml_document, id.clone())?;
+    let mut version_transition = VersionTransition::new(abstract_gml);
+
+    let mut reader = Reader::from_str(xml_document);
+    reader.config_mut().trim_text(true);
+
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"vers:reason" => {
+                    let text = reader.read_text(e.name())?;
+                    version_transition.reason = Some(text.trim().to_string());
+                }
+                b"vers:clonePredecessor" => {
+                    let text = reader.read_text(e.name())?;
+                    version_transition.clone_predecessor = text.trim() == "true";
+                }
+                b"vers:type" => {
+                    let text = reader.read_text(e.name())?;
+                    version_transition.transition_type = text
+                        .trim()
+                        .parse()
+                        .map_err(|_| {
+                            warn!(
+                                "type of version transition (id={}) has invalid value: {}",
+                                id, text
+                            );
+                        })
+                        .ok();
+                }
+                b"vers:transaction" => {
+                    let xml_snippet: String = reader.read_text(e.name())?.into_owned();
+                    let transaction = parse_transaction(&xml_snippet)
+                        .map_err(|e| {
+                            warn!(
+                                "transaction of version transition (id={}) could not be parsed: {}",
+                                id,
+                                e.to_string()
+                            );
+                        })
+                        .ok();
+                    if let Some(transaction) = transaction {
+                        version_transition.transaction.push(transaction);
+                    }
+                }
+                _ => {
+                    reader.read_to_end(e.name())?;
+                }
+            },
+            Ok(Event::Empty(e)) => {
+                let extracted_attributes = extract_xml_element_attributes(&reader, &e);
+                let href: Option<Id> = extracted_attributes.get("href").and_then(|x| parse_href(x));
+
+                match e.name().as_ref() {
+                    b"vers:from" => version_transition.from = href,
+                    b"vers:to" => version_transition.to = href,
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
+            _ => (),
+        }
+    }
+
+    Ok(version_transition)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+struct GmlTransaction {
+    #[serde(rename = "type")]
+    pub transaction_type: String,
+    #[serde(rename = "oldFeature", default)]
+    pub old_feature: Option<FeatureReference>,
+    #[serde(rename = "newFeature", default)]
+    pub new_feature: Option<FeatureReference>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+struct FeatureReference {
+    #[serde(rename = "@href", default)]
+    pub xlink_href: String,
+}
+
+impl TryFrom<GmlTransaction> for Transaction {
+    type Error = Error;
+
+    fn try_from(value: GmlTransaction) -> Result<Self, Self::Error> {
+        let transaction_type: TransactionType =
+            value.transaction_type.trim().parse().map_err(|_| {
+                Error::ElementNotFound(format!("type `{}`", value.transaction_type))
+            })?;
+
+        let mut transaction = Transaction::new(transaction_type);
+        transaction.old_feature = value.old_feature.and_then(|x| parse_href(&x.xlink_href));
+        transaction.new_feature = value.new_feature.and_then(|x| parse_href(&x.xlink_href));
+
+        Ok(transaction)
+    }
+}
+
+fn parse_transaction(xml_document: &str) -> Result<Transaction, Error> {
+    let parsed_transaction: GmlTransaction = de::from_str(xml_document)?;
+    parsed_transaction.try_into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ecitygml_core::model::versioning::TransitionType;
+
+    #[test]
+    fn test_parse_version_basic() {
+        let id = Id::try_from("version_2030").expect("should work");
+        let xml_document = String::from(
+            "<gml:name>District plan 2030</gml:name>
+      <vers:tag>planned</vers:tag>
+      <vers:tag>2030</vers:tag>
+      <vers:versionMember xlink:href=\"#building_1\"/>
+      <vers:versionMember xlink:href=\"#building_2\"/>",
+        );
+
+        let version = parse_version(&id, &xml_document).expect("should work");
+
+        assert_eq!(version.abstract_gml.name, vec!["District plan 2030"]);
+        assert_eq!(version.tag, vec!["planned", "2030"]);
+        assert!(version.has_tag("planned"));
+        assert_eq!(
+            version.version_member,
+            vec![
+                Id::try_from("building_1").unwrap(),
+                Id::try_from("building_2").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_version_with_inline_members() {
+        let id = Id::try_from("version_2030").expect("should work");
+        let xml_document = String::from(
+            "<vers:tag>2030</vers:tag>
+      <vers:versionMember>
+        <bldg:Building gml:id=\"building_1\"><gml:name>Town hall</gml:name></bldg:Building>
+      </vers:versionMember>
+      <vers:versionMember><bldg:Building/></vers:versionMember>
+      <vers:versionMember></vers:versionMember>
+      <vers:versionMember xlink:href=\"#building_2\"/>",
+        );
+
+        let version = parse_version(&id, &xml_document).expect("should work");
+
+        assert_eq!(version.tag, vec!["2030"]);
+        assert_eq!(
+            version.version_member,
+            vec![
+                Id::try_from("building_1").unwrap(),
+                Id::try_from("building_2").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_version_transition_basic() {
+        let id = Id::try_from("transition_1").expect("should work");
+        let xml_document = String::from(
+            "<vers:reason>new development area</vers:reason>
+      <vers:clonePredecessor>false</vers:clonePredecessor>
+      <vers:type>planned</vers:type>
+      <vers:from xlink:href=\"#version_2020\"/>
+      <vers:to xlink:href=\"#version_2030\"/>
+      <vers:transaction>
+        <vers:Transaction>
+          <vers:type>replace</vers:type>
+          <vers:oldFeature xlink:href=\"#building_1_2020\"/>
+          <vers:newFeature xlink:href=\"#building_1_2030\"/>
+        </vers:Transaction>
+      </vers:transaction>
+      <vers:transaction>
+        <vers:Transaction>
+          <vers:type>insert</vers:type>
+          <vers:newFeature xlink:href=\"#building_2\"/>
+        </vers:Transaction>
+      </vers:transaction>",
+        );
+
+        let version_transition = parse_version_transition(&id, &xml_document).expect("should work");
+
+        assert_eq!(
+            version_transition.reason.as_deref(),
+            Some("new development area")
+        );
+        assert!(!version_transition.clone_predecessor);
+        assert_eq!(
+            version_transition.transition_type,
+            Some(TransitionType::Planned)
+        );
+        assert_eq!(
+            version_transition.from,
+            Some(Id::try_from("version_2020").unwrap())
+        );
+        assert_eq!(version_transition.transaction.len(), 2);
+        assert_eq!(
+            version_transition.transaction[0].transaction_type,
+            TransactionType::Replace
+        );
+        assert_eq!(
+            version_transition.transaction[1].new_feature,
+            Some(Id::try_from("building_2").unwrap())
+        );
+        assert!(version_transition.transaction[1].old_feature.is_none());
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Stage of a running read operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadStage {
+    /// The document is read from the source into memory.
+    Buffering,
+    /// The features of the buffered document are parsed.
+    Parsing,
+}
+
+/// State of a running read operation, passed to the progress callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadProgress {
+    pub stage: ReadStage,
+    /// Bytes of the document read from the source while buffering or parsed afterwards.
+    pub bytes_consumed: u64,
+    /// Size of the document in bytes, which is only known once it is buffered.
+    pub bytes_total: u64,
+    /// Number of top-level features parsed so far.
+    pub features_parsed: usize,
+    /// Number of top-level features selected for parsing.
+    pub features_total: usize,
+}
+
+impl ReadProgress {
+    /// Returns the parsed share of the document between 0.0 and 1.0, which is 0.0 while
+    /// buffering.
+    pub fn fraction(&self) -> f64 {
+        if self.stage == ReadStage::Buffering {
+            return 0.0;
+        }
+        if self.bytes_total == 0 {
+            return 1.0;
+        }
+        self.bytes_consumed as f64 / self.bytes_total as f64
+    }
+}
+
+/// Callback invoked while the document is buffered and after each parsed feature.
+///
+/// Features are parsed in parallel, so the callback may be invoked from multiple threads.
+/// Invocations are serialized and the reported counts never decrease.
+#[derive(Clone)]
+pub struct ProgressCallback(Arc<dyn Fn(&ReadProgress) + Send + Sync>);
+
+impl ProgressCallback {
+    pub fn new(callback: impl Fn(&ReadProgress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    pub(crate) fn call(&self, progress: &ReadProgress) {
+        (self.0)(progress)
+    }
+}
+
+impl fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressCallback").finish_non_exhaustive()
+    }
+}
+
+/// Token for aborting a running read operation from another thread.
+///
+/// Cancellation is checked while buffering and between features, after which reading returns
+/// [`Error::Cancelled`](crate::Error::Cancelled).
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
@@ -1,48 +1,219 @@
+use crate::ade::AdeHandler;
 use crate::error::Error;
-use crate::read_impl::read_from_file;
-use std::fs::File;
-use std::io::{Read, Seek};
+use crate::progress::{CancellationToken, ProgressCallback, ReadProgress};
+use crate::read_impl::{ReadOptions, read_from_file};
+use std::io::Read;
 
-use crate::error::Error::{InvalidFileExtension, NoFileExtension};
+use crate::selection::FeatureSelection;
+use crate::source::FileSource;
+use crate::validate::{
+    GeometryTolerance, SemanticTolerance, TopologyTolerance, ValidateOptions, ValidationProfile,
+};
 use crate::validate_impl::validate_from_reader;
-use crate::{FILE_EXTENSION_CITYGML_GML_FORMAT, FILE_EXTENSION_CITYGML_XML_FORMAT};
 use ecitygml_core::model::city_model::CitygmlModel;
+use ecitygml_core::model::common::CityObjectClass;
+use egml::model::base::Id;
+use egml::model::geometry::Envelope;
 use std::path::Path;
 
 /// `CitygmlReader` reads CityGML datasets.
 ///
+/// Any [`Read`] source is supported, including non-seekable streams such as stdin.
 #[derive(Debug, Clone)]
-pub struct CitygmlReader<R: Read + Seek> {
+pub struct CitygmlReader<R: Read> {
     reader: R,
+    options: ReadOptions,
+    validate_options: ValidateOptions,
 }
 
-impl<R: Read + Seek> CitygmlReader<R> {
+impl<R: Read> CitygmlReader<R> {
     /// Create a new [`CitygmlReader`] from an existing `Reader`.
     pub fn new(reader: R) -> Self {
-        Self { reader }
+        Self {
+            reader,
+            options: ReadOptions::default(),
+            validate_options: ValidateOptions::default(),
+        }
+    }
+
+    /// Registers a handler for the properties of an ADE with the given namespace URI.
+    pub fn with_ade_handler(
+        mut self,
+        namespace: impl Into<String>,
+        handler: impl AdeHandler + 'static,
+    ) -> Self {
+        self.options
+            .ade_handler_registry
+            .register(namespace, handler);
+        self
+    }
+
+    /// Keeps child elements of city objects that are not interpreted as raw XML fragments,
+    /// so that they can be written back by the [`CitygmlWriter`](crate::CitygmlWriter).
+    pub fn with_preserve_unknown_content(mut self, preserve_unknown_content: bool) -> Self {
+        self.options.preserve_unknown_content = preserve_unknown_content;
+        self
+    }
+
+    /// Reads only city objects of the given classes.
+    pub fn with_city_object_classes(
+        mut self,
+        city_object_classes: impl IntoIterator<Item = CityObjectClass>,
+    ) -> Self {
+        self.options.feature_selection = self
+            .options
+            .feature_selection
+            .with_city_object_classes(city_object_classes);
+        self
+    }
+
+    /// Reads only city objects whose coordinates intersect the envelope.
+    ///
+    /// Features outside the envelope are skipped before their geometries are parsed, as are
+    /// features without coordinates of their own, for example with geometries referenced by
+    /// `xlink:href` only.
+    pub fn with_envelope(mut self, envelope: Envelope) -> Self {
+        self.options.feature_selection = self.options.feature_selection.with_envelope(envelope);
+        self
+    }
+
+    /// Reads only top-level features with the given gml:ids.
+    pub fn with_ids(mut self, ids: impl IntoIterator<Item = Id>) -> Self {
+        self.options.feature_selection = self.options.feature_selection.with_ids(ids);
+        self
+    }
+
+    /// Replaces the selection of features to be read.
+    pub fn with_feature_selection(mut self, feature_selection: FeatureSelection) -> Self {
+        self.options.feature_selection = feature_selection;
+        self
+    }
+
+    /// Calls `progress_callback` while the document is buffered and after each parsed
+    /// top-level feature.
+    ///
+    /// The callback may be called from multiple threads, since features are parsed in parallel,
+    /// but not concurrently.
+    pub fn with_progress_callback(
+        mut self,
+        progress_callback: impl Fn(&ReadProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.options.progress_callback = Some(ProgressCallback::new(progress_callback));
+        self
+    }
+
+    /// Aborts reading with [`Error::Cancelled`], once the token is cancelled.
+    ///
+    /// The token is checked while the document is buffered and between features, so that a
+    /// feature being parsed is completed first.
+    pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.options.cancellation_token = Some(cancellation_token);
+        self
+    }
+
+    pub(crate) fn with_options(mut self, options: ReadOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Checks the well-formedness of the document and the structure of selected elements during
+    /// validation.
+    ///
+    /// The structural rules are hand-written and cover the cardinalities and simple types of GML
+    /// geometries, the `CityModel` root, the temporal properties of city objects and generic
+    /// attributes. This is no validation against the CityGML or GML XSDs: elements of other
+    /// modules, such as buildings or transportation objects, are not checked.
+    ///
+    /// Violations are listed in the [`Report`](crate::validate::Report) with their line numbers.
+    pub fn with_structure_validation(mut self, structure_validation: bool) -> Self {
+        self.validate_options.structure_validation = structure_validation;
+        self
+    }
+
+    /// Checks polygons and `lod1Solid`, `lod2Solid` and `lod3Solid` geometries during validation
+    /// following the error codes of val3dity.
+    ///
+    /// Errors are listed in the [`Report`](crate::validate::Report) per gml:id and LOD.
+    pub fn with_geometry_validation(mut self, geometry_validation: bool) -> Self {
+        self.validate_options.geometry_validation = geometry_validation;
+        self
+    }
+
+    /// Sets the snap and planarity tolerances of the geometric validation.
+    pub fn with_geometry_tolerance(mut self, geometry_tolerance: GeometryTolerance) -> Self {
+        self.validate_options.geometry_tolerance = geometry_tolerance;
+        self
+    }
+
+    /// Checks the semantic consistency of the boundary surfaces of buildings during validation,
+    /// for which the model is read with the configured options.
+    pub fn with_semantic_validation(mut self, semantic_validation: bool) -> Self {
+        self.validate_options.semantic_validation = semantic_validation;
+        self
+    }
+
+    /// Sets the tolerances of the semantic consistency rules.
+    pub fn with_semantic_tolerance(mut self, semantic_tolerance: SemanticTolerance) -> Self {
+        self.validate_options.semantic_tolerance = semantic_tolerance;
+        self
+    }
+
+    /// Checks the predecessor and successor links between the traffic spaces of roads during
+    /// validation, for which the model is read with the configured options.
+    pub fn with_topology_validation(mut self, topology_validation: bool) -> Self {
+        self.validate_options.topology_validation = topology_validation;
+        self
+    }
+
+    /// Sets the tolerances of the road network topology rules.
+    pub fn with_topology_tolerance(mut self, topology_tolerance: TopologyTolerance) -> Self {
+        self.validate_options.topology_tolerance = topology_tolerance;
+        self
+    }
+
+    /// Selects the rules of the validation with their severities and parameters.
+    ///
+    /// The structure, geometry, semantic and topology checks run, if any of their rules is enabled in the
+    /// profile, and use the tolerances of the profile.
+    pub fn with_profile(mut self, profile: ValidationProfile) -> Self {
+        self.validate_options = ValidateOptions::from_profile(profile);
+        self
+    }
+
+    /// Replaces all options of the validation.
+    pub fn with_validate_options(mut self, validate_options: ValidateOptions) -> Self {
+        self.validate_options = validate_options;
+        self
     }
 
     pub fn validate(self) -> Result<crate::validate::report::Report, Error> {
-        validate_from_reader(self.reader)
+        validate_from_reader(self.reader, &self.validate_options, &self.options)
     }
 
     pub fn finish(self) -> Result<CitygmlModel, Error> {
-        read_from_file(self.reader)
+        read_from_file(self.reader, &self.options)
     }
 }
 
-impl CitygmlReader<File> {
-    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
-        let extension = path.as_ref().extension().ok_or(NoFileExtension())?;
-        if extension != FILE_EXTENSION_CITYGML_GML_FORMAT
-            && extension != FILE_EXTENSION_CITYGML_XML_FORMAT
-        {
-            return Err(InvalidFileExtension(
-                extension.to_str().unwrap_or_default().to_string(),
-            ));
-        }
+impl<'a> CitygmlReader<&'a [u8]> {
+    /// Create a new [`CitygmlReader`] reading from an in-memory buffer.
+    pub fn from_bytes(bytes: &'a [u8]) -> Self {
+        Self::new(bytes)
+    }
 
-        let file = std::fs::File::open(path)?;
-        Ok(Self::new(file))
+    /// Create a new [`CitygmlReader`] reading from a string containing the document.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(xml_document: &'a str) -> Self {
+        Self::new(xml_document.as_bytes())
+    }
+}
+
+impl CitygmlReader<FileSource> {
+    /// Opens a CityGML document, which is decompressed transparently if it has a `.gz` extension.
+    ///
+    /// Zip archives and directories are read with the
+    /// [`CitygmlDatasetReader`](crate::CitygmlDatasetReader).
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Ok(Self::new(FileSource::open(path)?))
     }
 }
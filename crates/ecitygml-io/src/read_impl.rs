@@ -1,88 +1,621 @@
+use crate::ade::AdeHandlerRegistry;
 use crate::error::Error;
 
 use quick_xml::Reader;
 use quick_xml::events::Event;
+use rayon::prelude::*;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Mutex;
 
 use crate::parser::building::parse_building;
+use crate::parser::context::{ParseContext, extract_namespace_declarations};
 use crate::parser::space::parse_occupied_space;
 use crate::parser::transportation::parse_road;
 use crate::parser::util::extract_xml_element_attributes;
+use crate::parser::versioning::{parse_version, parse_version_transition};
+use crate::progress::{CancellationToken, ProgressCallback, ReadProgress, ReadStage};
+use crate::selection::FeatureSelection;
+use ecitygml_core::model::building::Building;
 use ecitygml_core::model::city_furniture::CityFurniture;
 use ecitygml_core::model::city_model::CitygmlModel;
+use ecitygml_core::model::common::CityObjectClass;
 use ecitygml_core::model::solitary_vegetation_object::SolitaryVegetationObject;
+use ecitygml_core::model::transportation::Road;
+use ecitygml_core::model::versioning::{Version, VersionTransition};
 use egml::model::base::Id;
-use std::io::{BufReader, Read, Seek};
+use std::io::Read;
 
 extern crate quick_xml;
 extern crate serde;
 
-pub fn read_from_file<R: Read + Seek>(reader: R) -> Result<CitygmlModel, Error> {
+/// Number of bytes read from the source between two progress reports while buffering.
+const BUFFER_CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Feature types that are parsed from the top level of a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FeatureType {
+    Building,
+    CityFurniture,
+    Road,
+    SolitaryVegetationObject,
+    Version,
+    VersionTransition,
+}
+
+impl FeatureType {
+    fn from_element_name(element_name: &[u8]) -> Option<Self> {
+        match element_name {
+            b"bldg:Building" => Some(Self::Building),
+            b"frn:CityFurniture" => Some(Self::CityFurniture),
+            b"tran:Road" => Some(Self::Road),
+            b"veg:SolitaryVegetationObject" => Some(Self::SolitaryVegetationObject),
+            b"vers:Version" => Some(Self::Version),
+            b"vers:VersionTransition" => Some(Self::VersionTransition),
+            _ => None,
+        }
+    }
+
+    fn city_object_class(&self) -> Option<CityObjectClass> {
+        match self {
+            Self::Building => Some(CityObjectClass::Building),
+            Self::CityFurniture => Some(CityObjectClass::CityFurniture),
+            Self::Road => Some(CityObjectClass::Road),
+            Self::SolitaryVegetationObject => Some(CityObjectClass::SolitaryVegetationObject),
+            Self::Version | Self::VersionTransition => None,
+        }
+    }
+}
+
+/// Byte range of the content of a top-level feature within the document.
+#[derive(Debug, Clone)]
+struct FeatureRange {
+    feature_type: FeatureType,
+    id: Option<Id>,
+    range: Range<usize>,
+    /// Namespace declarations in scope of the feature content beyond those of the root element.
+    namespace_declarations: HashMap<String, String>,
+}
+
+/// Parsed top-level feature, which is boxed, so that the parsed features of a document take up
+/// little more memory than the model they are moved into.
+#[derive(Debug, Clone)]
+enum ParsedFeature {
+    Building(Box<Building>),
+    CityFurniture(Box<CityFurniture>),
+    Road(Box<Road>),
+    SolitaryVegetationObject(Box<SolitaryVegetationObject>),
+    Version(Box<Version>),
+    VersionTransition(Box<VersionTransition>),
+}
+
+/// Options of a read operation as configured on the [`CitygmlReader`](crate::CitygmlReader).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ReadOptions {
+    pub(crate) ade_handler_registry: AdeHandlerRegistry,
+    pub(crate) preserve_unknown_content: bool,
+    pub(crate) feature_selection: FeatureSelection,
+    pub(crate) progress_callback: Option<ProgressCallback>,
+    pub(crate) cancellation_token: Option<CancellationToken>,
+}
+
+impl ReadOptions {
+    pub(crate) fn check_cancelled(&self) -> Result<(), Error> {
+        match &self.cancellation_token {
+            Some(x) if x.is_cancelled() => Err(Error::Cancelled()),
+            _ => Ok(()),
+        }
+    }
+}
+
+pub(crate) fn read_from_file<R: Read>(
+    reader: R,
+    options: &ReadOptions,
+) -> Result<CitygmlModel, Error> {
     let mut citygml_model = CitygmlModel::default();
 
-    let mut file_content: String = Default::default();
-    BufReader::new(reader).read_to_string(&mut file_content)?;
-    let mut reader = Reader::from_str(file_content.as_str());
+    let file_content = buffer_document(reader, options)?;
+
+    let (namespace_declarations, feature_ranges) =
+        split_feature_ranges(&file_content, &options.feature_selection)?;
+    citygml_model.namespace_declarations = namespace_declarations
+        .iter()
+        .map(|(prefix, namespace)| (prefix.clone(), namespace.clone()))
+        .collect();
+    let context = ParseContext::new(&options.ade_handler_registry, &namespace_declarations)
+        .with_preserve_unknown_content(options.preserve_unknown_content);
+
+    let bytes_total = file_content.len() as u64;
+    let features_total = feature_ranges.len();
+    // the counts are updated and reported under the lock, so that reports never go backwards
+    let parse_progress = Mutex::new((0u64, 0usize));
+    let report_progress = |bytes_consumed: u64, features_parsed: usize| {
+        if let Some(progress_callback) = &options.progress_callback {
+            progress_callback.call(&ReadProgress {
+                stage: ReadStage::Parsing,
+                bytes_consumed,
+                bytes_total,
+                features_parsed,
+                features_total,
+            });
+        }
+    };
+
+    // the ranges are parsed in parallel, while collecting preserves the document order
+    let parsed_features: Vec<Option<ParsedFeature>> = feature_ranges
+        .par_iter()
+        .map(|x| {
+            options.check_cancelled()?;
+
+            let xml_snippet = &file_content[x.range.clone()];
+            let parsed_feature = if x.feature_type.city_object_class().is_none()
+                || options.feature_selection.selects_extent(xml_snippet)
+            {
+                Some(parse_feature_range(&file_content, x, &context)?)
+            } else {
+                None
+            };
+
+            let mut parse_progress = parse_progress.lock().expect("should not be poisoned");
+            parse_progress.0 += x.range.len() as u64;
+            parse_progress.1 += 1;
+            report_progress(parse_progress.0, parse_progress.1);
+
+            Ok(parsed_feature)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    report_progress(bytes_total, features_total);
+    // the parsed features own their content, so the document is released before they are
+    // moved into the model
+    drop(file_content);
+
+    let count = |feature_type: FeatureType| {
+        feature_ranges
+            .iter()
+            .filter(|x| x.feature_type == feature_type)
+            .count()
+    };
+    citygml_model
+        .building
+        .reserve_exact(count(FeatureType::Building));
+    citygml_model
+        .city_furniture
+        .reserve_exact(count(FeatureType::CityFurniture));
+    citygml_model.road.reserve_exact(count(FeatureType::Road));
+    citygml_model
+        .solitary_vegetation_object
+        .reserve_exact(count(FeatureType::SolitaryVegetationObject));
+    for parsed_feature in parsed_features.into_iter().flatten() {
+        match parsed_feature {
+            ParsedFeature::Building(x) => citygml_model.building.push(*x),
+            ParsedFeature::CityFurniture(x) => citygml_model.city_furniture.push(*x),
+            ParsedFeature::Road(x) => citygml_model.road.push(*x),
+            ParsedFeature::SolitaryVegetationObject(x) => {
+                citygml_model.solitary_vegetation_object.push(*x)
+            }
+            ParsedFeature::Version(x) => citygml_model.version.push(*x),
+            ParsedFeature::VersionTransition(x) => citygml_model.version_transition.push(*x),
+        }
+    }
+
+    Ok(citygml_model)
+}
+
+/// Reads the document from the source in chunks and reports the bytes read after each chunk.
+fn buffer_document<R: Read>(mut reader: R, options: &ReadOptions) -> Result<String, Error> {
+    let mut file_content: Vec<u8> = Vec::new();
+    loop {
+        let bytes_read = reader
+            .by_ref()
+            .take(BUFFER_CHUNK_SIZE)
+            .read_to_end(&mut file_content)?;
+        options.check_cancelled()?;
+        if bytes_read == 0 {
+            break;
+        }
+
+        if let Some(progress_callback) = &options.progress_callback {
+            progress_callback.call(&ReadProgress {
+                stage: ReadStage::Buffering,
+                bytes_consumed: file_content.len() as u64,
+                bytes_total: 0,
+                features_parsed: 0,
+                features_total: 0,
+            });
+        }
+    }
+
+    String::from_utf8(file_content)
+        .map_err(|e| Error::from(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
+}
+
+/// Scans the document once and returns the namespace declarations of the root element and the
+/// byte ranges of the selected top-level features without parsing their content.
+fn split_feature_ranges(
+    file_content: &str,
+    feature_selection: &FeatureSelection,
+) -> Result<(HashMap<String, String>, Vec<FeatureRange>), Error> {
+    // namespace declarations of the open elements starting with the root element
+    let mut namespace_scopes: Vec<HashMap<String, String>> = Vec::new();
+    let mut feature_ranges: Vec<FeatureRange> = Vec::new();
+
+    let mut reader = Reader::from_str(file_content);
     reader.config_mut().trim_text(true);
 
-    let mut txt = Vec::new();
     let mut buf = Vec::new();
     loop {
         match reader.read_event_into(&mut buf) {
             Ok(Event::Start(e)) => {
-                let extracted_attributes: HashMap<String, String> =
-                    extract_xml_element_attributes(&reader, &e);
-                let id: Option<Id> = extracted_attributes
-                    .get("id")
-                    .and_then(|x| Id::try_from(x.as_str()).ok());
-
-                match e.name().as_ref() {
-                    b"bldg:Building" => {
-                        let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                        let id: Id = id.unwrap_or(Id::from_hashed_bytes(&xml_snippet));
+                namespace_scopes.push(extract_namespace_declarations(&e));
 
-                        let building = parse_building(&id, &xml_snippet)?;
+                if let Some(feature_type) = FeatureType::from_element_name(e.name().as_ref()) {
+                    let extracted_attributes: HashMap<String, String> =
+                        extract_xml_element_attributes(&reader, &e);
+                    let id: Option<Id> = extracted_attributes
+                        .get("id")
+                        .and_then(|x| Id::try_from(x.as_str()).ok());
+                    let namespace_declarations: HashMap<String, String> = namespace_scopes
+                        .iter()
+                        .skip(1)
+                        .flatten()
+                        .map(|(prefix, namespace)| (prefix.clone(), namespace.clone()))
+                        .collect();
 
-                        citygml_model.building.push(building);
+                    let span = if feature_type == FeatureType::Version {
+                        // features inline in version members are split as features of their
+                        // own, which the version references by their gml:id
+                        reader.clone().read_to_end(e.name())?
+                    } else {
+                        // the end tag is consumed, so the scope of the feature is closed here,
+                        // unless it is the root element
+                        if namespace_scopes.len() > 1 {
+                            namespace_scopes.pop();
+                        }
+                        reader.read_to_end(e.name())?
+                    };
+                    if !feature_selection.selects(feature_type.city_object_class(), id.as_ref()) {
+                        continue;
                     }
-                    b"frn:CityFurniture" => {
-                        let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                        let id: Id = id.unwrap_or(Id::from_hashed_bytes(&xml_snippet));
-
-                        let occupied_space = parse_occupied_space(&id, &xml_snippet)?;
-                        let city_furniture = CityFurniture::new(occupied_space);
-                        citygml_model.city_furniture.push(city_furniture);
-                    }
-                    b"tran:Road" => {
-                        let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                        let id: Id = id.unwrap_or(Id::from_hashed_bytes(&xml_snippet));
-
-                        let road = parse_road(&id, &xml_snippet)?;
-                        citygml_model.road.push(road);
-                    }
-                    b"veg:SolitaryVegetationObject" => {
-                        let xml_snippet: String = reader.read_text(e.name())?.into_owned();
-                        let id: Id = id.unwrap_or(Id::from_hashed_bytes(&xml_snippet));
-
-                        let occupied_space = parse_occupied_space(&id, &xml_snippet)?;
-                        let solitary_vegetation_object =
-                            SolitaryVegetationObject::new(occupied_space);
-                        citygml_model
-                            .solitary_vegetation_object
-                            .push(solitary_vegetation_object);
-                    }
-
-                    _ => (),
+                    feature_ranges.push(FeatureRange {
+                        feature_type,
+                        id,
+                        range: span.start as usize..span.end as usize,
+                        namespace_declarations,
+                    });
                 }
             }
+            // the root scope is kept for the namespace declarations of the document
+            Ok(Event::End(_)) if namespace_scopes.len() > 1 => {
+                namespace_scopes.pop();
+            }
             Ok(Event::Eof) => break,
-            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
-            Ok(Event::Text(e)) => txt.push(e.decode().unwrap().into_owned()),
+            Err(e) => return Err(Error::from(e)),
             _ => (),
         }
 
         buf.clear();
     }
 
-    Ok(citygml_model)
+    let namespace_declarations = namespace_scopes.into_iter().next().unwrap_or_default();
+    Ok((namespace_declarations, feature_ranges))
+}
+
+fn parse_feature_range(
+    file_content: &str,
+    feature_range: &FeatureRange,
+    context: &ParseContext,
+) -> Result<ParsedFeature, Error> {
+    let xml_snippet: &str = &file_content[feature_range.range.clone()];
+    let context = if feature_range.namespace_declarations.is_empty() {
+        Cow::Borrowed(context)
+    } else {
+        Cow::Owned(
+            context
+                .clone()
+                .with_namespace_declarations(&feature_range.namespace_declarations),
+        )
+    };
+    let context = context.as_ref();
+    let id: Id = feature_range
+        .id
+        .clone()
+        .unwrap_or(Id::from_hashed_bytes(xml_snippet));
+
+    let parsed_feature = match feature_range.feature_type {
+        FeatureType::Building => {
+            ParsedFeature::Building(Box::new(parse_building(&id, xml_snippet, context)?))
+        }
+        FeatureType::CityFurniture => {
+            let occupied_space = parse_occupied_space(&id, xml_snippet, context)?;
+            ParsedFeature::CityFurniture(Box::new(CityFurniture::new(occupied_space)))
+        }
+        FeatureType::Road => ParsedFeature::Road(Box::new(parse_road(&id, xml_snippet, context)?)),
+        FeatureType::SolitaryVegetationObject => {
+            let occupied_space = parse_occupied_space(&id, xml_snippet, context)?;
+            ParsedFeature::SolitaryVegetationObject(Box::new(SolitaryVegetationObject::new(
+                occupied_space,
+            )))
+        }
+        FeatureType::Version => ParsedFeature::Version(Box::new(parse_version(&id, xml_snippet)?)),
+        FeatureType::VersionTransition => {
+            ParsedFeature::VersionTransition(Box::new(parse_version_transition(&id, xml_snippet)?))
+        }
+    };
+
+    Ok(parsed_feature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ecitygml_core::model::ade::AdeProperty;
+    use std::any::Any;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_read_from_file_keeps_document_order() {
+        let members: String = (0..50)
+            .map(|i| {
+                format!(
+                    "<cityObjectMember><frn:CityFurniture gml:id=\"furniture_{i}\"><gml:name>{i}</gml:name></frn:CityFurniture></cityObjectMember>"
+                )
+            })
+            .collect();
+        let xml_document = format!(
+            "<CityModel xmlns:gml=\"http://www.opengis.net/gml/3.2\" xmlns:frn=\"http://www.opengis.net/citygml/cityfurniture/3.0\">{members}</CityModel>"
+        );
+
+        let citygml_model = read_from_file(Cursor::new(xml_document), &ReadOptions::default())
+            .expect("should work");
+
+        assert_eq!(citygml_model.city_furniture.len(), 50);
+        for (i, city_furniture) in citygml_model.city_furniture.iter().enumerate() {
+            assert_eq!(
+                city_furniture
+                    .occupied_space
+                    .space
+                    .city_object
+                    .abstract_gml
+                    .id,
+                Id::try_from(format!("furniture_{i}").as_str()).expect("should work")
+            );
+        }
+        assert_eq!(
+            citygml_model
+                .namespace_declarations
+                .get("frn")
+                .map(String::as_str),
+            Some("http://www.opengis.net/citygml/cityfurniture/3.0")
+        );
+    }
+
+    #[test]
+    fn test_read_from_str_and_non_seekable_reader() {
+        let xml_document = "<CityModel xmlns:gml=\"http://www.opengis.net/gml/3.2\" xmlns:frn=\"http://www.opengis.net/citygml/cityfurniture/3.0\"><cityObjectMember><frn:CityFurniture gml:id=\"furniture_1\"><gml:name>Bench</gml:name></frn:CityFurniture></cityObjectMember></CityModel>";
+
+        let citygml_model = crate::CitygmlReader::from_str(xml_document)
+            .finish()
+            .expect("should work");
+        assert_eq!(citygml_model.city_furniture.len(), 1);
+
+        // a chained reader implements `Read`, but not `Seek`
+        let stream = xml_document.as_bytes().chain(&b""[..]);
+        let streamed_citygml_model = crate::CitygmlReader::new(stream)
+            .finish()
+            .expect("should work");
+        assert_eq!(streamed_citygml_model, citygml_model);
+    }
+
+    #[test]
+    fn test_read_with_feature_selection() {
+        let member = |element_name: &str, id: &str, x: f64| {
+            format!(
+                "<cityObjectMember><{element_name} gml:id=\"{id}\"><lod2MultiSurface><gml:MultiSurface><gml:surfaceMember><gml:Polygon><gml:exterior><gml:LinearRing><gml:posList srsDimension=\"3\">{x} 0 0 {} 0 0 {} 1 0 {x} 0 0</gml:posList></gml:LinearRing></gml:exterior></gml:Polygon></gml:surfaceMember></gml:MultiSurface></lod2MultiSurface></{element_name}></cityObjectMember>",
+                x + 1.0,
+                x + 1.0
+            )
+        };
+        let xml_document = format!(
+            "<CityModel xmlns:gml=\"http://www.opengis.net/gml/3.2\" xmlns:frn=\"http://www.opengis.net/citygml/cityfurniture/3.0\" xmlns:veg=\"http://www.opengis.net/citygml/vegetation/3.0\">{}{}{}</CityModel>",
+            member("frn:CityFurniture", "furniture_1", 0.0),
+            member("frn:CityFurniture", "furniture_2", 100.0),
+            member("veg:SolitaryVegetationObject", "tree_1", 0.5),
+        );
+        let read = |feature_selection: FeatureSelection| {
+            crate::CitygmlReader::from_str(&xml_document)
+                .with_feature_selection(feature_selection)
+                .finish()
+                .expect("should work")
+        };
+
+        let citygml_model = read(
+            FeatureSelection::new().with_city_object_classes([CityObjectClass::CityFurniture]),
+        );
+        assert_eq!(citygml_model.city_furniture.len(), 2);
+        assert!(citygml_model.solitary_vegetation_object.is_empty());
+
+        let envelope = egml::model::geometry::Envelope::new(
+            egml::model::geometry::DirectPosition::new(-10.0, -10.0, -10.0).unwrap(),
+            egml::model::geometry::DirectPosition::new(10.0, 10.0, 10.0).unwrap(),
+        )
+        .unwrap();
+        let citygml_model = read(FeatureSelection::new().with_envelope(envelope));
+        assert_eq!(citygml_model.city_furniture.len(), 1);
+        assert_eq!(citygml_model.solitary_vegetation_object.len(), 1);
+
+        let citygml_model = read(
+            FeatureSelection::new().with_ids([Id::try_from("furniture_2").expect("should work")]),
+        );
+        assert_eq!(citygml_model.number_of_objects(), 1);
+        assert_eq!(
+            citygml_model.city_furniture[0]
+                .occupied_space
+                .space
+                .city_object
+                .abstract_gml
+                .id,
+            Id::try_from("furniture_2").expect("should work")
+        );
+    }
+
+    #[test]
+    fn test_read_with_progress_and_cancellation() {
+        let members: String = (0..20)
+            .map(|i| {
+                format!(
+                    "<cityObjectMember><frn:CityFurniture gml:id=\"furniture_{i}\"><gml:name>{i}</gml:name></frn:CityFurniture></cityObjectMember>"
+                )
+            })
+            .collect();
+        let xml_document = format!(
+            "<CityModel xmlns:gml=\"http://www.opengis.net/gml/3.2\" xmlns:frn=\"http://www.opengis.net/citygml/cityfurniture/3.0\">{members}</CityModel>"
+        );
+
+        let progress_reports = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let reported = progress_reports.clone();
+        let citygml_model = crate::CitygmlReader::from_str(&xml_document)
+            .with_progress_callback(move |x| reported.lock().unwrap().push(*x))
+            .finish()
+            .expect("should work");
+        assert_eq!(citygml_model.city_furniture.len(), 20);
+
+        let progress_reports = progress_reports.lock().unwrap();
+        assert_eq!(progress_reports.len(), 22);
+        assert_eq!(progress_reports[0].stage, ReadStage::Buffering);
+        assert_eq!(
+            progress_reports[0].bytes_consumed,
+            xml_document.len() as u64
+        );
+        assert_eq!(progress_reports[0].fraction(), 0.0);
+        let parse_reports = &progress_reports[1..];
+        assert!(parse_reports.iter().all(|x| x.stage == ReadStage::Parsing));
+        assert!(parse_reports.iter().all(|x| x.features_total == 20));
+        assert!(
+            parse_reports
+                .iter()
+                .all(|x| x.bytes_consumed <= x.bytes_total)
+        );
+        assert!(parse_reports.windows(2).all(|x| {
+            x[0].bytes_consumed <= x[1].bytes_consumed
+                && x[0].features_parsed <= x[1].features_parsed
+        }));
+        let last_report = progress_reports.last().expect("should exist");
+        assert_eq!(last_report.features_parsed, 20);
+        assert_eq!(last_report.bytes_consumed, xml_document.len() as u64);
+        assert_eq!(last_report.fraction(), 1.0);
+
+        let cancellation_token = CancellationToken::new();
+        cancellation_token.cancel();
+        let result = crate::CitygmlReader::from_str(&xml_document)
+            .with_cancellation_token(cancellation_token)
+            .finish();
+        assert!(matches!(result, Err(Error::Cancelled())));
+    }
+
+    #[test]
+    fn test_read_returns_error_for_ill_formed_document() {
+        let xml_document = "<CityModel xmlns:gml=\"http://www.opengis.net/gml/3.2\" xmlns:frn=\"http://www.opengis.net/citygml/cityfurniture/3.0\"><cityObjectMember><frn:CityFurniture gml:id=\"furniture_1\"><gml:name>Bench</gml:name></frn:CityFurniture></cityObjectMembr></CityModel>";
+
+        let result = crate::CitygmlReader::from_str(xml_document).finish();
+
+        assert!(matches!(result, Err(Error::QuickXmlError(_))), "{result:?}");
+    }
+
+    #[test]
+    fn test_read_inline_version_members() {
+        let xml_document = "<CityModel xmlns:gml=\"http://www.opengis.net/gml/3.2\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" xmlns:bldg=\"http://www.opengis.net/citygml/building/3.0\" xmlns:vers=\"http://www.opengis.net/citygml/versioning/3.0\">
+  <cityObjectMember><bldg:Building gml:id=\"building_1\"><gml:name>School</gml:name></bldg:Building></cityObjectMember>
+  <versionMember>
+    <vers:Version gml:id=\"version_2030\">
+      <vers:tag>2030</vers:tag>
+      <vers:versionMember xlink:href=\"#building_1\"/>
+      <vers:versionMember><bldg:Building gml:id=\"building_2\"><gml:name>Town hall</gml:name></bldg:Building></vers:versionMember>
+    </vers:Version>
+  </versionMember>
+</CityModel>";
+
+        let citygml_model = crate::CitygmlReader::from_str(xml_document)
+            .finish()
+            .expect("should work");
+
+        let building_ids: Vec<String> = citygml_model
+            .building
+            .iter()
+            .map(|x| {
+                x.occupied_space
+                    .space
+                    .city_object
+                    .abstract_gml
+                    .id
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(building_ids, vec!["building_1", "building_2"]);
+        assert_eq!(citygml_model.version.len(), 1);
+        assert_eq!(
+            citygml_model.version[0].version_member,
+            vec![
+                Id::try_from("building_1").expect("should work"),
+                Id::try_from("building_2").expect("should work")
+            ]
+        );
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct RawAdeProperty(String);
+
+    impl AdeProperty for RawAdeProperty {
+        fn to_xml(&self) -> String {
+            self.0.clone()
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn clone_box(&self) -> Box<dyn AdeProperty> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_read_ade_property_with_prefix_declared_below_the_root() {
+        let namespace = "http://www.sig3d.org/citygml/3.0/energy/2.0";
+        let xml_document = format!(
+            "<CityModel xmlns:gml=\"http://www.opengis.net/gml/3.2\" xmlns:bldg=\"http://www.opengis.net/citygml/building/3.0\">
+  <cityObjectMember xmlns:energy=\"{namespace}\">
+    <bldg:Building gml:id=\"building_1\"><energy:volume>1250.5</energy:volume></bldg:Building>
+  </cityObjectMember>
+  <cityObjectMember>
+    <bldg:Building gml:id=\"building_2\"><energy:volume>1.0</energy:volume></bldg:Building>
+  </cityObjectMember>
+</CityModel>"
+        );
+
+        let citygml_model = crate::CitygmlReader::from_str(&xml_document)
+            .with_ade_handler(
+                namespace,
+                |_: &str, xml_fragment: &str| -> Result<Box<dyn AdeProperty>, Error> {
+                    Ok(Box::new(RawAdeProperty(xml_fragment.to_string())))
+                },
+            )
+            .finish()
+            .expect("should work");
+
+        let ade_extension = |i: usize| {
+            &citygml_model.building[i]
+                .occupied_space
+                .space
+                .city_object
+                .ade_extension
+        };
+        assert_eq!(
+            ade_extension(0).get_typed::<RawAdeProperty>(namespace),
+            vec![&RawAdeProperty(
+                "<energy:volume>1250.5</energy:volume>".to_string()
+            )]
+        );
+        assert!(ade_extension(1).is_empty());
+    }
 }
@@ -0,0 +1,283 @@
+use ecitygml_core::model::common::CityObjectClass;
+use egml::model::base::Id;
+use egml::model::geometry::Envelope;
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use std::collections::HashSet;
+
+/// Restricts reading to a subset of the top-level features of a document.
+///
+/// Features are selected before their content is parsed. All restrictions must be met for a
+/// feature to be read; unset restrictions select every feature.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeatureSelection {
+    city_object_classes: Option<HashSet<CityObjectClass>>,
+    envelope: Option<Envelope>,
+    ids: Option<HashSet<Id>>,
+}
+
+impl FeatureSelection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Selects city objects of the given classes.
+    ///
+    /// Versions and version transitions are no city objects and are not affected.
+    pub fn with_city_object_classes(
+        mut self,
+        city_object_classes: impl IntoIterator<Item = CityObjectClass>,
+    ) -> Self {
+        self.city_object_classes = Some(city_object_classes.into_iter().collect());
+        self
+    }
+
+    /// Selects city objects with coordinates intersecting the envelope.
+    ///
+    /// The extent of a feature is determined from the coordinates of its `gml:pos`,
+    /// `gml:posList` and `gml:coordinates` elements without parsing the geometries, where 2D
+    /// coordinates match any height. Features without coordinates, such as features referencing all of their
+    /// geometries by `xlink:href`, have no extent and are not selected. Versions and version
+    /// transitions are not affected.
+    pub fn with_envelope(mut self, envelope: Envelope) -> Self {
+        self.envelope = Some(envelope);
+        self
+    }
+
+    /// Selects features by the gml:id of the top-level feature.
+    pub fn with_ids(mut self, ids: impl IntoIterator<Item = Id>) -> Self {
+        self.ids = Some(ids.into_iter().collect());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.city_object_classes.is_none() && self.envelope.is_none() && self.ids.is_none()
+    }
+
+    /// Returns `true` if the feature is selected by class and id.
+    pub(crate) fn selects(
+        &self,
+        city_object_class: Option<CityObjectClass>,
+        id: Option<&Id>,
+    ) -> bool {
+        if let (Some(city_object_classes), Some(city_object_class)) =
+            (&self.city_object_classes, city_object_class)
+            && !city_object_classes.contains(&city_object_class)
+        {
+            return false;
+        }
+        if let Some(ids) = &self.ids
+            && !id.is_some_and(|x| ids.contains(x))
+        {
+            return false;
+        }
+
+        true
+    }
+
+    /// Returns `true` if the coordinates within the XML content intersect the envelope.
+    pub(crate) fn selects_extent(&self, xml_document: &str) -> bool {
+        let Some(envelope) = &self.envelope else {
+            return true;
+        };
+
+        scan_extent(xml_document).is_some_and(|x| x.intersects(envelope))
+    }
+}
+
+/// Axis-aligned extent of coordinates, which is unbounded in z if only 2D coordinates were
+/// found.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Extent {
+    lower: [f64; 3],
+    upper: [f64; 3],
+}
+
+impl Extent {
+    fn intersects(&self, envelope: &Envelope) -> bool {
+        let lower_corner = envelope.lower_corner().coords();
+        let upper_corner = envelope.upper_corner().coords();
+        (0..3).all(|i| lower_corner[i] <= self.upper[i] && self.lower[i] <= upper_corner[i])
+    }
+}
+
+/// Determines the extent of all `gml:pos`, `gml:posList` and `gml:coordinates` coordinates of
+/// an XML fragment.
+///
+/// The dimension of `gml:pos` and `gml:posList` tuples is taken from the `srsDimension`
+/// attribute of the element or its closest ancestor and defaults to 3. The tuples of
+/// `gml:coordinates` are split by its `ts`, `cs` and `decimal` attributes and have the number
+/// of coordinates they contain. The z range of 2D tuples is unbounded. Returns `None` if the
+/// fragment contains no coordinates, for example if all geometries are referenced by
+/// `xlink:href`.
+fn scan_extent(xml_document: &str) -> Option<Extent> {
+    let mut reader = Reader::from_str(xml_document);
+    reader.config_mut().trim_text(true);
+
+    let mut lower = [f64::INFINITY; 3];
+    let mut upper = [f64::NEG_INFINITY; 3];
+    let mut dimensions: Vec<Option<usize>> = Vec::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let attribute = |name: &str| -> Option<String> {
+                    let value = e.try_get_attribute(name).ok().flatten()?;
+                    Some(std::str::from_utf8(&value.value).ok()?.to_string())
+                };
+                let dimension: Option<usize> =
+                    attribute("srsDimension").and_then(|x| x.trim().parse().ok());
+                let tuples: Vec<Vec<f64>> = match e.local_name().as_ref() {
+                    b"pos" | b"posList" => {
+                        let dimension = dimension
+                            .or_else(|| dimensions.iter().rev().find_map(|x| *x))
+                            .unwrap_or(3);
+                        let Ok(text) = reader.read_text(e.name()) else {
+                            break;
+                        };
+                        let values: Vec<f64> = text
+                            .split_whitespace()
+                            .filter_map(|x| x.parse().ok())
+                            .collect();
+                        values
+                            .chunks_exact(dimension.max(1))
+                            .map(<[f64]>::to_vec)
+                            .collect()
+                    }
+                    b"coordinates" => {
+                        let decimal = attribute("decimal").unwrap_or_else(|| ".".to_string());
+                        let cs = attribute("cs").unwrap_or_else(|| ",".to_string());
+                        let ts = attribute("ts").unwrap_or_else(|| " ".to_string());
+                        let Ok(text) = reader.read_text(e.name()) else {
+                            break;
+                        };
+                        parse_coordinates(&text, &decimal, &cs, &ts)
+                    }
+                    _ => {
+                        dimensions.push(dimension);
+                        continue;
+                    }
+                };
+
+                for coordinates in tuples.iter().filter(|x| (2..=3).contains(&x.len())) {
+                    for (i, value) in coordinates.iter().enumerate() {
+                        lower[i] = lower[i].min(*value);
+                        upper[i] = upper[i].max(*value);
+                    }
+                    if coordinates.len() == 2 {
+                        lower[2] = f64::NEG_INFINITY;
+                        upper[2] = f64::INFINITY;
+                    }
+                }
+            }
+            Ok(Event::End(_)) => {
+                dimensions.pop();
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => (),
+        }
+    }
+
+    (lower[0] <= upper[0]).then_some(Extent { lower, upper })
+}
+
+/// Splits the text of a `gml:coordinates` element into tuples, where a whitespace tuple
+/// separator matches any whitespace. Tuples with a value that is not a number are skipped.
+fn parse_coordinates(text: &str, decimal: &str, cs: &str, ts: &str) -> Vec<Vec<f64>> {
+    let tuples: Vec<&str> = if ts.trim().is_empty() {
+        text.split_whitespace().collect()
+    } else {
+        text.split(ts)
+            .map(str::trim)
+            .filter(|x| !x.is_empty())
+            .collect()
+    };
+
+    tuples
+        .into_iter()
+        .filter_map(|tuple| {
+            tuple
+                .split(cs)
+                .map(|x| x.trim().replace(decimal, ".").parse::<f64>().ok())
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egml::model::geometry::DirectPosition;
+
+    #[test]
+    fn test_scan_extent() {
+        let xml_document = "<lod2MultiSurface><gml:MultiSurface><gml:surfaceMember><gml:Polygon><gml:exterior><gml:LinearRing>
+<gml:posList srsDimension=\"3\">1.0 2.0 3.0 4.0 2.0 3.0 4.0 6.0 9.0 1.0 2.0 3.0</gml:posList>
+</gml:LinearRing></gml:exterior></gml:Polygon></gml:surfaceMember></gml:MultiSurface></lod2MultiSurface>
+<lod1ImplicitRepresentation><ImplicitGeometry><referencePoint><gml:Point><gml:pos>-1.0 2.5 3.0</gml:pos></gml:Point></referencePoint></ImplicitGeometry></lod1ImplicitRepresentation>";
+
+        let extent = scan_extent(xml_document).expect("should contain coordinates");
+
+        assert_eq!(extent.lower, [-1.0, 2.0, 3.0]);
+        assert_eq!(extent.upper, [4.0, 6.0, 9.0]);
+    }
+
+    #[test]
+    fn test_scan_extent_of_2d_coordinates() {
+        let xml_document = "<lod0MultiSurface><gml:MultiSurface srsDimension=\"2\"><gml:surfaceMember><gml:Polygon><gml:exterior><gml:LinearRing>
+<gml:posList>10.0 20.0 14.0 20.0 14.0 26.0</gml:posList>
+</gml:LinearRing></gml:exterior></gml:Polygon></gml:surfaceMember></gml:MultiSurface></lod0MultiSurface>";
+
+        let extent = scan_extent(xml_document).expect("should contain coordinates");
+
+        assert_eq!(extent.lower, [10.0, 20.0, f64::NEG_INFINITY]);
+        assert_eq!(extent.upper, [14.0, 26.0, f64::INFINITY]);
+
+        let selection = FeatureSelection::new().with_envelope(
+            Envelope::new(
+                DirectPosition::new(12.0, 22.0, 100.0).unwrap(),
+                DirectPosition::new(13.0, 23.0, 200.0).unwrap(),
+            )
+            .unwrap(),
+        );
+        assert!(selection.selects_extent(xml_document));
+    }
+
+    #[test]
+    fn test_features_without_coordinates_are_not_selected_by_envelope() {
+        let selection = FeatureSelection::new().with_envelope(
+            Envelope::new(
+                DirectPosition::new(0.0, 0.0, 0.0).unwrap(),
+                DirectPosition::new(100.0, 100.0, 100.0).unwrap(),
+            )
+            .unwrap(),
+        );
+        let referenced_geometry = "<lod2Solid xlink:href=\"#solid_1\"/>";
+
+        assert!(scan_extent(referenced_geometry).is_none());
+        assert!(scan_extent("<gml:name>no geometry</gml:name>").is_none());
+        assert!(!selection.selects_extent(referenced_geometry));
+        assert!(FeatureSelection::new().selects_extent(referenced_geometry));
+    }
+
+    #[test]
+    fn test_scan_extent_of_gml_coordinates() {
+        let xml_document = "<lod2MultiSurface><gml:MultiSurface><gml:surfaceMember><gml:Polygon><gml:exterior><gml:LinearRing>
+<gml:coordinates>1.0,2.0,3.0 4.0,2.0,3.0
+4.0,6.0,9.0 1.0,2.0,3.0</gml:coordinates>
+</gml:LinearRing></gml:exterior></gml:Polygon></gml:surfaceMember></gml:MultiSurface></lod2MultiSurface>
+<lod1ImplicitRepresentation><ImplicitGeometry><referencePoint><gml:Point><gml:coordinates decimal=\",\" cs=\" \" ts=\";\">-1,5 2,5 3; 0 2 4</gml:coordinates></gml:Point></referencePoint></ImplicitGeometry></lod1ImplicitRepresentation>";
+
+        let extent = scan_extent(xml_document).expect("should contain coordinates");
+
+        assert_eq!(extent.lower, [-1.5, 2.0, 3.0]);
+        assert_eq!(extent.upper, [4.0, 6.0, 9.0]);
+
+        let extent =
+            scan_extent("<gml:Point><gml:coordinates>10.0,20.0</gml:coordinates></gml:Point>")
+                .expect("should contain coordinates");
+
+        assert_eq!(extent.lower, [10.0, 20.0, f64::NEG_INFINITY]);
+        assert_eq!(extent.upper, [10.0, 20.0, f64::INFINITY]);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::error::Error;
+use crate::error::Error::{InvalidFileExtension, NoFileExtension};
+use crate::{
+    FILE_EXTENSION_CITYGML_GML_FORMAT, FILE_EXTENSION_CITYGML_XML_FORMAT,
+    FILE_EXTENSION_GZIP_FORMAT, FILE_EXTENSION_ZIP_FORMAT,
+};
+use flate2::read::GzDecoder;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// File opened by [`CitygmlReader::from_path`](crate::CitygmlReader::from_path).
+///
+/// Gzip compressed documents are decompressed while reading.
+#[derive(Debug)]
+pub enum FileSource {
+    Plain(File),
+    Gzip(GzDecoder<File>),
+}
+
+impl FileSource {
+    /// Opens a plain or gzip compressed CityGML document.
+    pub(crate) fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        match SourceFormat::from_path(path)? {
+            SourceFormat::Citygml => Ok(FileSource::Plain(File::open(path)?)),
+            SourceFormat::GzipCitygml => Ok(FileSource::Gzip(GzDecoder::new(File::open(path)?))),
+            SourceFormat::Zip => Err(Error::ArchiveNotSupported(path.display().to_string())),
+        }
+    }
+}
+
+impl Read for FileSource {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            FileSource::Plain(x) => x.read(buf),
+            FileSource::Gzip(x) => x.read(buf),
+        }
+    }
+}
+
+/// Formats of files containing CityGML data, derived from the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SourceFormat {
+    /// Plain CityGML document (`.gml`, `.xml`)
+    Citygml,
+    /// Gzip compressed CityGML document (`.gml.gz`, `.xml.gz`)
+    GzipCitygml,
+    /// Zip archive of CityGML documents (`.zip`)
+    Zip,
+}
+
+impl SourceFormat {
+    pub(crate) fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let extension = path.extension().ok_or(NoFileExtension())?;
+
+        if is_citygml_extension(extension.to_str()) {
+            return Ok(Self::Citygml);
+        }
+        if extension == FILE_EXTENSION_ZIP_FORMAT {
+            return Ok(Self::Zip);
+        }
+        if extension == FILE_EXTENSION_GZIP_FORMAT {
+            let inner_extension = path
+                .file_stem()
+                .map(Path::new)
+                .and_then(|x| x.extension())
+                .and_then(|x| x.to_str());
+            if is_citygml_extension(inner_extension) {
+                return Ok(Self::GzipCitygml);
+            }
+        }
+
+        Err(InvalidFileExtension(
+            extension.to_str().unwrap_or_default().to_string(),
+        ))
+    }
+}
+
+/// Returns `true` if the path has the extension of a plain or gzip compressed CityGML document.
+pub fn is_citygml_document_path(path: impl AsRef<Path>) -> bool {
+    matches!(
+        SourceFormat::from_path(path),
+        Ok(SourceFormat::Citygml | SourceFormat::GzipCitygml)
+    )
+}
+
+fn is_citygml_extension(extension: Option<&str>) -> bool {
+    extension.is_some_and(|x| {
+        x == FILE_EXTENSION_CITYGML_GML_FORMAT || x == FILE_EXTENSION_CITYGML_XML_FORMAT
+    })
+}
+
+pub(crate) fn decompress_gzip(reader: impl Read) -> Result<Vec<u8>, Error> {
+    let mut content = Vec::new();
+    GzDecoder::new(reader)
+        .read_to_end(&mut content)
+        .map_err(|e| Error::InvalidGzipContent(e.to_string()))?;
+
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_format_from_path() {
+        assert_eq!(
+            SourceFormat::from_path("tile.gml").expect("should work"),
+            SourceFormat::Citygml
+        );
+        assert_eq!(
+            SourceFormat::from_path("tile.gml.gz").expect("should work"),
+            SourceFormat::GzipCitygml
+        );
+        assert_eq!(
+            SourceFormat::from_path("tiles.zip").expect("should work"),
+            SourceFormat::Zip
+        );
+        assert!(SourceFormat::from_path("tile.json.gz").is_err());
+        assert!(SourceFormat::from_path("tile").is_err());
+    }
+}
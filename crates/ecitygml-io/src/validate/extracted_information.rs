@@ -1,7 +1,12 @@
-use crate::validate::report::{Report, ReportElement, ReportStatistics};
+use crate::validate::profile::{Severity, ValidationProfile};
+use crate::validate::report::{
+    CrossFileReference, Finding, GeometryError, ReferenceKind, ReferenceResolution, Report,
+    ReportElement, ReportStatistics, SemanticIssue, StructureViolation, TopologyIssue,
+};
 
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Default)]
 pub struct ExtractedInformation {
@@ -11,6 +16,12 @@ pub struct ExtractedInformation {
     pub city_object_relations: HashSet<CityObjectRelation>,
     pub predecessor_hrefs: HashMap<String, usize>,
     pub successor_hrefs: HashMap<String, usize>,
+    pub structure_violations: Vec<StructureViolation>,
+    pub geometry_errors: Vec<GeometryError>,
+    pub semantic_issues: Vec<SemanticIssue>,
+    pub topology_issues: Vec<TopologyIssue>,
+    /// gml:ids of all documents of a dataset with the name of the document containing them.
+    pub dataset_gml_ids: Arc<HashMap<String, String>>,
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
@@ -43,15 +54,24 @@ impl ExtractedInformation {
         self.gml_id_count.values().any(|x| *x > 1usize)
     }
 
+    /// Classifies whether the gml:id referenced by the href is contained in this document, in
+    /// another document of the dataset or in none of them.
+    pub fn classify_href(&self, href: &str) -> ReferenceResolution {
+        let gml_id = href_target_id(href);
+        if self.gml_id_count.contains_key(gml_id) {
+            return ReferenceResolution::ResolvedLocally;
+        }
+        match self.dataset_gml_ids.get(gml_id) {
+            Some(document) => ReferenceResolution::ResolvedInOtherFile(document.clone()),
+            None => ReferenceResolution::Broken,
+        }
+    }
+
     pub fn find_broken_object_relations(&self) -> Vec<&CityObjectRelation> {
         let broken_object_relations: Vec<&CityObjectRelation> = self
             .city_object_relations
             .iter()
-            .filter(|x| {
-                !self
-                    .gml_id_count
-                    .contains_key(&x.related_to.xlink_href.as_str()[1..])
-            })
+            .filter(|x| self.classify_href(&x.related_to.xlink_href) == ReferenceResolution::Broken)
             .collect();
         broken_object_relations
     }
@@ -59,9 +79,9 @@ impl ExtractedInformation {
     pub fn find_broken_predecessor_hrefs(&self) -> Vec<String> {
         let broken_hrefs: Vec<String> = self
             .predecessor_hrefs
-            .iter()
-            .filter(|x| !self.gml_id_count.contains_key(&x.0.as_str()[1..]))
-            .map(|x| x.0.clone())
+            .keys()
+            .filter(|x| self.classify_href(x) == ReferenceResolution::Broken)
+            .cloned()
             .collect();
         broken_hrefs
     }
@@ -69,25 +89,167 @@ impl ExtractedInformation {
     pub fn find_broken_successor_hrefs(&self) -> Vec<String> {
         let broken_hrefs: Vec<String> = self
             .successor_hrefs
-            .iter()
-            .filter(|x| !self.gml_id_count.contains_key(&x.0.as_str()[1..]))
-            .map(|x| x.0.clone())
+            .keys()
+            .filter(|x| self.classify_href(x) == ReferenceResolution::Broken)
+            .cloned()
             .collect();
         broken_hrefs
     }
 
-    pub fn compile_report(&self) -> Report {
-        let gml_id_duplicates: Vec<(String, usize)> = self
+    /// Returns the references resolved in other documents of the dataset.
+    pub fn find_cross_file_references(&self) -> Vec<CrossFileReference> {
+        let hrefs = self
+            .predecessor_hrefs
+            .keys()
+            .map(|x| (ReferenceKind::Predecessor, x))
+            .chain(
+                self.successor_hrefs
+                    .keys()
+                    .map(|x| (ReferenceKind::Successor, x)),
+            )
+            .chain(
+                self.city_object_relations
+                    .iter()
+                    .map(|x| (ReferenceKind::ObjectRelation, &x.related_to.xlink_href)),
+            );
+
+        let mut cross_file_references: Vec<CrossFileReference> = hrefs
+            .filter_map(|(kind, href)| match self.classify_href(href) {
+                ReferenceResolution::ResolvedInOtherFile(document) => Some(CrossFileReference {
+                    kind,
+                    href: href.clone(),
+                    document,
+                }),
+                _ => None,
+            })
+            .collect();
+        cross_file_references.sort();
+        cross_file_references
+    }
+
+    /// Compiles the report with the rules enabled in the profile.
+    pub fn compile_report(&self, profile: &ValidationProfile) -> Report {
+        let mut gml_id_duplicates: Vec<(String, usize)> = self
             .get_gml_id_duplicates()
             .iter()
             .map(|x| (x.0.clone(), *x.1))
+            .filter(|_| profile.is_enabled("gml_id_duplicate"))
             .collect();
+        gml_id_duplicates.sort();
 
         let broken_object_relations: Vec<String> = self
             .find_broken_object_relations()
             .iter()
             .map(|x| x.related_to.xlink_href.clone())
+            .filter(|_| profile.is_enabled("broken_object_relation"))
+            .collect();
+        let broken_predecessor_hrefs: Vec<String> = self
+            .find_broken_predecessor_hrefs()
+            .into_iter()
+            .filter(|_| profile.is_enabled("broken_predecessor_href"))
             .collect();
+        let broken_successor_hrefs: Vec<String> = self
+            .find_broken_successor_hrefs()
+            .into_iter()
+            .filter(|_| profile.is_enabled("broken_successor_href"))
+            .collect();
+        let cross_file_references = self.find_cross_file_references();
+        let structure_violations: Vec<StructureViolation> = self
+            .structure_violations
+            .iter()
+            .filter(|_| profile.is_enabled("structure_violation"))
+            .cloned()
+            .collect();
+        let geometry_errors: Vec<GeometryError> = self
+            .geometry_errors
+            .iter()
+            .filter(|x| profile.is_enabled(x.error.rule_id()))
+            .cloned()
+            .collect();
+        let semantic_issues: Vec<SemanticIssue> = self
+            .semantic_issues
+            .iter()
+            .filter(|x| profile.is_enabled(x.rule.rule_id()))
+            .cloned()
+            .collect();
+        let topology_issues: Vec<TopologyIssue> = self
+            .topology_issues
+            .iter()
+            .filter(|x| profile.is_enabled(x.rule.rule_id()))
+            .cloned()
+            .collect();
+
+        let finding = |rule_id: &str, feature_id: Option<String>, message: String| Finding {
+            rule_id: rule_id.to_string(),
+            severity: profile.severity(rule_id),
+            feature_id,
+            line: None,
+            column: None,
+            message,
+        };
+        let mut findings: Vec<Finding> = Vec::new();
+        findings.extend(gml_id_duplicates.iter().map(|(id, count)| {
+            finding(
+                "gml_id_duplicate",
+                Some(id.clone()),
+                format!("gml:id occurs {count} times"),
+            )
+        }));
+        findings.extend(broken_object_relations.iter().map(|x| {
+            finding(
+                "broken_object_relation",
+                None,
+                format!("related object `{x}` does not exist"),
+            )
+        }));
+        findings.extend(broken_predecessor_hrefs.iter().map(|x| {
+            finding(
+                "broken_predecessor_href",
+                None,
+                format!("predecessor `{x}` does not exist"),
+            )
+        }));
+        findings.extend(broken_successor_hrefs.iter().map(|x| {
+            finding(
+                "broken_successor_href",
+                None,
+                format!("successor `{x}` does not exist"),
+            )
+        }));
+        findings.extend(structure_violations.iter().map(|x| Finding {
+            line: Some(x.line),
+            column: Some(x.column),
+            ..finding(
+                "structure_violation",
+                None,
+                format!("`{}`: {}", x.element_name, x.message),
+            )
+        }));
+        findings.extend(geometry_errors.iter().map(|x| {
+            let lod = x.lod.map(|lod| format!(" in LOD{lod}")).unwrap_or_default();
+            let geometry_id = x.geometry_id.as_deref().unwrap_or("without gml:id");
+            finding(
+                x.error.rule_id(),
+                x.feature_id.clone(),
+                format!("error {} of geometry `{geometry_id}`{lod}", x.code),
+            )
+        }));
+        findings.extend(semantic_issues.iter().map(|x| {
+            finding(
+                x.rule.rule_id(),
+                Some(x.feature_id.clone()),
+                x.message.clone(),
+            )
+        }));
+        findings.extend(topology_issues.iter().map(|x| {
+            finding(
+                x.rule.rule_id(),
+                Some(x.feature_id.clone()),
+                x.message.clone(),
+            )
+        }));
+        let number_of_findings =
+            |severity: Severity| findings.iter().filter(|x| x.severity == severity).count();
 
         let mut xml_elements: Vec<ReportElement> = self
             .gml_id_count_per_element_type
@@ -104,6 +266,14 @@ impl ExtractedInformation {
         let statistics = ReportStatistics {
             number_of_gml_duplicates: gml_id_duplicates.len(),
             number_of_broken_object_relations: broken_object_relations.len(),
+            number_of_cross_file_references: cross_file_references.len(),
+            number_of_structure_violations: structure_violations.len(),
+            number_of_geometry_errors: geometry_errors.len(),
+            number_of_semantic_issues: semantic_issues.len(),
+            number_of_topology_issues: topology_issues.len(),
+            number_of_errors: number_of_findings(Severity::Error),
+            number_of_warnings: number_of_findings(Severity::Warning),
+            number_of_infos: number_of_findings(Severity::Info),
         };
 
         Report {
@@ -111,12 +281,25 @@ impl ExtractedInformation {
             xml_elements,
             gml_id_duplicates,
             broken_object_relations,
-            broken_predecessor_hrefs: self.find_broken_predecessor_hrefs(),
-            broken_successor_hrefs: self.find_broken_successor_hrefs(),
+            broken_predecessor_hrefs,
+            broken_successor_hrefs,
+            cross_file_references,
+            structure_violations,
+            geometry_errors,
+            semantic_issues,
+            topology_issues,
+            source: None,
+            profile_name: profile.name.clone(),
+            findings,
         }
     }
 }
 
+/// Returns the gml:id referenced by an href such as `#id` or `tile.gml#id`.
+fn href_target_id(href: &str) -> &str {
+    href.rsplit_once('#').map_or(href, |x| x.1)
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct CityObjectRelation {
     #[serde(rename = "relationType")]
@@ -0,0 +1,866 @@
+use crate::validate::report::{GeometryError, GeometryErrorCode};
+use crate::validate::structure::rule_name_prefix;
+use ecitygml_core::operations::newell_vector;
+use nalgebra::{Vector2, Vector3};
+use quick_xml::NsReader;
+use quick_xml::events::{BytesStart, Event};
+use std::collections::HashMap;
+
+/// Distance below which two points are considered identical.
+pub const DEFAULT_SNAP_TOLERANCE: f64 = 0.001;
+/// Maximum distance of polygon points to the fitted plane.
+pub const DEFAULT_PLANARITY_TOLERANCE: f64 = 0.01;
+
+/// Tolerances of the geometric validation, which follow the defaults of val3dity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeometryTolerance {
+    pub snap: f64,
+    pub planarity: f64,
+}
+
+impl Default for GeometryTolerance {
+    fn default() -> Self {
+        Self {
+            snap: DEFAULT_SNAP_TOLERANCE,
+            planarity: DEFAULT_PLANARITY_TOLERANCE,
+        }
+    }
+}
+
+/// Polygon with the raw coordinates of its rings as written in the document.
+#[derive(Debug, Clone, Default)]
+struct RawPolygon {
+    id: Option<String>,
+    exterior: Vec<Vector3<f64>>,
+    interiors: Vec<Vec<Vector3<f64>>>,
+    is_valid: bool,
+}
+
+/// Solid of a `lod1Solid`, `lod2Solid` or `lod3Solid` property with its exterior shell.
+#[derive(Debug, Clone, Default)]
+struct RawSolid {
+    id: Option<String>,
+    feature_id: Option<String>,
+    lod: Option<u8>,
+    polygon_indices: Vec<usize>,
+    polygon_hrefs: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct OpenElement {
+    local_name: String,
+    is_gml: bool,
+    id: Option<String>,
+    srs_dimension: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RingTarget {
+    Exterior,
+    Interior,
+}
+
+struct GeometryChecker {
+    tolerance: GeometryTolerance,
+    errors: Vec<GeometryError>,
+    polygons: Vec<RawPolygon>,
+    solids: Vec<RawSolid>,
+}
+
+impl GeometryChecker {
+    fn report(
+        &mut self,
+        code: GeometryErrorCode,
+        context: &GeometryContext,
+        geometry_id: Option<&String>,
+    ) {
+        self.errors.push(GeometryError {
+            feature_id: context.feature_id.clone(),
+            lod: context.lod,
+            geometry_id: geometry_id.cloned(),
+            code: code.code(),
+            error: code,
+        });
+    }
+
+    fn check(mut self, xml_document: &str) -> Vec<GeometryError> {
+        let mut reader = NsReader::from_str(xml_document);
+        reader.config_mut().trim_text(true);
+
+        let mut open_elements: Vec<OpenElement> = Vec::new();
+        let mut current_polygon: Option<(RawPolygon, GeometryContext)> = None;
+        let mut current_ring: Option<(RingTarget, Vec<Vector3<f64>>)> = None;
+        let mut current_solid: Option<RawSolid> = None;
+        let mut text = String::new();
+
+        // well-formedness is reported by the structural validation
+        while let Ok((resolve_result, event)) = reader.read_resolved_event() {
+            let prefix = rule_name_prefix(&resolve_result);
+
+            match event {
+                Event::Start(e) => {
+                    let element = open_element(&reader, prefix, &e, open_elements.last());
+                    text.clear();
+
+                    if element.is_gml {
+                        match element.local_name.as_str() {
+                            "Solid" if is_solid_property(&open_elements) => {
+                                let context = GeometryContext::new(&open_elements);
+                                current_solid = Some(RawSolid {
+                                    id: element.id.clone(),
+                                    feature_id: context.feature_id,
+                                    lod: context.lod,
+                                    ..Default::default()
+                                });
+                            }
+                            "Polygon" => {
+                                let polygon = RawPolygon {
+                                    id: element.id.clone(),
+                                    ..Default::default()
+                                };
+                                current_polygon =
+                                    Some((polygon, GeometryContext::new(&open_elements)));
+                            }
+                            "LinearRing" if current_polygon.is_some() => {
+                                let target = match open_elements.last() {
+                                    Some(x) if x.local_name == "interior" => RingTarget::Interior,
+                                    _ => RingTarget::Exterior,
+                                };
+                                current_ring = Some((target, Vec::new()));
+                            }
+                            _ => {}
+                        }
+                    }
+                    open_elements.push(element);
+                }
+                Event::Empty(e) => {
+                    let element = open_element(&reader, prefix, &e, open_elements.last());
+                    if element.is_gml
+                        && element.local_name == "surfaceMember"
+                        && let Some(solid) = &mut current_solid
+                        && !is_in_solid_interior(&open_elements)
+                        && let Some(href) = extract_href(&reader, &e)
+                    {
+                        solid
+                            .polygon_hrefs
+                            .push(href.trim_start_matches('#').to_string());
+                    }
+                }
+                Event::Text(e) => text.push_str(&e.decode().unwrap_or_default()),
+                Event::End(_) => {
+                    let Some(element) = open_elements.pop() else {
+                        continue;
+                    };
+                    if !element.is_gml {
+                        continue;
+                    }
+
+                    match element.local_name.as_str() {
+                        "pos" | "posList" => {
+                            if let Some((_, points)) = &mut current_ring {
+                                points.extend(parse_coordinates(
+                                    &text,
+                                    element.srs_dimension.unwrap_or(3),
+                                ));
+                            }
+                        }
+                        "LinearRing" => {
+                            if let (Some((target, points)), Some((polygon, _))) =
+                                (current_ring.take(), &mut current_polygon)
+                            {
+                                match target {
+                                    RingTarget::Exterior => polygon.exterior = points,
+                                    RingTarget::Interior => polygon.interiors.push(points),
+                                }
+                            }
+                        }
+                        "Polygon" => {
+                            if let Some((mut polygon, context)) = current_polygon.take() {
+                                polygon.is_valid = self.check_polygon(&mut polygon, &context);
+                                self.polygons.push(polygon);
+                                if let Some(solid) = &mut current_solid
+                                    && !is_in_solid_interior(&open_elements)
+                                {
+                                    solid.polygon_indices.push(self.polygons.len() - 1);
+                                }
+                            }
+                        }
+                        "Solid" => {
+                            if let Some(solid) = current_solid.take() {
+                                self.solids.push(solid);
+                            }
+                        }
+                        _ => {}
+                    }
+                    text.clear();
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        // solids are checked at the end, since their surfaces may reference later polygons
+        let mut polygon_index_by_id: HashMap<String, usize> = HashMap::new();
+        for (index, polygon) in self.polygons.iter().enumerate() {
+            if let Some(id) = &polygon.id {
+                // duplicate gml:ids resolve to the first polygon
+                polygon_index_by_id.entry(id.clone()).or_insert(index);
+            }
+        }
+        let solids = std::mem::take(&mut self.solids);
+        for solid in &solids {
+            self.check_solid(solid, &polygon_index_by_id);
+        }
+
+        self.errors
+    }
+
+    /// Checks the rings and the polygon and returns true, if no error was found.
+    ///
+    /// The closing point of each ring is removed, such that the polygon can be used for the shell
+    /// checks afterward.
+    fn check_polygon(&mut self, polygon: &mut RawPolygon, context: &GeometryContext) -> bool {
+        let mut rings: Vec<&mut Vec<Vector3<f64>>> = vec![&mut polygon.exterior];
+        rings.extend(polygon.interiors.iter_mut());
+
+        let mut ring_errors: Vec<GeometryErrorCode> = Vec::new();
+        for ring in rings {
+            if let Some(code) = check_ring(ring, &self.tolerance) {
+                ring_errors.push(code);
+            }
+        }
+        ring_errors.dedup();
+        if !ring_errors.is_empty() {
+            for code in ring_errors {
+                self.report(code, context, polygon.id.as_ref());
+            }
+            return false;
+        }
+
+        // rings with all points on a line collapse onto themselves
+        let Some(normal) = ring_normal(&polygon.exterior) else {
+            self.report(
+                GeometryErrorCode::RingSelfIntersection,
+                context,
+                polygon.id.as_ref(),
+            );
+            return false;
+        };
+        let centroid =
+            polygon.exterior.iter().sum::<Vector3<f64>>() / polygon.exterior.len() as f64;
+        let is_planar = polygon
+            .exterior
+            .iter()
+            .chain(polygon.interiors.iter().flatten())
+            .all(|x| (x - centroid).dot(&normal).abs() <= self.tolerance.planarity);
+        if !is_planar {
+            self.report(
+                GeometryErrorCode::NonPlanarPolygonDistancePlane,
+                context,
+                polygon.id.as_ref(),
+            );
+            return false;
+        }
+
+        let exterior = project_ring(&polygon.exterior, &normal);
+        if is_self_intersecting(&exterior) {
+            self.report(
+                GeometryErrorCode::RingSelfIntersection,
+                context,
+                polygon.id.as_ref(),
+            );
+            return false;
+        }
+        for interior in &polygon.interiors {
+            let interior = project_ring(interior, &normal);
+            if is_self_intersecting(&interior) {
+                self.report(
+                    GeometryErrorCode::RingSelfIntersection,
+                    context,
+                    polygon.id.as_ref(),
+                );
+                return false;
+            }
+            let is_inside = interior.iter().all(|x| {
+                is_point_in_ring(x, &exterior)
+                    || distance_to_ring(x, &exterior) <= self.tolerance.snap
+            });
+            if !is_inside {
+                self.report(
+                    GeometryErrorCode::InnerRingOutside,
+                    context,
+                    polygon.id.as_ref(),
+                );
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn check_solid(&mut self, solid: &RawSolid, polygon_index_by_id: &HashMap<String, usize>) {
+        let context = GeometryContext {
+            feature_id: solid.feature_id.clone(),
+            lod: solid.lod,
+        };
+        let mut polygon_indices = solid.polygon_indices.clone();
+        for href in &solid.polygon_hrefs {
+            match polygon_index_by_id.get(href) {
+                Some(index) => polygon_indices.push(*index),
+                None => {
+                    // surfaces, which cannot be resolved, leave the shell open
+                    self.report(
+                        GeometryErrorCode::ShellNotClosed,
+                        &context,
+                        solid.id.as_ref(),
+                    );
+                    return;
+                }
+            }
+        }
+
+        if polygon_indices.len() < 4 {
+            self.report(
+                GeometryErrorCode::TooFewPolygons,
+                &context,
+                solid.id.as_ref(),
+            );
+            return;
+        }
+        // shell checks require valid polygons, whose errors are already reported
+        if polygon_indices.iter().any(|x| !self.polygons[*x].is_valid) {
+            return;
+        }
+
+        let snap = |x: &Vector3<f64>| -> [i64; 3] {
+            [
+                (x.x / self.tolerance.snap).round() as i64,
+                (x.y / self.tolerance.snap).round() as i64,
+                (x.z / self.tolerance.snap).round() as i64,
+            ]
+        };
+        // the volume is summed relative to a vertex of the shell, since the products of
+        // georeferenced coordinates would cancel out the volume of small solids
+        let reference = self.polygons[polygon_indices[0]].exterior[0];
+        let mut edges: HashMap<([i64; 3], [i64; 3]), Vec<bool>> = HashMap::new();
+        let mut signed_volume = 0.0;
+        for polygon in polygon_indices.iter().map(|x| &self.polygons[*x]) {
+            for ring in std::iter::once(&polygon.exterior).chain(polygon.interiors.iter()) {
+                for i in 0..ring.len() {
+                    let (start, end) = (snap(&ring[i]), snap(&ring[(i + 1) % ring.len()]));
+                    let (key, is_forward) = if start < end {
+                        ((start, end), true)
+                    } else {
+                        ((end, start), false)
+                    };
+                    edges.entry(key).or_default().push(is_forward);
+
+                    if i > 0 && i + 1 < ring.len() {
+                        signed_volume += (ring[0] - reference)
+                            .dot(&(ring[i] - reference).cross(&(ring[i + 1] - reference)))
+                            / 6.0;
+                    }
+                }
+            }
+        }
+
+        let mut is_consistent = true;
+        if edges.values().any(|x| x.len() == 1) {
+            self.report(
+                GeometryErrorCode::ShellNotClosed,
+                &context,
+                solid.id.as_ref(),
+            );
+            is_consistent = false;
+        }
+        if edges.values().any(|x| x.len() > 2) {
+            self.report(
+                GeometryErrorCode::NonManifoldCase,
+                &context,
+                solid.id.as_ref(),
+            );
+            is_consistent = false;
+        }
+        if edges.values().any(|x| x.len() == 2 && x[0] == x[1]) {
+            self.report(
+                GeometryErrorCode::PolygonWrongOrientation,
+                &context,
+                solid.id.as_ref(),
+            );
+            is_consistent = false;
+        }
+        if is_consistent && signed_volume < 0.0 {
+            self.report(
+                GeometryErrorCode::AllPolygonsWrongOrientation,
+                &context,
+                solid.id.as_ref(),
+            );
+        }
+    }
+}
+
+/// Feature and LOD, to which a geometry belongs.
+#[derive(Debug, Clone, Default)]
+struct GeometryContext {
+    feature_id: Option<String>,
+    lod: Option<u8>,
+}
+
+impl GeometryContext {
+    fn new(open_elements: &[OpenElement]) -> Self {
+        let feature_id = open_elements
+            .iter()
+            .rev()
+            .find(|x| !x.is_gml && x.id.is_some())
+            .and_then(|x| x.id.clone());
+        let lod = open_elements
+            .iter()
+            .rev()
+            .find_map(|x| lod_of_property(&x.local_name));
+
+        Self { feature_id, lod }
+    }
+}
+
+/// Returns the LOD of properties like `lod2Solid` or `lod3MultiSurface`.
+fn lod_of_property(local_name: &str) -> Option<u8> {
+    local_name
+        .strip_prefix("lod")
+        .and_then(|x| x.chars().next())
+        .and_then(|x| x.to_digit(10))
+        .map(|x| x as u8)
+}
+
+fn is_solid_property(open_elements: &[OpenElement]) -> bool {
+    open_elements.last().is_some_and(|x| {
+        !x.is_gml && lod_of_property(&x.local_name).is_some() && x.local_name.ends_with("Solid")
+    })
+}
+
+fn is_in_solid_interior(open_elements: &[OpenElement]) -> bool {
+    open_elements
+        .windows(2)
+        .any(|x| x[0].is_gml && x[0].local_name == "Solid" && x[1].local_name == "interior")
+}
+
+fn open_element(
+    reader: &NsReader<&[u8]>,
+    prefix: Option<&'static str>,
+    start: &BytesStart,
+    parent: Option<&OpenElement>,
+) -> OpenElement {
+    let mut id = None;
+    let mut srs_dimension = parent.and_then(|x| x.srs_dimension);
+    for attribute in start.attributes().flatten() {
+        let (namespace, name) = reader.resolve_attribute(attribute.key);
+        match name.as_ref() {
+            b"id" if rule_name_prefix(&namespace) == Some("gml") => {
+                id = Some(String::from_utf8_lossy(&attribute.value).to_string());
+            }
+            b"srsDimension" => {
+                srs_dimension = String::from_utf8_lossy(&attribute.value).parse().ok();
+            }
+            _ => {}
+        }
+    }
+
+    OpenElement {
+        local_name: String::from_utf8_lossy(start.local_name().as_ref()).to_string(),
+        is_gml: prefix == Some("gml"),
+        id,
+        srs_dimension,
+    }
+}
+
+fn extract_href(reader: &NsReader<&[u8]>, start: &BytesStart) -> Option<String> {
+    start
+        .attributes()
+        .flatten()
+        .find(|x| reader.resolve_attribute(x.key).1.as_ref() == b"href")
+        .map(|x| String::from_utf8_lossy(&x.value).to_string())
+}
+
+fn parse_coordinates(text: &str, srs_dimension: usize) -> Vec<Vector3<f64>> {
+    let values: Vec<f64> = text
+        .split_whitespace()
+        .filter_map(|x| x.parse().ok())
+        .collect();
+    values
+        .chunks_exact(srs_dimension.max(2))
+        .map(|x| Vector3::new(x[0], x[1], x.get(2).copied().unwrap_or(0.0)))
+        .collect()
+}
+
+/// Checks closure, consecutive duplicates and the number of points of a ring and removes its
+/// closing point.
+fn check_ring(
+    ring: &mut Vec<Vector3<f64>>,
+    tolerance: &GeometryTolerance,
+) -> Option<GeometryErrorCode> {
+    let is_equal = |a: &Vector3<f64>, b: &Vector3<f64>| (a - b).norm() <= tolerance.snap;
+
+    let is_closed = ring.len() > 1 && is_equal(&ring[0], &ring[ring.len() - 1]);
+    if !is_closed {
+        return Some(GeometryErrorCode::RingNotClosed);
+    }
+    ring.pop();
+
+    if ring.windows(2).any(|x| is_equal(&x[0], &x[1])) {
+        return Some(GeometryErrorCode::ConsecutivePointsSame);
+    }
+    if ring.len() < 3 {
+        return Some(GeometryErrorCode::TooFewPoints);
+    }
+
+    None
+}
+
+/// Computes the unit normal of a ring.
+///
+/// Falls back to the first three non-collinear points for rings enclosing no area, such as
+/// self-intersecting bowties.
+fn ring_normal(ring: &[Vector3<f64>]) -> Option<Vector3<f64>> {
+    newell_vector(ring).try_normalize(f64::EPSILON).or_else(|| {
+        (1..ring.len()).find_map(|i| {
+            (i + 1..ring.len()).find_map(|j| {
+                (ring[i] - ring[0])
+                    .cross(&(ring[j] - ring[0]))
+                    .try_normalize(f64::EPSILON)
+            })
+        })
+    })
+}
+
+/// Projects a ring onto the coordinate plane, in which the polygon has the largest extent.
+fn project_ring(ring: &[Vector3<f64>], normal: &Vector3<f64>) -> Vec<Vector2<f64>> {
+    let dropped_axis = normal.iamax();
+    ring.iter()
+        .map(|x| match dropped_axis {
+            0 => Vector2::new(x.y, x.z),
+            1 => Vector2::new(x.x, x.z),
+            _ => Vector2::new(x.x, x.y),
+        })
+        .collect()
+}
+
+fn is_self_intersecting(ring: &[Vector2<f64>]) -> bool {
+    let n = ring.len();
+    (0..n).any(|i| {
+        (i + 1..n).any(|j| {
+            let is_adjacent = j == i + 1 || (i == 0 && j == n - 1);
+            !is_adjacent
+                && segments_intersect(&ring[i], &ring[(i + 1) % n], &ring[j], &ring[(j + 1) % n])
+        })
+    })
+}
+
+fn segments_intersect(
+    a: &Vector2<f64>,
+    b: &Vector2<f64>,
+    c: &Vector2<f64>,
+    d: &Vector2<f64>,
+) -> bool {
+    let orientation = |p: &Vector2<f64>, q: &Vector2<f64>, r: &Vector2<f64>| {
+        let value = (q - p).perp(&(r - p));
+        if value.abs() < f64::EPSILON {
+            0
+        } else {
+            value.signum() as i8
+        }
+    };
+    let is_on_segment = |p: &Vector2<f64>, q: &Vector2<f64>, r: &Vector2<f64>| {
+        r.x >= p.x.min(q.x) && r.x <= p.x.max(q.x) && r.y >= p.y.min(q.y) && r.y <= p.y.max(q.y)
+    };
+
+    let (o1, o2, o3, o4) = (
+        orientation(a, b, c),
+        orientation(a, b, d),
+        orientation(c, d, a),
+        orientation(c, d, b),
+    );
+    (o1 != o2 && o3 != o4)
+        || (o1 == 0 && is_on_segment(a, b, c))
+        || (o2 == 0 && is_on_segment(a, b, d))
+        || (o3 == 0 && is_on_segment(c, d, a))
+        || (o4 == 0 && is_on_segment(c, d, b))
+}
+
+pub(crate) fn is_point_in_ring(point: &Vector2<f64>, ring: &[Vector2<f64>]) -> bool {
+    let mut is_inside = false;
+    for i in 0..ring.len() {
+        let (a, b) = (&ring[i], &ring[(i + 1) % ring.len()]);
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+        {
+            is_inside = !is_inside;
+        }
+    }
+    is_inside
+}
+
+pub(crate) fn distance_to_ring(point: &Vector2<f64>, ring: &[Vector2<f64>]) -> f64 {
+    (0..ring.len())
+        .map(|i| {
+            let (a, b) = (&ring[i], &ring[(i + 1) % ring.len()]);
+            let t = ((point - a).dot(&(b - a)) / (b - a).norm_squared()).clamp(0.0, 1.0);
+            (point - (a + (b - a) * t)).norm()
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Checks polygons and solids following the error codes of val3dity.
+///
+/// The raw coordinates of the document are checked, since invalid rings cannot be represented by
+/// the model.
+pub(crate) fn check_geometry(
+    xml_document: &str,
+    tolerance: GeometryTolerance,
+) -> Vec<GeometryError> {
+    GeometryChecker {
+        tolerance,
+        errors: Vec::new(),
+        polygons: Vec::new(),
+        solids: Vec::new(),
+    }
+    .check(xml_document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(id: &str, points: &[[f64; 3]]) -> String {
+        let pos_list: Vec<String> = points.iter().flatten().map(|x| x.to_string()).collect();
+        format!(
+            "<gml:surfaceMember><gml:Polygon gml:id=\"{id}\"><gml:exterior><gml:LinearRing><gml:posList>{}</gml:posList></gml:LinearRing></gml:exterior></gml:Polygon></gml:surfaceMember>",
+            pos_list.join(" ")
+        )
+    }
+
+    const CUBE_FACES: [(&str, [[f64; 3]; 5]); 6] = [
+        (
+            "bottom",
+            [
+                [0., 0., 0.],
+                [0., 1., 0.],
+                [1., 1., 0.],
+                [1., 0., 0.],
+                [0., 0., 0.],
+            ],
+        ),
+        (
+            "top",
+            [
+                [0., 0., 1.],
+                [1., 0., 1.],
+                [1., 1., 1.],
+                [0., 1., 1.],
+                [0., 0., 1.],
+            ],
+        ),
+        (
+            "front",
+            [
+                [0., 0., 0.],
+                [1., 0., 0.],
+                [1., 0., 1.],
+                [0., 0., 1.],
+                [0., 0., 0.],
+            ],
+        ),
+        (
+            "right",
+            [
+                [1., 0., 0.],
+                [1., 1., 0.],
+                [1., 1., 1.],
+                [1., 0., 1.],
+                [1., 0., 0.],
+            ],
+        ),
+        (
+            "back",
+            [
+                [1., 1., 0.],
+                [0., 1., 0.],
+                [0., 1., 1.],
+                [1., 1., 1.],
+                [1., 1., 0.],
+            ],
+        ),
+        (
+            "left",
+            [
+                [0., 1., 0.],
+                [0., 0., 0.],
+                [0., 0., 1.],
+                [0., 1., 1.],
+                [0., 1., 0.],
+            ],
+        ),
+    ];
+
+    /// Returns the faces of a unit cube with outward normals, except for the flipped faces.
+    fn cube(flipped_faces: &[&str], omitted_faces: &[&str]) -> String {
+        CUBE_FACES
+            .iter()
+            .filter(|(id, _)| !omitted_faces.contains(id))
+            .map(|(id, points)| {
+                let mut points = points.to_vec();
+                if flipped_faces.contains(id) {
+                    points.reverse();
+                }
+                polygon(id, &points)
+            })
+            .collect()
+    }
+
+    fn document(lod2_solid: &str, lod2_multi_surface: &str) -> String {
+        format!(
+            "<CityModel xmlns=\"http://www.opengis.net/citygml/3.0\" xmlns:gml=\"http://www.opengis.net/gml/3.2\" xmlns:bldg=\"http://www.opengis.net/citygml/building/3.0\">\
+            <cityObjectMember><bldg:Building gml:id=\"building_1\">\
+            <lod2Solid><gml:Solid gml:id=\"solid_1\"><gml:exterior><gml:Shell>{lod2_solid}</gml:Shell></gml:exterior></gml:Solid></lod2Solid>\
+            <lod2MultiSurface><gml:MultiSurface>{lod2_multi_surface}</gml:MultiSurface></lod2MultiSurface>\
+            </bldg:Building></cityObjectMember></CityModel>"
+        )
+    }
+
+    #[test]
+    fn test_check_geometry_accepts_valid_solid() {
+        let errors = check_geometry(&document(&cube(&[], &[]), ""), GeometryTolerance::default());
+
+        assert!(errors.is_empty(), "{errors:?}");
+    }
+
+    #[test]
+    fn test_check_geometry_detects_ring_errors() {
+        let multi_surface = [
+            polygon("open", &[[0., 0., 0.], [1., 0., 0.], [1., 1., 0.]]),
+            polygon(
+                "duplicate",
+                &[
+                    [0., 0., 0.],
+                    [1., 0., 0.],
+                    [1., 0., 0.],
+                    [1., 1., 0.],
+                    [0., 0., 0.],
+                ],
+            ),
+            polygon(
+                "bowtie",
+                &[
+                    [0., 0., 0.],
+                    [1., 1., 0.],
+                    [1., 0., 0.],
+                    [0., 1., 0.],
+                    [0., 0., 0.],
+                ],
+            ),
+            polygon(
+                "non_planar",
+                &[
+                    [0., 0., 0.],
+                    [1., 0., 0.],
+                    [1., 1., 0.5],
+                    [0., 1., 0.],
+                    [0., 0., 0.],
+                ],
+            ),
+        ]
+        .concat();
+
+        let errors = check_geometry(
+            &document(&cube(&[], &[]), &multi_surface),
+            GeometryTolerance::default(),
+        );
+        let codes: Vec<(Option<&str>, u16)> = errors
+            .iter()
+            .map(|x| (x.geometry_id.as_deref(), x.code))
+            .collect();
+
+        assert_eq!(
+            codes,
+            vec![
+                (Some("open"), 103),
+                (Some("duplicate"), 102),
+                (Some("bowtie"), 104),
+                (Some("non_planar"), 203)
+            ]
+        );
+        assert!(errors.iter().all(|x| x.lod == Some(2)));
+        assert!(
+            errors
+                .iter()
+                .all(|x| x.feature_id.as_deref() == Some("building_1"))
+        );
+    }
+
+    #[test]
+    fn test_check_geometry_detects_shell_errors() {
+        let errors = check_geometry(
+            &document(&cube(&["top"], &[]), ""),
+            GeometryTolerance::default(),
+        );
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, GeometryErrorCode::PolygonWrongOrientation);
+        assert_eq!(errors[0].geometry_id.as_deref(), Some("solid_1"));
+
+        let errors = check_geometry(
+            &document(&cube(&[], &["bottom"]), ""),
+            GeometryTolerance::default(),
+        );
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, GeometryErrorCode::ShellNotClosed);
+
+        let all_faces: Vec<&str> = CUBE_FACES.iter().map(|(id, _)| *id).collect();
+        let errors = check_geometry(
+            &document(&cube(&all_faces, &[]), ""),
+            GeometryTolerance::default(),
+        );
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].error,
+            GeometryErrorCode::AllPolygonsWrongOrientation
+        );
+    }
+
+    #[test]
+    fn test_check_geometry_accepts_valid_solid_at_projected_coordinates() {
+        // a 1 × 1 × 0.5 box at coordinates of a projected CRS with zone prefix
+        let offset = [32690123.457, 5336789.013, 512.3];
+        let solid: String = CUBE_FACES
+            .iter()
+            .map(|(id, points)| {
+                let points: Vec<[f64; 3]> = points
+                    .iter()
+                    .map(|x| [x[0] + offset[0], x[1] + offset[1], x[2] * 0.5 + offset[2]])
+                    .collect();
+                polygon(id, &points)
+            })
+            .collect();
+
+        let errors = check_geometry(&document(&solid, ""), GeometryTolerance::default());
+
+        assert!(errors.is_empty(), "{errors:?}");
+    }
+
+    #[test]
+    fn test_check_geometry_resolves_referenced_solid_faces() {
+        let references: String = CUBE_FACES
+            .iter()
+            .map(|(id, _)| format!("<gml:surfaceMember xlink:href=\"#{id}\"/>"))
+            .collect();
+        let xml_document = document(&references, &cube(&[], &[])).replace(
+            "<CityModel ",
+            "<CityModel xmlns:xlink=\"http://www.w3.org/1999/xlink\" ",
+        );
+
+        let errors = check_geometry(&xml_document, GeometryTolerance::default());
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let xml_document = xml_document.replace("#top", "#missing");
+        let errors = check_geometry(&xml_document, GeometryTolerance::default());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, GeometryErrorCode::ShellNotClosed);
+    }
+}
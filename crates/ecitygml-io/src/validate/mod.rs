@@ -1,5 +1,33 @@
 pub mod extracted_information;
+pub(crate) mod geometry;
+mod profile;
 pub mod report;
+mod report_format;
+mod semantic;
+pub(crate) mod structure;
+mod topology;
+
+#[doc(inline)]
+pub use crate::validate::geometry::{
+    DEFAULT_PLANARITY_TOLERANCE, DEFAULT_SNAP_TOLERANCE, GeometryTolerance,
+};
+
+#[doc(inline)]
+pub use crate::validate::profile::{
+    Check, RULES, RuleConfig, RuleDefinition, Severity, ValidationProfile,
+};
 
 #[doc(inline)]
 pub use crate::validate::report::Report;
+
+#[doc(inline)]
+pub use crate::validate::report_format::ReportFormat;
+
+#[doc(inline)]
+pub use crate::validate::semantic::{SemanticTolerance, check_semantics};
+
+#[doc(inline)]
+pub use crate::validate::topology::{TopologyTolerance, check_topology};
+
+#[doc(inline)]
+pub use crate::validate_impl::ValidateOptions;
@@ -0,0 +1,406 @@
+use crate::error::Error;
+use crate::validate::geometry::GeometryTolerance;
+use crate::validate::semantic::SemanticTolerance;
+use crate::validate::topology::TopologyTolerance;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Severity of the findings of a rule in the report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// Check, which produces the findings of a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Check {
+    /// Checks of gml:ids and references, which always run.
+    References,
+    Structure,
+    Geometry,
+    Semantic,
+    Topology,
+}
+
+/// Rule of the validation with its default severity and parameters.
+#[derive(Debug, Clone, Copy)]
+pub struct RuleDefinition {
+    pub id: &'static str,
+    pub check: Check,
+    pub default_severity: Severity,
+    pub parameters: &'static [(&'static str, f64)],
+}
+
+const fn rule(
+    id: &'static str,
+    check: Check,
+    default_severity: Severity,
+    parameters: &'static [(&'static str, f64)],
+) -> RuleDefinition {
+    RuleDefinition {
+        id,
+        check,
+        default_severity,
+        parameters,
+    }
+}
+
+/// All rules of the validation, identified by their id in the profiles.
+pub static RULES: &[RuleDefinition] = &[
+    rule("gml_id_duplicate", Check::References, Severity::Error, &[]),
+    rule(
+        "broken_object_relation",
+        Check::References,
+        Severity::Error,
+        &[],
+    ),
+    rule(
+        "broken_predecessor_href",
+        Check::References,
+        Severity::Warning,
+        &[],
+    ),
+    rule(
+        "broken_successor_href",
+        Check::References,
+        Severity::Warning,
+        &[],
+    ),
+    rule(
+        "structure_violation",
+        Check::Structure,
+        Severity::Error,
+        &[],
+    ),
+    rule("too_few_points", Check::Geometry, Severity::Error, &[]),
+    rule(
+        "consecutive_points_same",
+        Check::Geometry,
+        Severity::Error,
+        &[("snap_tolerance", 0.001)],
+    ),
+    rule("ring_not_closed", Check::Geometry, Severity::Error, &[]),
+    rule(
+        "ring_self_intersection",
+        Check::Geometry,
+        Severity::Error,
+        &[],
+    ),
+    rule(
+        "non_planar_polygon_distance_plane",
+        Check::Geometry,
+        Severity::Error,
+        &[("tolerance", 0.01)],
+    ),
+    rule("inner_ring_outside", Check::Geometry, Severity::Error, &[]),
+    rule("too_few_polygons", Check::Geometry, Severity::Error, &[]),
+    rule("shell_not_closed", Check::Geometry, Severity::Error, &[]),
+    rule("non_manifold_case", Check::Geometry, Severity::Error, &[]),
+    rule(
+        "polygon_wrong_orientation",
+        Check::Geometry,
+        Severity::Error,
+        &[],
+    ),
+    rule(
+        "all_polygons_wrong_orientation",
+        Check::Geometry,
+        Severity::Error,
+        &[],
+    ),
+    rule(
+        "roof_surface_normal_upward",
+        Check::Semantic,
+        Severity::Warning,
+        &[("angle", 5.0)],
+    ),
+    rule(
+        "ground_surface_normal_downward",
+        Check::Semantic,
+        Severity::Warning,
+        &[("angle", 10.0)],
+    ),
+    rule(
+        "ground_surface_at_minimum_height",
+        Check::Semantic,
+        Severity::Warning,
+        &[("height", 1.0)],
+    ),
+    rule(
+        "wall_surface_vertical",
+        Check::Semantic,
+        Severity::Warning,
+        &[("angle", 10.0)],
+    ),
+    rule(
+        "opening_within_wall_surface",
+        Check::Semantic,
+        Severity::Warning,
+        &[("distance", 0.2)],
+    ),
+    rule(
+        "boundary_surfaces_cover_solid",
+        Check::Semantic,
+        Severity::Warning,
+        &[("coverage", 0.05)],
+    ),
+    rule("model_not_readable", Check::Semantic, Severity::Error, &[]),
+    rule(
+        "predecessor_successor_asymmetric",
+        Check::Topology,
+        Severity::Error,
+        &[],
+    ),
+    rule(
+        "successor_not_connected",
+        Check::Topology,
+        Severity::Warning,
+        &[("distance", 0.1)],
+    ),
+    rule(
+        "dangling_traffic_space",
+        Check::Topology,
+        Severity::Info,
+        &[],
+    ),
+    rule(
+        "traffic_space_cycle",
+        Check::Topology,
+        Severity::Warning,
+        &[],
+    ),
+];
+
+pub fn find_rule(id: &str) -> Option<&'static RuleDefinition> {
+    RULES.iter().find(|x| x.id == id)
+}
+
+/// Configuration of a rule in a profile, where omitted values keep the defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Severity>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub parameters: BTreeMap<String, f64>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Selection of the validation rules with their severities and parameters.
+///
+/// Rules not listed in the profile are enabled with their default severity and parameters.
+///
+/// ```yaml
+/// name: delivery-acceptance
+/// rules:
+///   non_planar_polygon_distance_plane:
+///     severity: warning
+///     parameters:
+///       tolerance: 0.02
+///   wall_surface_vertical:
+///     enabled: false
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationProfile {
+    pub name: String,
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleConfig>,
+}
+
+impl Default for ValidationProfile {
+    fn default() -> Self {
+        Self {
+            name: "default".to_string(),
+            rules: BTreeMap::new(),
+        }
+    }
+}
+
+impl ValidationProfile {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let yaml = fs::read_to_string(path)?;
+        Self::from_yaml(&yaml)
+    }
+
+    /// Parses a profile and checks that all rules and parameters exist.
+    pub fn from_yaml(yaml: &str) -> Result<Self, Error> {
+        let profile: Self = serde_yaml::from_str(yaml)?;
+
+        for (id, config) in &profile.rules {
+            let rule = find_rule(id).ok_or_else(|| Error::UnknownValidationRule(id.clone()))?;
+            if let Some(name) = config
+                .parameters
+                .keys()
+                .find(|x| !rule.parameters.iter().any(|(name, _)| name == x))
+            {
+                return Err(Error::UnknownValidationRuleParameter(
+                    id.clone(),
+                    name.clone(),
+                ));
+            }
+        }
+
+        Ok(profile)
+    }
+
+    pub fn is_enabled(&self, id: &str) -> bool {
+        self.rules.get(id).is_none_or(|x| x.enabled)
+    }
+
+    /// Returns the severity of the rule, which is [`Severity::Error`] for unknown rules.
+    pub fn severity(&self, id: &str) -> Severity {
+        self.rules
+            .get(id)
+            .and_then(|x| x.severity)
+            .or_else(|| find_rule(id).map(|x| x.default_severity))
+            .unwrap_or(Severity::Error)
+    }
+
+    /// Returns the configured or default value of a rule parameter.
+    pub fn parameter(&self, id: &str, name: &str) -> Option<f64> {
+        self.rules
+            .get(id)
+            .and_then(|x| x.parameters.get(name).copied())
+            .or_else(|| {
+                find_rule(id)?
+                    .parameters
+                    .iter()
+                    .find(|(x, _)| *x == name)
+                    .map(|(_, value)| *value)
+            })
+    }
+
+    /// Returns true, if any rule produced by the check is enabled.
+    pub fn is_check_enabled(&self, check: Check) -> bool {
+        RULES
+            .iter()
+            .filter(|x| x.check == check)
+            .any(|x| self.is_enabled(x.id))
+    }
+
+    pub fn geometry_tolerance(&self) -> GeometryTolerance {
+        let default = GeometryTolerance::default();
+        GeometryTolerance {
+            snap: self
+                .parameter("consecutive_points_same", "snap_tolerance")
+                .unwrap_or(default.snap),
+            planarity: self
+                .parameter("non_planar_polygon_distance_plane", "tolerance")
+                .unwrap_or(default.planarity),
+        }
+    }
+
+    pub fn topology_tolerance(&self) -> TopologyTolerance {
+        let default = TopologyTolerance::default();
+        TopologyTolerance {
+            connection_distance: self
+                .parameter("successor_not_connected", "distance")
+                .unwrap_or(default.connection_distance),
+        }
+    }
+
+    pub fn semantic_tolerance(&self) -> SemanticTolerance {
+        let default = SemanticTolerance::default();
+        SemanticTolerance {
+            roof_surface_angle: self
+                .parameter("roof_surface_normal_upward", "angle")
+                .unwrap_or(default.roof_surface_angle),
+            ground_surface_angle: self
+                .parameter("ground_surface_normal_downward", "angle")
+                .unwrap_or(default.ground_surface_angle),
+            ground_surface_height: self
+                .parameter("ground_surface_at_minimum_height", "height")
+                .unwrap_or(default.ground_surface_height),
+            wall_surface_angle: self
+                .parameter("wall_surface_vertical", "angle")
+                .unwrap_or(default.wall_surface_angle),
+            opening_distance: self
+                .parameter("opening_within_wall_surface", "distance")
+                .unwrap_or(default.opening_distance),
+            boundary_coverage: self
+                .parameter("boundary_surfaces_cover_solid", "coverage")
+                .unwrap_or(default.boundary_coverage),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bundled_profiles() {
+        let delivery_acceptance =
+            ValidationProfile::from_yaml(include_str!("../../profiles/delivery-acceptance.yaml"))
+                .expect("should work");
+        assert_eq!(delivery_acceptance.name, "delivery-acceptance");
+        assert_eq!(
+            delivery_acceptance.severity("broken_predecessor_href"),
+            Severity::Error
+        );
+
+        let research = ValidationProfile::from_yaml(include_str!("../../profiles/research.yaml"))
+            .expect("should work");
+        assert!(!research.is_enabled("structure_violation"));
+        assert!(!research.is_check_enabled(Check::Structure));
+        assert_eq!(research.geometry_tolerance().planarity, 0.05);
+        assert_eq!(research.severity("ring_not_closed"), Severity::Warning);
+    }
+
+    #[test]
+    fn test_reject_unknown_rules_and_parameters() {
+        let result = ValidationProfile::from_yaml("name: x\nrules:\n  unknown_rule: {}\n");
+        assert!(matches!(result, Err(Error::UnknownValidationRule(_))));
+
+        let result = ValidationProfile::from_yaml(
+            "name: x\nrules:\n  ring_not_closed:\n    parameters:\n      tolerance: 1.0\n",
+        );
+        assert!(matches!(
+            result,
+            Err(Error::UnknownValidationRuleParameter(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_validate_with_profile() {
+        let xml_document = r#"<core:CityModel xmlns:core="http://www.opengis.net/citygml/2.0" xmlns:gml="http://www.opengis.net/gml" xmlns:bldg="http://www.opengis.net/citygml/building/2.0">
+  <core:cityObjectMember><bldg:Building gml:id="b1"><bldg:function>1000</bldg:function></bldg:Building></core:cityObjectMember>
+  <core:cityObjectMember><bldg:Building gml:id="b1"><bldg:function>1000</bldg:function></bldg:Building></core:cityObjectMember>
+</core:CityModel>"#;
+        let report = crate::CitygmlReader::from_str(xml_document)
+            .validate()
+            .expect("should work");
+        assert_eq!(report.count_findings(Severity::Error), 1);
+
+        let profile = ValidationProfile::from_yaml(
+            "name: x\nrules:\n  gml_id_duplicate:\n    severity: info\n",
+        )
+        .expect("should work");
+        let report = crate::CitygmlReader::from_str(xml_document)
+            .with_profile(profile)
+            .validate()
+            .expect("should work");
+        assert_eq!(report.count_findings(Severity::Error), 0);
+        assert_eq!(report.count_findings(Severity::Info), 1);
+
+        let profile = ValidationProfile::from_yaml(
+            "name: x\nrules:\n  gml_id_duplicate:\n    enabled: false\n",
+        )
+        .expect("should work");
+        let report = crate::CitygmlReader::from_str(xml_document)
+            .with_profile(profile)
+            .validate()
+            .expect("should work");
+        assert!(report.gml_id_duplicates.is_empty());
+        assert!(report.findings.is_empty());
+    }
+}
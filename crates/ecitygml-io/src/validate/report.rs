@@ -1,35 +1,267 @@
+use crate::error::Error;
+use crate::validate::profile::Severity;
+use crate::validate::report_format;
+use crate::validate::report_format::ReportFormat;
 use serde::{Deserialize, Serialize};
 
-use std::fs;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::path::Path;
 
+/// Result of the validation, which can be written in the formats of [`ReportFormat`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Report {
-    pub(crate) statistics: ReportStatistics,
-    pub(crate) xml_elements: Vec<ReportElement>,
-    pub(crate) gml_id_duplicates: Vec<(String, usize)>,
-    pub(crate) broken_object_relations: Vec<String>,
-    pub(crate) broken_predecessor_hrefs: Vec<String>,
-    pub(crate) broken_successor_hrefs: Vec<String>,
+    pub statistics: ReportStatistics,
+    pub xml_elements: Vec<ReportElement>,
+    pub gml_id_duplicates: Vec<(String, usize)>,
+    pub broken_object_relations: Vec<String>,
+    pub broken_predecessor_hrefs: Vec<String>,
+    pub broken_successor_hrefs: Vec<String>,
+    /// References resolved in other documents, when a dataset of multiple documents is validated.
+    pub cross_file_references: Vec<CrossFileReference>,
+    pub structure_violations: Vec<StructureViolation>,
+    pub geometry_errors: Vec<GeometryError>,
+    pub semantic_issues: Vec<SemanticIssue>,
+    pub topology_issues: Vec<TopologyIssue>,
+    /// Path of the validated document, which is referenced by the JUnit and SARIF reports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    pub profile_name: String,
+    pub findings: Vec<Finding>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReportStatistics {
-    pub(crate) number_of_gml_duplicates: usize,
-    pub(crate) number_of_broken_object_relations: usize,
+    pub number_of_gml_duplicates: usize,
+    pub number_of_broken_object_relations: usize,
+    pub number_of_cross_file_references: usize,
+    pub number_of_structure_violations: usize,
+    pub number_of_geometry_errors: usize,
+    pub number_of_semantic_issues: usize,
+    pub number_of_topology_issues: usize,
+    pub number_of_errors: usize,
+    pub number_of_warnings: usize,
+    pub number_of_infos: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReportElement {
-    pub(crate) element_name: String,
-    pub(crate) number_total: usize,
-    pub(crate) number_with_gml_id: usize,
-    pub(crate) number_without_gml_id: usize,
+    pub element_name: String,
+    pub number_total: usize,
+    pub number_with_gml_id: usize,
+    pub number_without_gml_id: usize,
+}
+
+/// Resolution of an href to the gml:id of a feature.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ReferenceResolution {
+    /// The gml:id is contained in the same document.
+    ResolvedLocally,
+    /// The gml:id is contained in the given other document of the dataset.
+    ResolvedInOtherFile(String),
+    /// The gml:id is contained in none of the documents.
+    Broken,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceKind {
+    Predecessor,
+    Successor,
+    ObjectRelation,
+}
+
+/// Reference to a feature contained in another document of the dataset.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct CrossFileReference {
+    pub kind: ReferenceKind,
+    pub href: String,
+    pub document: String,
+}
+
+/// Result of an enabled rule with the severity assigned by the validation profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Finding {
+    pub rule_id: String,
+    pub severity: Severity,
+    pub feature_id: Option<String>,
+    /// Position in the document, which is known for structure violations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+/// Violation of the well-formedness or the structural rules at a position in the document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructureViolation {
+    pub line: usize,
+    pub column: usize,
+    pub element_name: String,
+    pub message: String,
+}
+
+/// Geometric error of a polygon or solid, identified by the feature and the LOD it belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeometryError {
+    pub feature_id: Option<String>,
+    pub lod: Option<u8>,
+    pub geometry_id: Option<String>,
+    pub code: u16,
+    pub error: GeometryErrorCode,
+}
+
+/// Error codes of the geometric validation as defined by val3dity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GeometryErrorCode {
+    TooFewPoints,
+    ConsecutivePointsSame,
+    RingNotClosed,
+    RingSelfIntersection,
+    NonPlanarPolygonDistancePlane,
+    InnerRingOutside,
+    TooFewPolygons,
+    ShellNotClosed,
+    NonManifoldCase,
+    PolygonWrongOrientation,
+    AllPolygonsWrongOrientation,
+}
+
+impl GeometryErrorCode {
+    /// Returns the id of the rule in the validation profiles.
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            GeometryErrorCode::TooFewPoints => "too_few_points",
+            GeometryErrorCode::ConsecutivePointsSame => "consecutive_points_same",
+            GeometryErrorCode::RingNotClosed => "ring_not_closed",
+            GeometryErrorCode::RingSelfIntersection => "ring_self_intersection",
+            GeometryErrorCode::NonPlanarPolygonDistancePlane => "non_planar_polygon_distance_plane",
+            GeometryErrorCode::InnerRingOutside => "inner_ring_outside",
+            GeometryErrorCode::TooFewPolygons => "too_few_polygons",
+            GeometryErrorCode::ShellNotClosed => "shell_not_closed",
+            GeometryErrorCode::NonManifoldCase => "non_manifold_case",
+            GeometryErrorCode::PolygonWrongOrientation => "polygon_wrong_orientation",
+            GeometryErrorCode::AllPolygonsWrongOrientation => "all_polygons_wrong_orientation",
+        }
+    }
+
+    pub fn code(&self) -> u16 {
+        match self {
+            GeometryErrorCode::TooFewPoints => 101,
+            GeometryErrorCode::ConsecutivePointsSame => 102,
+            GeometryErrorCode::RingNotClosed => 103,
+            GeometryErrorCode::RingSelfIntersection => 104,
+            GeometryErrorCode::NonPlanarPolygonDistancePlane => 203,
+            GeometryErrorCode::InnerRingOutside => 206,
+            GeometryErrorCode::TooFewPolygons => 301,
+            GeometryErrorCode::ShellNotClosed => 302,
+            GeometryErrorCode::NonManifoldCase => 303,
+            GeometryErrorCode::PolygonWrongOrientation => 307,
+            GeometryErrorCode::AllPolygonsWrongOrientation => 308,
+        }
+    }
+}
+
+/// Violation of a semantic consistency rule by a city object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SemanticIssue {
+    pub feature_id: String,
+    pub parent_id: Option<String>,
+    pub rule: SemanticRule,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SemanticRule {
+    RoofSurfaceNormalUpward,
+    GroundSurfaceNormalDownward,
+    GroundSurfaceAtMinimumHeight,
+    WallSurfaceVertical,
+    OpeningWithinWallSurface,
+    BoundarySurfacesCoverSolid,
+    /// The model could not be read, so that no semantic rules were checked.
+    ModelNotReadable,
+}
+
+impl SemanticRule {
+    /// Returns the id of the rule in the validation profiles.
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            SemanticRule::RoofSurfaceNormalUpward => "roof_surface_normal_upward",
+            SemanticRule::GroundSurfaceNormalDownward => "ground_surface_normal_downward",
+            SemanticRule::GroundSurfaceAtMinimumHeight => "ground_surface_at_minimum_height",
+            SemanticRule::WallSurfaceVertical => "wall_surface_vertical",
+            SemanticRule::OpeningWithinWallSurface => "opening_within_wall_surface",
+            SemanticRule::BoundarySurfacesCoverSolid => "boundary_surfaces_cover_solid",
+            SemanticRule::ModelNotReadable => "model_not_readable",
+        }
+    }
+}
+
+/// Violation of a topology rule of the road network by a traffic space.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopologyIssue {
+    pub feature_id: String,
+    pub parent_id: Option<String>,
+    pub rule: TopologyRule,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TopologyRule {
+    /// A successor does not list the traffic space as predecessor or vice versa.
+    PredecessorSuccessorAsymmetric,
+    /// The geometry of the traffic space does not touch the geometry of its successor.
+    SuccessorNotConnected,
+    /// The traffic space has no predecessor or no successor.
+    DanglingTrafficSpace,
+    /// The traffic space is part of a cycle of successors.
+    TrafficSpaceCycle,
+}
+
+impl TopologyRule {
+    /// Returns the id of the rule in the validation profiles.
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            TopologyRule::PredecessorSuccessorAsymmetric => "predecessor_successor_asymmetric",
+            TopologyRule::SuccessorNotConnected => "successor_not_connected",
+            TopologyRule::DanglingTrafficSpace => "dangling_traffic_space",
+            TopologyRule::TrafficSpaceCycle => "traffic_space_cycle",
+        }
+    }
 }
 
 impl Report {
-    pub fn write(&self, path: impl AsRef<Path>) {
-        let yaml = serde_yaml::to_string(&self).unwrap();
-        fs::write(path, yaml).expect("Unable to write file");
+    /// Returns the number of findings with the severity.
+    pub fn count_findings(&self, severity: Severity) -> usize {
+        self.findings
+            .iter()
+            .filter(|x| x.severity == severity)
+            .count()
+    }
+
+    /// Writes the report to a file in the given format.
+    pub fn write(&self, path: impl AsRef<Path>, format: ReportFormat) -> Result<(), Error> {
+        let file = BufWriter::new(File::create(path)?);
+        self.write_to(file, format)
+    }
+
+    /// Writes the report to a writer in the given format.
+    pub fn write_to(&self, mut writer: impl Write, format: ReportFormat) -> Result<(), Error> {
+        match format {
+            ReportFormat::Yaml => serde_yaml::to_writer(&mut writer, self)?,
+            ReportFormat::Json => serde_json::to_writer_pretty(&mut writer, self)?,
+            ReportFormat::Junit => writer.write_all(report_format::to_junit(self).as_bytes())?,
+            ReportFormat::Html => writer.write_all(report_format::to_html(self).as_bytes())?,
+            ReportFormat::Sarif => {
+                serde_json::to_writer_pretty(&mut writer, &report_format::to_sarif(self))?
+            }
+        }
+        writer.flush()?;
+        Ok(())
     }
 }
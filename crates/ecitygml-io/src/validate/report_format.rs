@@ -0,0 +1,437 @@
+use crate::error::Error;
+use crate::validate::profile::Severity;
+use crate::validate::report::{Finding, Report};
+use itertools::Itertools;
+use quick_xml::escape::escape;
+use serde_json::{Value, json};
+use std::fmt;
+use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const INFORMATION_URI: &str = "https://github.com/envis-space/ecitygml";
+
+/// Output formats of the validation [`Report`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ReportFormat {
+    /// Complete report as YAML
+    #[default]
+    Yaml,
+    /// Complete report as JSON
+    Json,
+    /// Findings as JUnit XML test cases, where findings with severity error are failures
+    Junit,
+    /// Browsable summary of the findings and statistics
+    Html,
+    /// Findings as SARIF 2.1.0 results for code scanning tools
+    Sarif,
+}
+
+impl ReportFormat {
+    /// Returns the file extension conventionally used for the format.
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Yaml => "yaml",
+            ReportFormat::Json => "json",
+            ReportFormat::Junit => "junit.xml",
+            ReportFormat::Html => "html",
+            ReportFormat::Sarif => "sarif",
+        }
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ReportFormat::Yaml => "yaml",
+            ReportFormat::Json => "json",
+            ReportFormat::Junit => "junit",
+            ReportFormat::Html => "html",
+            ReportFormat::Sarif => "sarif",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "yaml" | "yml" => Ok(ReportFormat::Yaml),
+            "json" => Ok(ReportFormat::Json),
+            "junit" | "xml" => Ok(ReportFormat::Junit),
+            "html" => Ok(ReportFormat::Html),
+            "sarif" => Ok(ReportFormat::Sarif),
+            _ => Err(Error::UnknownReportFormat(s.to_string())),
+        }
+    }
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+    }
+}
+
+fn finding_title(finding: &Finding) -> String {
+    let position = match (finding.line, finding.column) {
+        (Some(line), Some(column)) => Some(format!("line {line}, column {column}")),
+        (Some(line), None) => Some(format!("line {line}")),
+        _ => None,
+    };
+    match (&finding.feature_id, position) {
+        (Some(feature_id), _) => format!("{feature_id}: {}", finding.message),
+        (None, Some(position)) => format!("{position}: {}", finding.message),
+        (None, None) => finding.message.clone(),
+    }
+}
+
+/// Renders the findings as JUnit XML with a test suite per rule.
+///
+/// Findings with severity error become failures, while warnings and infos are passed test cases
+/// with their message as output, so that CI pipelines fail only on errors.
+pub(crate) fn to_junit(report: &Report) -> String {
+    let findings_per_rule = report
+        .findings
+        .iter()
+        .into_group_map_by(|x| x.rule_id.as_str());
+    let number_of_errors = report.count_findings(Severity::Error);
+    let number_of_tests = report.findings.len().max(1);
+    let file = report
+        .source
+        .as_deref()
+        .map(|x| format!(" file=\"{}\"", escape(x)))
+        .unwrap_or_default();
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"ecitygml validation ({})\" tests=\"{number_of_tests}\" failures=\"{number_of_errors}\">",
+        escape(&report.profile_name)
+    );
+
+    if report.findings.is_empty() {
+        xml.push_str("  <testsuite name=\"validation\" tests=\"1\" failures=\"0\">\n");
+        let _ = writeln!(
+            xml,
+            "    <testcase classname=\"validation\" name=\"no findings\"{file}/>"
+        );
+        xml.push_str("  </testsuite>\n");
+    }
+
+    for (rule_id, findings) in findings_per_rule.iter().sorted_by_key(|x| x.0) {
+        let number_of_failures = findings
+            .iter()
+            .filter(|x| x.severity == Severity::Error)
+            .count();
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{number_of_failures}\">",
+            escape(*rule_id),
+            findings.len()
+        );
+        for finding in findings {
+            let line = finding
+                .line
+                .map(|x| format!(" line=\"{x}\""))
+                .unwrap_or_default();
+            let _ = writeln!(
+                xml,
+                "    <testcase classname=\"{}\" name=\"{}\"{file}{line}>",
+                escape(*rule_id),
+                escape(finding_title(finding))
+            );
+            let message = escape(&finding.message);
+            if finding.severity == Severity::Error {
+                let _ = writeln!(
+                    xml,
+                    "      <failure type=\"{}\" message=\"{message}\">{message}</failure>",
+                    escape(*rule_id)
+                );
+            } else {
+                let _ = writeln!(
+                    xml,
+                    "      <system-out>{}: {message}</system-out>",
+                    severity_name(finding.severity)
+                );
+            }
+            xml.push_str("    </testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Renders a self-contained HTML page with the statistics and findings.
+pub(crate) fn to_html(report: &Report) -> String {
+    let statistics = &report.statistics;
+    let title = match &report.source {
+        Some(source) => format!("Validation report of {}", escape(source)),
+        None => "Validation report".to_string(),
+    };
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(html, "<title>{title}</title>");
+    html.push_str(
+        "<style>\n\
+         body { font-family: sans-serif; margin: 2em; }\n\
+         table { border-collapse: collapse; margin-bottom: 2em; }\n\
+         th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }\n\
+         th { background: #eee; }\n\
+         .error { color: #b00020; }\n\
+         .warning { color: #b36b00; }\n\
+         .info { color: #00639b; }\n\
+         </style>\n</head>\n<body>\n",
+    );
+    let _ = writeln!(html, "<h1>{title}</h1>");
+    let _ = writeln!(html, "<p>Profile: {}</p>", escape(&report.profile_name));
+
+    html.push_str("<h2>Summary</h2>\n<table>\n");
+    for (name, value) in [
+        ("Errors", statistics.number_of_errors),
+        ("Warnings", statistics.number_of_warnings),
+        ("Infos", statistics.number_of_infos),
+        ("Duplicate gml:ids", statistics.number_of_gml_duplicates),
+        (
+            "Broken object relations",
+            statistics.number_of_broken_object_relations,
+        ),
+        (
+            "Cross-file references",
+            statistics.number_of_cross_file_references,
+        ),
+        (
+            "Structure violations",
+            statistics.number_of_structure_violations,
+        ),
+        ("Geometry errors", statistics.number_of_geometry_errors),
+        ("Semantic issues", statistics.number_of_semantic_issues),
+        ("Topology issues", statistics.number_of_topology_issues),
+    ] {
+        let _ = writeln!(html, "<tr><th>{name}</th><td>{value}</td></tr>");
+    }
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>Findings</h2>\n");
+    if report.findings.is_empty() {
+        html.push_str("<p>No findings.</p>\n");
+    } else {
+        html.push_str(
+            "<table>\n<tr><th>Severity</th><th>Rule</th><th>Feature</th><th>Line</th><th>Message</th></tr>\n",
+        );
+        for finding in report.findings.iter().sorted_by_key(|x| x.severity) {
+            let severity = severity_name(finding.severity);
+            let _ = writeln!(
+                html,
+                "<tr class=\"{severity}\"><td>{severity}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(&finding.rule_id),
+                escape(finding.feature_id.as_deref().unwrap_or_default()),
+                finding.line.map(|x| x.to_string()).unwrap_or_default(),
+                escape(&finding.message)
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("<h2>Elements</h2>\n<table>\n");
+    html.push_str(
+        "<tr><th>Element</th><th>Total</th><th>With gml:id</th><th>Without gml:id</th></tr>\n",
+    );
+    for element in &report.xml_elements {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(&element.element_name),
+            element.number_total,
+            element.number_with_gml_id,
+            element.number_without_gml_id
+        );
+    }
+    html.push_str("</table>\n</body>\n</html>\n");
+    html
+}
+
+/// Renders the findings as a SARIF 2.1.0 log with a single run.
+pub(crate) fn to_sarif(report: &Report) -> Value {
+    let rule_ids: Vec<&str> = report
+        .findings
+        .iter()
+        .map(|x| x.rule_id.as_str())
+        .unique()
+        .sorted()
+        .collect();
+    let rules: Vec<Value> = rule_ids
+        .iter()
+        .map(|x| json!({ "id": x, "name": x }))
+        .collect();
+
+    let results: Vec<Value> = report
+        .findings
+        .iter()
+        .map(|finding| {
+            let level = match finding.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info => "note",
+            };
+            let mut location = serde_json::Map::new();
+            if let Some(source) = &report.source {
+                let mut physical_location =
+                    json!({ "artifactLocation": { "uri": artifact_uri(source) } });
+                if let Some(line) = finding.line {
+                    physical_location["region"] = json!({
+                        "startLine": line,
+                        "startColumn": finding.column.unwrap_or(1),
+                    });
+                }
+                location.insert("physicalLocation".to_string(), physical_location);
+            }
+            if let Some(feature_id) = &finding.feature_id {
+                location.insert(
+                    "logicalLocations".to_string(),
+                    json!([{ "fullyQualifiedName": feature_id, "kind": "object" }]),
+                );
+            }
+
+            let mut result = json!({
+                "ruleId": finding.rule_id,
+                "ruleIndex": rule_ids.iter().position(|x| *x == finding.rule_id),
+                "level": level,
+                "message": { "text": finding.message },
+            });
+            if !location.is_empty() {
+                result["locations"] = json!([location]);
+            }
+            result
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "ecitygml",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": INFORMATION_URI,
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+/// Returns the source as URI reference for SARIF, which is a `file://` URI for absolute paths
+/// and a relative reference otherwise.
+fn artifact_uri(source: &str) -> String {
+    let path = if std::path::MAIN_SEPARATOR == '\\' {
+        source.replace('\\', "/")
+    } else {
+        source.to_string()
+    };
+    let encoded_path: String = path
+        .bytes()
+        .map(|x| {
+            if x.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@/".contains(&x) {
+                char::from(x).to_string()
+            } else {
+                format!("%{x:02X}")
+            }
+        })
+        .collect();
+
+    if !Path::new(source).is_absolute() {
+        encoded_path
+    } else if encoded_path.starts_with('/') {
+        format!("file://{encoded_path}")
+    } else {
+        // absolute Windows paths start with the drive letter
+        format!("file:///{encoded_path}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CitygmlReader;
+
+    const DOCUMENT: &str = r#"<core:CityModel xmlns:core="http://www.opengis.net/citygml/2.0" xmlns:gml="http://www.opengis.net/gml" xmlns:bldg="http://www.opengis.net/citygml/building/2.0">
+  <core:cityObjectMember><bldg:Building gml:id="b&amp;1"><bldg:function>1000</bldg:function></bldg:Building></core:cityObjectMember>
+  <core:cityObjectMember><bldg:Building gml:id="b&amp;1"><bldg:function>1000</bldg:function></bldg:Building></core:cityObjectMember>
+</core:CityModel>"#;
+
+    fn write_report(report: &Report, format: ReportFormat) -> String {
+        let mut buffer = Vec::new();
+        report.write_to(&mut buffer, format).expect("should work");
+        String::from_utf8(buffer).expect("should be valid UTF-8")
+    }
+
+    #[test]
+    fn test_parse_report_format() {
+        assert_eq!("JSON".parse::<ReportFormat>().unwrap(), ReportFormat::Json);
+        assert_eq!(ReportFormat::Junit.file_extension(), "junit.xml");
+        assert!(matches!(
+            "pdf".parse::<ReportFormat>(),
+            Err(Error::UnknownReportFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_artifact_uri() {
+        assert_eq!(artifact_uri("city.gml"), "city.gml");
+        assert_eq!(artifact_uri("tiles/city 1#2.gml"), "tiles/city%201%232.gml");
+        assert_eq!(artifact_uri("tiles/stadt_ü.gml"), "tiles/stadt_%C3%BC.gml");
+        #[cfg(unix)]
+        assert_eq!(
+            artifact_uri("/data/city model.gml"),
+            "file:///data/city%20model.gml"
+        );
+    }
+
+    #[test]
+    fn test_write_report_formats() {
+        let mut report = CitygmlReader::from_str(DOCUMENT)
+            .validate()
+            .expect("should work");
+        report.source = Some("city.gml".to_string());
+        assert_eq!(report.findings.len(), 1);
+
+        let json: Value =
+            serde_json::from_str(&write_report(&report, ReportFormat::Json)).expect("should work");
+        assert_eq!(json["statistics"]["number_of_errors"], 1);
+
+        let junit = write_report(&report, ReportFormat::Junit);
+        assert!(junit.contains("<testsuite name=\"gml_id_duplicate\" tests=\"1\" failures=\"1\">"));
+        assert!(junit.contains("name=\"b&amp;1: gml:id occurs 2 times\""));
+        let mut reader = quick_xml::Reader::from_str(&junit);
+        loop {
+            let event = reader.read_event().expect("should be well-formed");
+            if matches!(event, quick_xml::events::Event::Eof) {
+                break;
+            }
+        }
+
+        let html = write_report(&report, ReportFormat::Html);
+        assert!(html.contains("<td>b&amp;1</td>"));
+
+        let sarif: Value =
+            serde_json::from_str(&write_report(&report, ReportFormat::Sarif)).expect("should work");
+        let result = &sarif["runs"][0]["results"][0];
+        assert_eq!(result["ruleId"], "gml_id_duplicate");
+        assert_eq!(result["level"], "error");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "city.gml"
+        );
+    }
+}
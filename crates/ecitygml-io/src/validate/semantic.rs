@@ -0,0 +1,397 @@
+use crate::validate::geometry::{distance_to_ring, is_point_in_ring};
+use crate::validate::report::{SemanticIssue, SemanticRule};
+use ecitygml_core::model::building::Building;
+use ecitygml_core::model::city_model::CitygmlModel;
+use ecitygml_core::model::core::ThematicSurface;
+use ecitygml_core::operations::{
+    newell_vector, polygon_area, polygon_normal, ring_area, ring_vectors,
+};
+use egml::model::geometry::{MultiSurface, Polygon, Solid};
+use egml::operations::geometry::Geometry;
+use nalgebra::{Vector2, Vector3};
+
+/// Tolerances of the semantic consistency rules, with angles in degrees and distances in the
+/// unit of the coordinate reference system.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SemanticTolerance {
+    /// Angle, by which the normal of a `RoofSurface` polygon may point below the horizon.
+    pub roof_surface_angle: f64,
+    /// Maximum angle between the normal of a `GroundSurface` polygon and the downward direction.
+    pub ground_surface_angle: f64,
+    /// Maximum height of a `GroundSurface` polygon above the lowest point of its building.
+    pub ground_surface_height: f64,
+    /// Maximum angle between the normal of a `WallSurface` polygon and the horizontal plane.
+    pub wall_surface_angle: f64,
+    /// Maximum distance of `DoorSurface` and `WindowSurface` points to their parent wall.
+    pub opening_distance: f64,
+    /// Maximum relative difference between the area of the boundary surfaces and the solid.
+    pub boundary_coverage: f64,
+}
+
+impl Default for SemanticTolerance {
+    fn default() -> Self {
+        Self {
+            roof_surface_angle: 5.0,
+            ground_surface_angle: 10.0,
+            ground_surface_height: 1.0,
+            wall_surface_angle: 10.0,
+            opening_distance: 0.2,
+            boundary_coverage: 0.05,
+        }
+    }
+}
+
+/// Checks the semantic consistency of the boundary surfaces of all buildings in the model.
+pub fn check_semantics(
+    citygml_model: &CitygmlModel,
+    tolerance: &SemanticTolerance,
+) -> Vec<SemanticIssue> {
+    let mut issues = Vec::new();
+    for building in &citygml_model.building {
+        check_building(building, tolerance, &mut issues);
+    }
+    issues
+}
+
+fn check_building(
+    building: &Building,
+    tolerance: &SemanticTolerance,
+    issues: &mut Vec<SemanticIssue>,
+) {
+    let building_id = building
+        .occupied_space
+        .space
+        .city_object
+        .abstract_gml
+        .id
+        .to_string();
+
+    for roof_surface in &building.roof_surface {
+        let surface = &roof_surface.thematic_surface;
+        if let Some(angle) = polygons(surface)
+            .filter_map(polygon_normal)
+            .map(|x| angle_to(&x, &Vector3::z()) - 90.0)
+            .reduce(f64::max)
+            .filter(|x| *x > tolerance.roof_surface_angle)
+        {
+            issues.push(surface_issue(
+                SemanticRule::RoofSurfaceNormalUpward,
+                surface,
+                &building_id,
+                format!("roof polygon normal points {angle:.1}° below the horizon"),
+            ));
+        }
+    }
+
+    let min_z = building_min_z(building);
+    for ground_surface in &building.ground_surface {
+        let surface = &ground_surface.thematic_surface;
+        if let Some(angle) = polygons(surface)
+            .filter_map(polygon_normal)
+            .map(|x| angle_to(&x, &-Vector3::z()))
+            .reduce(f64::max)
+            .filter(|x| *x > tolerance.ground_surface_angle)
+        {
+            issues.push(surface_issue(
+                SemanticRule::GroundSurfaceNormalDownward,
+                surface,
+                &building_id,
+                format!("ground polygon normal deviates {angle:.1}° from the downward direction"),
+            ));
+        }
+
+        if let Some(min_z) = min_z
+            && let Some(height) = polygons(surface)
+                .map(|x| mean_z(x) - min_z)
+                .reduce(f64::max)
+                .filter(|x| *x > tolerance.ground_surface_height)
+        {
+            issues.push(surface_issue(
+                SemanticRule::GroundSurfaceAtMinimumHeight,
+                surface,
+                &building_id,
+                format!("ground polygon lies {height:.3} above the lowest point of the building"),
+            ));
+        }
+    }
+
+    for wall_surface in &building.wall_surface {
+        let surface = &wall_surface.thematic_surface;
+        if let Some(angle) = polygons(surface)
+            .filter_map(polygon_normal)
+            .map(|x| (angle_to(&x, &Vector3::z()) - 90.0).abs())
+            .reduce(f64::max)
+            .filter(|x| *x > tolerance.wall_surface_angle)
+        {
+            issues.push(surface_issue(
+                SemanticRule::WallSurfaceVertical,
+                surface,
+                &building_id,
+                format!("wall polygon is tilted {angle:.1}° from the vertical"),
+            ));
+        }
+
+        let wall_polygons: Vec<&Polygon> = polygons(surface).collect();
+        let openings = wall_surface
+            .door_surface
+            .iter()
+            .map(|x| &x.thematic_surface)
+            .chain(
+                wall_surface
+                    .window_surface
+                    .iter()
+                    .map(|x| &x.thematic_surface),
+            );
+        for opening in openings {
+            let distance = polygons(opening)
+                .flat_map(|x| x.exterior.points())
+                .map(|x| distance_to_polygons(&Vector3::from(x), &wall_polygons))
+                .reduce(f64::max);
+            if let Some(distance) = distance.filter(|x| *x > tolerance.opening_distance) {
+                issues.push(surface_issue(
+                    SemanticRule::OpeningWithinWallSurface,
+                    opening,
+                    &surface.city_object.abstract_gml.id.to_string(),
+                    format!("opening lies {distance:.3} outside its wall surface"),
+                ));
+            }
+        }
+    }
+
+    let space = &building.occupied_space.space;
+    let boundary_surfaces = boundary_surfaces(building);
+    for (lod, solid) in [(2, &space.lod2_solid), (3, &space.lod3_solid)] {
+        let Some(solid) = solid else {
+            continue;
+        };
+        let boundary_polygons: Vec<&Polygon> = boundary_surfaces
+            .iter()
+            .filter_map(|x| match lod {
+                2 => x.lod2_multi_surface.as_ref(),
+                _ => x.lod3_multi_surface.as_ref(),
+            })
+            .flat_map(MultiSurface::surface_member)
+            .collect();
+
+        let solid_area = solid_area(solid, &boundary_polygons);
+        let boundary_area: f64 = boundary_polygons.iter().map(|x| polygon_area(x)).sum();
+        if solid_area <= 0.0 {
+            continue;
+        }
+        let difference = (solid_area - boundary_area).abs() / solid_area;
+        if difference > tolerance.boundary_coverage {
+            issues.push(SemanticIssue {
+                feature_id: building_id.clone(),
+                parent_id: None,
+                rule: SemanticRule::BoundarySurfacesCoverSolid,
+                message: format!(
+                    "boundary surfaces cover {:.1}% of the lod{lod}Solid area",
+                    boundary_area / solid_area * 100.0
+                ),
+            });
+        }
+    }
+}
+
+fn surface_issue(
+    rule: SemanticRule,
+    surface: &ThematicSurface,
+    parent_id: &str,
+    message: String,
+) -> SemanticIssue {
+    SemanticIssue {
+        feature_id: surface.city_object.abstract_gml.id.to_string(),
+        parent_id: Some(parent_id.to_string()),
+        rule,
+        message,
+    }
+}
+
+fn boundary_surfaces(building: &Building) -> Vec<&ThematicSurface> {
+    let mut surfaces: Vec<&ThematicSurface> = Vec::new();
+    surfaces.extend(building.wall_surface.iter().map(|x| &x.thematic_surface));
+    surfaces.extend(building.roof_surface.iter().map(|x| &x.thematic_surface));
+    surfaces.extend(building.ground_surface.iter().map(|x| &x.thematic_surface));
+    surfaces.extend(building.closure_surface.iter().map(|x| &x.thematic_surface));
+    surfaces.extend(
+        building
+            .outer_ceiling_surface
+            .iter()
+            .map(|x| &x.thematic_surface),
+    );
+    surfaces.extend(
+        building
+            .outer_floor_surface
+            .iter()
+            .map(|x| &x.thematic_surface),
+    );
+    surfaces
+}
+
+pub(crate) fn polygons(surface: &ThematicSurface) -> impl Iterator<Item = &Polygon> {
+    [
+        &surface.lod0_multi_surface,
+        &surface.lod1_multi_surface,
+        &surface.lod2_multi_surface,
+        &surface.lod3_multi_surface,
+    ]
+    .into_iter()
+    .flatten()
+    .flat_map(MultiSurface::surface_member)
+}
+
+/// Sums the area of the solid's surfaces, where referenced surfaces are resolved among the
+/// boundary polygons.
+fn solid_area(solid: &Solid, boundary_polygons: &[&Polygon]) -> f64 {
+    solid
+        .members()
+        .iter()
+        .map(|member| match &member.linear_ring {
+            Some(linear_ring) => ring_area(linear_ring),
+            None => boundary_polygons
+                .iter()
+                .find(|x| x.abstract_gml.id.to_string() == member.href.trim_start_matches('#'))
+                .map_or(0.0, |x| polygon_area(x)),
+        })
+        .sum()
+}
+
+fn mean_z(polygon: &Polygon) -> f64 {
+    let points = polygon.exterior.points();
+    points.iter().map(|x| x.z()).sum::<f64>() / points.len() as f64
+}
+
+fn building_min_z(building: &Building) -> Option<f64> {
+    boundary_surfaces(building)
+        .into_iter()
+        .flat_map(polygons)
+        .flat_map(|x| x.exterior.points())
+        .map(|x| x.z())
+        .reduce(f64::min)
+}
+
+/// Returns the angle between both vectors in degrees.
+fn angle_to(a: &Vector3<f64>, b: &Vector3<f64>) -> f64 {
+    a.angle(b).to_degrees()
+}
+
+/// Returns the distance of the point to the closest polygon, where points above the interior of
+/// a polygon have the distance to its plane.
+pub(crate) fn distance_to_polygons(point: &Vector3<f64>, polygons: &[&Polygon]) -> f64 {
+    polygons
+        .iter()
+        .filter_map(|polygon| {
+            let ring = ring_vectors(&polygon.exterior);
+            let normal = newell_vector(&ring).try_normalize(f64::EPSILON)?;
+            let plane_distance = (point - ring[0]).dot(&normal).abs();
+
+            // distance within the plane to the polygon outline, if the point is outside
+            let projected = point - normal * (point - ring[0]).dot(&normal);
+            let u = (ring[1] - ring[0]).normalize();
+            let v = normal.cross(&u);
+            let to_plane =
+                |x: &Vector3<f64>| Vector2::new((x - ring[0]).dot(&u), (x - ring[0]).dot(&v));
+            let outline: Vec<Vector2<f64>> = ring.iter().map(to_plane).collect();
+            let in_plane = to_plane(&projected);
+            let outline_distance = if is_point_in_ring(&in_plane, &outline) {
+                0.0
+            } else {
+                distance_to_ring(&in_plane, &outline)
+            };
+
+            Some(plane_distance.hypot(outline_distance))
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CitygmlReader;
+
+    fn multi_surface(points: &[[f64; 3]]) -> String {
+        let mut points = points.to_vec();
+        points.push(points[0]);
+        let pos_list: Vec<String> = points.iter().flatten().map(|x| x.to_string()).collect();
+        format!(
+            "<lod2MultiSurface><gml:MultiSurface><gml:surfaceMember><gml:Polygon><gml:exterior><gml:LinearRing><gml:posList>{}</gml:posList></gml:LinearRing></gml:exterior></gml:Polygon></gml:surfaceMember></gml:MultiSurface></lod2MultiSurface>",
+            pos_list.join(" ")
+        )
+    }
+
+    fn boundary(element_name: &str, id: &str, points: &[[f64; 3]], content: &str) -> String {
+        format!(
+            "<boundary><{element_name} gml:id=\"{id}\">{}{content}</{element_name}></boundary>",
+            multi_surface(points)
+        )
+    }
+
+    #[test]
+    fn test_check_semantics() {
+        let window = format!(
+            "<con:fillingSurface><con:WindowSurface gml:id=\"window_1\">{}</con:WindowSurface></con:fillingSurface>",
+            multi_surface(&[
+                [0.2, 3.0, 0.2],
+                [0.8, 3.0, 0.2],
+                [0.8, 3.0, 0.8],
+                [0.2, 3.0, 0.8]
+            ])
+        );
+        let boundaries = [
+            boundary(
+                "con:GroundSurface",
+                "ground_1",
+                &[[0., 0., 0.], [0., 1., 0.], [1., 1., 0.], [1., 0., 0.]],
+                "",
+            ),
+            // the roof polygon is oriented downward
+            boundary(
+                "con:RoofSurface",
+                "roof_1",
+                &[[0., 0., 1.], [0., 1., 1.], [1., 1., 1.], [1., 0., 1.]],
+                "",
+            ),
+            boundary(
+                "con:WallSurface",
+                "wall_front",
+                &[[0., 0., 0.], [1., 0., 0.], [1., 0., 1.], [0., 0., 1.]],
+                &window,
+            ),
+            boundary(
+                "con:WallSurface",
+                "wall_right",
+                &[[1., 0., 0.], [1., 1., 0.], [1., 1., 1.], [1., 0., 1.]],
+                "",
+            ),
+            boundary(
+                "con:WallSurface",
+                "wall_back",
+                &[[1., 1., 0.], [0., 1., 0.], [0., 1., 1.], [1., 1., 1.]],
+                "",
+            ),
+        ]
+        .concat();
+        let xml_document = format!(
+            "<CityModel xmlns=\"http://www.opengis.net/citygml/3.0\" xmlns:gml=\"http://www.opengis.net/gml/3.2\" xmlns:bldg=\"http://www.opengis.net/citygml/building/3.0\" xmlns:con=\"http://www.opengis.net/citygml/construction/3.0\">\
+            <cityObjectMember><bldg:Building gml:id=\"building_1\">{boundaries}</bldg:Building></cityObjectMember></CityModel>"
+        );
+        let citygml_model = CitygmlReader::from_str(&xml_document)
+            .finish()
+            .expect("should work");
+
+        let issues = check_semantics(&citygml_model, &SemanticTolerance::default());
+        let rules: Vec<(&str, SemanticRule)> = issues
+            .iter()
+            .map(|x| (x.feature_id.as_str(), x.rule))
+            .collect();
+
+        assert_eq!(
+            rules,
+            vec![
+                ("roof_1", SemanticRule::RoofSurfaceNormalUpward),
+                ("window_1", SemanticRule::OpeningWithinWallSurface),
+            ]
+        );
+        assert_eq!(issues[1].parent_id.as_deref(), Some("wall_front"));
+    }
+}
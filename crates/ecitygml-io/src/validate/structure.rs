@@ -0,0 +1,794 @@
+use crate::parser::attribute::parse_date;
+use crate::validate::report::StructureViolation;
+use chrono::{DateTime, NaiveDateTime};
+use quick_xml::NsReader;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::ResolveResult;
+
+const UNBOUNDED: usize = usize::MAX;
+
+/// Namespaces covered by the structural rules and the prefixes used for them in the rules.
+///
+/// CityGML 2.0 is based on GML 3.1.1 and CityGML 3.0 on GML 3.2, whose structure of the
+/// checked elements is identical.
+const RULE_NAMESPACES: [(&str, &str); 6] = [
+    ("http://www.opengis.net/gml/3.2", "gml"),
+    ("http://www.opengis.net/gml", "gml"),
+    ("http://www.opengis.net/citygml/3.0", "core"),
+    ("http://www.opengis.net/citygml/2.0", "core"),
+    ("http://www.opengis.net/citygml/generics/3.0", "gen"),
+    ("http://www.opengis.net/citygml/generics/2.0", "gen"),
+];
+
+/// Properties inherited from `gml:AbstractGMLType`, which are allowed in every GML object.
+const GML_STANDARD_PROPERTIES: [&str; 5] = [
+    "gml:metaDataProperty",
+    "gml:description",
+    "gml:descriptionReference",
+    "gml:identifier",
+    "gml:name",
+];
+
+#[derive(Debug, Clone, Copy)]
+struct Particle {
+    name: &'static str,
+    min_occurs: usize,
+    max_occurs: usize,
+}
+
+const fn particle(name: &'static str, min_occurs: usize, max_occurs: usize) -> Particle {
+    Particle {
+        name,
+        min_occurs,
+        max_occurs,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SimpleType {
+    Integer,
+    Double,
+    DoubleList,
+    Date,
+    DateTime,
+}
+
+impl SimpleType {
+    fn accepts(&self, value: &str) -> bool {
+        match self {
+            SimpleType::Integer => value.parse::<i64>().is_ok(),
+            SimpleType::Double => value.parse::<f64>().is_ok(),
+            SimpleType::DoubleList => value.split_whitespace().all(|x| x.parse::<f64>().is_ok()),
+            SimpleType::Date => parse_date(value).is_ok(),
+            SimpleType::DateTime => {
+                DateTime::parse_from_rfc3339(value).is_ok()
+                    || NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f").is_ok()
+            }
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            SimpleType::Integer => "xs:integer",
+            SimpleType::Double => "xs:double",
+            SimpleType::DoubleList => "gml:doubleList",
+            SimpleType::Date => "xs:date",
+            SimpleType::DateTime => "xs:dateTime",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ContentModel {
+    /// Child elements with individual cardinalities, other child elements are not allowed.
+    Sequence(&'static [Particle]),
+    /// Between `min_occurs` and `max_occurs` child elements out of `names`, where no names
+    /// allow any element.
+    Choice {
+        names: &'static [&'static str],
+        min_occurs: usize,
+        max_occurs: usize,
+    },
+    /// Text content of a simple type without child elements.
+    Simple(SimpleType),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ElementRule {
+    name: &'static str,
+    /// Restricts the rule to elements contained in this parent element.
+    parent: Option<&'static str>,
+    content_model: ContentModel,
+    /// Minimum number of coordinate tuples of geometries like `gml:LinearRing`.
+    min_positions: usize,
+}
+
+const fn rule(name: &'static str, content_model: ContentModel) -> ElementRule {
+    ElementRule {
+        name,
+        parent: None,
+        content_model,
+        min_positions: 0,
+    }
+}
+
+const fn rule_in_parent(
+    name: &'static str,
+    parent: &'static str,
+    content_model: ContentModel,
+) -> ElementRule {
+    ElementRule {
+        name,
+        parent: Some(parent),
+        content_model,
+        min_positions: 0,
+    }
+}
+
+const RING_CHOICE: ContentModel = ContentModel::Choice {
+    names: &[
+        "gml:LinearRing",
+        "gml:Ring",
+        "gml:Shell",
+        "gml:CompositeSurface",
+    ],
+    min_occurs: 1,
+    max_occurs: 1,
+};
+const SURFACE_PATCH_CHOICE: ContentModel = ContentModel::Choice {
+    names: &[
+        "gml:Polygon",
+        "gml:CompositeSurface",
+        "gml:OrientableSurface",
+        "gml:Surface",
+        "gml:TriangulatedSurface",
+        "gml:Tin",
+    ],
+    min_occurs: 0,
+    max_occurs: 1,
+};
+const CURVE_CHOICE: ContentModel = ContentModel::Choice {
+    names: &[
+        "gml:LineString",
+        "gml:Curve",
+        "gml:CompositeCurve",
+        "gml:OrientableCurve",
+    ],
+    min_occurs: 0,
+    max_occurs: 1,
+};
+const POSITIONS: &[Particle] = &[
+    particle("gml:pos", 0, UNBOUNDED),
+    particle("gml:pointProperty", 0, UNBOUNDED),
+    particle("gml:posList", 0, 1),
+    particle("gml:coordinates", 0, 1),
+];
+const VOLUME_BOUNDARY: &[Particle] = &[
+    particle("gml:exterior", 0, 1),
+    particle("gml:interior", 0, UNBOUNDED),
+];
+const GENERIC_ATTRIBUTE_V3: &[Particle] = &[
+    particle("gen:name", 1, 1),
+    particle("gen:value", 1, 1),
+    particle("gen:uom", 0, 1),
+];
+const GENERIC_ATTRIBUTE_V2: &[Particle] = &[particle("gen:value", 1, 1)];
+
+/// Structural rules derived from the CityGML 2.0/3.0 and GML 3.1/3.2 schemas.
+///
+/// The rules are a hand-written subset and cover the geometry structures, the temporal properties of city objects and the
+/// values of generic attributes. Elements without a rule are not checked.
+static ELEMENT_RULES: &[ElementRule] = &[
+    rule("gml:Polygon", ContentModel::Sequence(VOLUME_BOUNDARY)),
+    rule("gml:Solid", ContentModel::Sequence(VOLUME_BOUNDARY)),
+    rule("gml:exterior", RING_CHOICE),
+    rule("gml:interior", RING_CHOICE),
+    ElementRule {
+        min_positions: 4,
+        ..rule("gml:LinearRing", ContentModel::Sequence(POSITIONS))
+    },
+    ElementRule {
+        min_positions: 2,
+        ..rule("gml:LineString", ContentModel::Sequence(POSITIONS))
+    },
+    rule(
+        "gml:Shell",
+        ContentModel::Sequence(&[particle("gml:surfaceMember", 1, UNBOUNDED)]),
+    ),
+    rule(
+        "gml:CompositeSurface",
+        ContentModel::Sequence(&[particle("gml:surfaceMember", 1, UNBOUNDED)]),
+    ),
+    rule(
+        "gml:MultiSurface",
+        ContentModel::Sequence(&[
+            particle("gml:surfaceMember", 0, UNBOUNDED),
+            particle("gml:surfaceMembers", 0, 1),
+        ]),
+    ),
+    rule(
+        "gml:MultiCurve",
+        ContentModel::Sequence(&[
+            particle("gml:curveMember", 0, UNBOUNDED),
+            particle("gml:curveMembers", 0, 1),
+        ]),
+    ),
+    rule(
+        "gml:MultiPoint",
+        ContentModel::Sequence(&[
+            particle("gml:pointMember", 0, UNBOUNDED),
+            particle("gml:pointMembers", 0, 1),
+        ]),
+    ),
+    rule(
+        "gml:OrientableSurface",
+        ContentModel::Sequence(&[particle("gml:baseSurface", 1, 1)]),
+    ),
+    rule(
+        "gml:Point",
+        ContentModel::Sequence(&[particle("gml:pos", 0, 1), particle("gml:coordinates", 0, 1)]),
+    ),
+    rule(
+        "gml:Envelope",
+        ContentModel::Sequence(&[
+            particle("gml:lowerCorner", 0, 1),
+            particle("gml:upperCorner", 0, 1),
+            particle("gml:pos", 0, 2),
+        ]),
+    ),
+    rule("gml:surfaceMember", SURFACE_PATCH_CHOICE),
+    rule("gml:baseSurface", SURFACE_PATCH_CHOICE),
+    rule("gml:curveMember", CURVE_CHOICE),
+    rule(
+        "gml:boundedBy",
+        ContentModel::Choice {
+            names: &["gml:Envelope", "gml:Null"],
+            min_occurs: 0,
+            max_occurs: 1,
+        },
+    ),
+    rule("gml:pos", ContentModel::Simple(SimpleType::DoubleList)),
+    rule("gml:posList", ContentModel::Simple(SimpleType::DoubleList)),
+    rule(
+        "gml:lowerCorner",
+        ContentModel::Simple(SimpleType::DoubleList),
+    ),
+    rule(
+        "gml:upperCorner",
+        ContentModel::Simple(SimpleType::DoubleList),
+    ),
+    rule(
+        "core:cityObjectMember",
+        ContentModel::Choice {
+            names: &[],
+            min_occurs: 0,
+            max_occurs: 1,
+        },
+    ),
+    rule(
+        "core:creationDate",
+        ContentModel::Simple(SimpleType::DateTime),
+    ),
+    rule(
+        "core:terminationDate",
+        ContentModel::Simple(SimpleType::DateTime),
+    ),
+    rule("core:validFrom", ContentModel::Simple(SimpleType::DateTime)),
+    rule("core:validTo", ContentModel::Simple(SimpleType::DateTime)),
+    rule(
+        "gen:IntAttribute",
+        ContentModel::Sequence(GENERIC_ATTRIBUTE_V3),
+    ),
+    rule(
+        "gen:DoubleAttribute",
+        ContentModel::Sequence(GENERIC_ATTRIBUTE_V3),
+    ),
+    rule(
+        "gen:DateAttribute",
+        ContentModel::Sequence(GENERIC_ATTRIBUTE_V3),
+    ),
+    rule(
+        "gen:MeasureAttribute",
+        ContentModel::Sequence(GENERIC_ATTRIBUTE_V3),
+    ),
+    rule(
+        "gen:intAttribute",
+        ContentModel::Sequence(GENERIC_ATTRIBUTE_V2),
+    ),
+    rule(
+        "gen:doubleAttribute",
+        ContentModel::Sequence(GENERIC_ATTRIBUTE_V2),
+    ),
+    rule(
+        "gen:dateAttribute",
+        ContentModel::Sequence(GENERIC_ATTRIBUTE_V2),
+    ),
+    rule(
+        "gen:measureAttribute",
+        ContentModel::Sequence(GENERIC_ATTRIBUTE_V2),
+    ),
+    rule_in_parent(
+        "gen:value",
+        "gen:IntAttribute",
+        ContentModel::Simple(SimpleType::Integer),
+    ),
+    rule_in_parent(
+        "gen:value",
+        "gen:intAttribute",
+        ContentModel::Simple(SimpleType::Integer),
+    ),
+    rule_in_parent(
+        "gen:value",
+        "gen:DoubleAttribute",
+        ContentModel::Simple(SimpleType::Double),
+    ),
+    rule_in_parent(
+        "gen:value",
+        "gen:doubleAttribute",
+        ContentModel::Simple(SimpleType::Double),
+    ),
+    rule_in_parent(
+        "gen:value",
+        "gen:MeasureAttribute",
+        ContentModel::Simple(SimpleType::Double),
+    ),
+    rule_in_parent(
+        "gen:value",
+        "gen:measureAttribute",
+        ContentModel::Simple(SimpleType::Double),
+    ),
+    rule_in_parent(
+        "gen:value",
+        "gen:DateAttribute",
+        ContentModel::Simple(SimpleType::Date),
+    ),
+    rule_in_parent(
+        "gen:value",
+        "gen:dateAttribute",
+        ContentModel::Simple(SimpleType::Date),
+    ),
+];
+
+fn find_element_rule(name: &str, parent: Option<&str>) -> Option<&'static ElementRule> {
+    ELEMENT_RULES
+        .iter()
+        .find(|x| x.name == name && (x.parent.is_none() || x.parent == parent))
+}
+
+/// Element being checked, whose end tag has not been read yet.
+#[derive(Debug)]
+struct OpenElement {
+    /// Name as written in the document.
+    element_name: String,
+    /// Name with the prefix of the bundled rules, if the namespace is covered by the rules.
+    rule_name: Option<String>,
+    rule: Option<&'static ElementRule>,
+    offset: usize,
+    srs_dimension: Option<usize>,
+    child_names: Vec<Option<String>>,
+    text: String,
+}
+
+/// Converts byte offsets into line and column numbers, which both start at 1.
+#[derive(Debug)]
+struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    fn new(text: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { line_starts }
+    }
+
+    fn line_and_column(&self, offset: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|x| *x <= offset);
+        let column = offset - self.line_starts[line - 1] + 1;
+        (line, column)
+    }
+}
+
+struct StructureChecker<'a> {
+    xml_document: &'a str,
+    line_index: LineIndex,
+    violations: Vec<StructureViolation>,
+}
+
+impl StructureChecker<'_> {
+    fn report(&mut self, offset: usize, element_name: &str, message: String) {
+        let (line, column) = self.line_index.line_and_column(offset);
+        self.violations.push(StructureViolation {
+            line,
+            column,
+            element_name: element_name.to_string(),
+            message,
+        });
+    }
+
+    fn open_element(
+        &mut self,
+        reader: &NsReader<&[u8]>,
+        prefix: Option<&'static str>,
+        start: &BytesStart,
+        offset: usize,
+        parent: Option<&mut OpenElement>,
+    ) -> OpenElement {
+        let element_name = String::from_utf8_lossy(start.name().as_ref()).to_string();
+        let rule_name = prefix.map(|x| {
+            format!(
+                "{x}:{}",
+                String::from_utf8_lossy(start.local_name().as_ref())
+            )
+        });
+        let parent_rule_name = parent.as_ref().and_then(|x| x.rule_name.clone());
+        let mut srs_dimension = parent.as_ref().and_then(|x| x.srs_dimension);
+
+        match parent {
+            Some(parent) => parent.child_names.push(rule_name.clone()),
+            None if rule_name.as_deref() != Some("core:CityModel") => {
+                self.report(
+                    offset,
+                    &element_name,
+                    "root element must be `CityModel`".to_string(),
+                );
+            }
+            None => {}
+        }
+
+        for attribute in start.attributes().flatten() {
+            let value = String::from_utf8_lossy(&attribute.value);
+            let (attribute_namespace, attribute_name) = reader.resolve_attribute(attribute.key);
+
+            match attribute_name.as_ref() {
+                b"id"
+                    if rule_name_prefix(&attribute_namespace) == Some("gml")
+                        && !is_ncname(&value) =>
+                {
+                    self.report(
+                        offset,
+                        &element_name,
+                        format!("gml:id `{value}` is not a valid xs:ID"),
+                    );
+                }
+                b"srsDimension" => match value.parse::<usize>() {
+                    Ok(x) if x > 0 => srs_dimension = Some(x),
+                    _ => self.report(
+                        offset,
+                        &element_name,
+                        format!("srsDimension `{value}` is not a positive integer"),
+                    ),
+                },
+                _ => {}
+            }
+        }
+
+        let rule = rule_name
+            .as_deref()
+            .and_then(|x| find_element_rule(x, parent_rule_name.as_deref()));
+
+        OpenElement {
+            element_name,
+            rule_name,
+            rule,
+            offset,
+            srs_dimension,
+            child_names: Vec::new(),
+            text: String::new(),
+        }
+    }
+
+    fn close_element(&mut self, element: OpenElement) {
+        let Some(rule) = element.rule else {
+            return;
+        };
+        let count = |name: &str| {
+            element
+                .child_names
+                .iter()
+                .filter(|x| x.as_deref() == Some(name))
+                .count()
+        };
+
+        match rule.content_model {
+            ContentModel::Sequence(particles) => {
+                for particle in particles {
+                    let occurrences = count(particle.name);
+                    if occurrences < particle.min_occurs {
+                        self.report(
+                            element.offset,
+                            &element.element_name,
+                            format!(
+                                "`{}` is expected at least {} times, but occurs {} times",
+                                particle.name, particle.min_occurs, occurrences
+                            ),
+                        );
+                    }
+                    if occurrences > particle.max_occurs {
+                        self.report(
+                            element.offset,
+                            &element.element_name,
+                            format!(
+                                "`{}` is expected at most {} times, but occurs {} times",
+                                particle.name, particle.max_occurs, occurrences
+                            ),
+                        );
+                    }
+                }
+
+                let mut unexpected_children: Vec<String> = element
+                    .child_names
+                    .iter()
+                    .filter(|x| {
+                        !x.as_deref().is_some_and(|name| {
+                            particles.iter().any(|p| p.name == name)
+                                || GML_STANDARD_PROPERTIES.contains(&name)
+                        })
+                    })
+                    .map(|x| x.clone().unwrap_or_else(|| "foreign element".to_string()))
+                    .collect();
+                unexpected_children.dedup();
+                for child_name in unexpected_children {
+                    self.report(
+                        element.offset,
+                        &element.element_name,
+                        format!("`{child_name}` is not allowed as child element"),
+                    );
+                }
+            }
+            ContentModel::Choice {
+                names,
+                min_occurs,
+                max_occurs,
+            } => {
+                let occurrences = element.child_names.len();
+                if occurrences < min_occurs || occurrences > max_occurs {
+                    self.report(
+                        element.offset,
+                        &element.element_name,
+                        format!(
+                            "{min_occurs} to {max_occurs} child elements are expected, but {occurrences} occur"
+                        ),
+                    );
+                }
+                if !names.is_empty() {
+                    for child_name in &element.child_names {
+                        if !child_name.as_deref().is_some_and(|x| names.contains(&x)) {
+                            self.report(
+                                element.offset,
+                                &element.element_name,
+                                format!(
+                                    "`{}` is not allowed as child element, expected one of {}",
+                                    child_name.as_deref().unwrap_or("foreign element"),
+                                    names.join(", ")
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+            ContentModel::Simple(simple_type) => {
+                if !element.child_names.is_empty() {
+                    self.report(
+                        element.offset,
+                        &element.element_name,
+                        "child elements are not allowed in simple content".to_string(),
+                    );
+                }
+                let value = element.text.trim();
+                if !simple_type.accepts(value) {
+                    self.report(
+                        element.offset,
+                        &element.element_name,
+                        format!("`{value}` is not a valid {}", simple_type.type_name()),
+                    );
+                } else if simple_type == SimpleType::DoubleList
+                    && let Some(srs_dimension) = element.srs_dimension
+                {
+                    let number_of_values = value.split_whitespace().count();
+                    if !number_of_values.is_multiple_of(srs_dimension) {
+                        self.report(
+                            element.offset,
+                            &element.element_name,
+                            format!(
+                                "number of coordinates {number_of_values} is not a multiple of srsDimension {srs_dimension}"
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
+        if rule.min_positions > 0 {
+            let positions = count("gml:pos") + count("gml:pointProperty");
+            let has_position_list = count("gml:posList") + count("gml:coordinates") > 0;
+            if !has_position_list && positions < rule.min_positions {
+                self.report(
+                    element.offset,
+                    &element.element_name,
+                    format!(
+                        "at least {} positions or a `gml:posList` are expected",
+                        rule.min_positions
+                    ),
+                );
+            }
+        }
+    }
+
+    fn check(mut self) -> Vec<StructureViolation> {
+        let mut reader = NsReader::from_str(self.xml_document);
+        reader.config_mut().trim_text(true);
+        let mut open_elements: Vec<OpenElement> = Vec::new();
+
+        loop {
+            let event_start = reader.buffer_position() as usize;
+            let (prefix, event) = match reader.read_resolved_event() {
+                Ok((resolve_result, event)) => (rule_name_prefix(&resolve_result), event),
+                Err(e) => {
+                    let offset = reader.error_position() as usize;
+                    let element_name = open_elements
+                        .last()
+                        .map(|x| x.element_name.clone())
+                        .unwrap_or_default();
+                    self.report(
+                        offset,
+                        &element_name,
+                        format!("document is not well-formed: {e}"),
+                    );
+                    break;
+                }
+            };
+            // skip whitespace preceding the markup, which is trimmed by the reader
+            let offset = self.xml_document[event_start..]
+                .find('<')
+                .map_or(event_start, |x| event_start + x);
+
+            match event {
+                Event::Start(e) => {
+                    let element =
+                        self.open_element(&reader, prefix, &e, offset, open_elements.last_mut());
+                    open_elements.push(element);
+                }
+                Event::Empty(e) => {
+                    let element =
+                        self.open_element(&reader, prefix, &e, offset, open_elements.last_mut());
+                    self.close_element(element);
+                }
+                Event::Text(e) => {
+                    if let Some(element) = open_elements.last_mut() {
+                        element.text.push_str(&e.decode().unwrap_or_default());
+                    }
+                }
+                Event::CData(e) => {
+                    if let Some(element) = open_elements.last_mut() {
+                        element.text.push_str(&e.decode().unwrap_or_default());
+                    }
+                }
+                Event::End(_) => {
+                    if let Some(element) = open_elements.pop() {
+                        self.close_element(element);
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        self.violations.sort_by_key(|x| (x.line, x.column));
+        self.violations
+    }
+}
+
+/// Checks the well-formedness of the document and the structure of the elements covered by
+/// [`ELEMENT_RULES`].
+///
+/// Elements of other modules, such as buildings or transportation objects, are not checked,
+/// since the document is not validated against the CityGML and GML XSDs.
+pub(crate) fn check_structure(xml_document: &str) -> Vec<StructureViolation> {
+    StructureChecker {
+        xml_document,
+        line_index: LineIndex::new(xml_document),
+        violations: Vec::new(),
+    }
+    .check()
+}
+
+pub(crate) fn rule_name_prefix(resolve_result: &ResolveResult) -> Option<&'static str> {
+    match resolve_result {
+        ResolveResult::Bound(namespace) => RULE_NAMESPACES
+            .iter()
+            .find(|(uri, _)| uri.as_bytes() == namespace.as_ref())
+            .map(|(_, prefix)| *prefix),
+        _ => None,
+    }
+}
+
+/// Returns true, if the value is an XML non-colonized name as required for `xs:ID`.
+fn is_ncname(value: &str) -> bool {
+    let mut chars = value.chars();
+    chars.next().is_some_and(|x| x.is_alphabetic() || x == '_')
+        && chars.all(|x| x.is_alphanumeric() || matches!(x, '_' | '-' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_structure_reports_violations_with_line_numbers() {
+        let xml_document = r#"<?xml version="1.0" encoding="UTF-8"?>
+<core:CityModel xmlns:core="http://www.opengis.net/citygml/3.0" xmlns:gml="http://www.opengis.net/gml/3.2" xmlns:gen="http://www.opengis.net/citygml/generics/3.0">
+  <core:cityObjectMember>
+    <gen:GenericOccupiedSpace gml:id="1st_object">
+      <core:creationDate>yesterday</core:creationDate>
+      <gml:Polygon>
+        <gml:exterior>
+          <gml:LinearRing>
+            <gml:posList srsDimension="3">0 0 0 1 0 0 1 1</gml:posList>
+          </gml:LinearRing>
+        </gml:exterior>
+        <gml:exterior/>
+      </gml:Polygon>
+    </gen:GenericOccupiedSpace>
+  </core:cityObjectMember>
+</core:CityModel>"#;
+
+        let violations = check_structure(xml_document);
+        let lines: Vec<usize> = violations.iter().map(|x| x.line).collect();
+
+        // invalid gml:id
+        assert!(lines.contains(&4));
+        // invalid xs:dateTime
+        assert!(lines.contains(&5));
+        // two gml:exterior elements
+        assert!(lines.contains(&6));
+        // coordinates not matching the srsDimension
+        assert!(lines.contains(&9));
+        // empty gml:exterior
+        assert!(lines.contains(&12));
+        assert_eq!(violations.len(), 5);
+        assert_eq!(violations[0].column, 5);
+    }
+
+    #[test]
+    fn test_check_structure_accepts_valid_document() {
+        let xml_document = r#"<CityModel xmlns="http://www.opengis.net/citygml/2.0" xmlns:gml="http://www.opengis.net/gml" xmlns:gen="http://www.opengis.net/citygml/generics/2.0">
+  <cityObjectMember>
+    <gen:GenericCityObject gml:id="object_1">
+      <gen:intAttribute name="storeys"><gen:value>3</gen:value></gen:intAttribute>
+      <gen:lod2Geometry>
+        <gml:MultiSurface>
+          <gml:surfaceMember>
+            <gml:Polygon>
+              <gml:exterior>
+                <gml:LinearRing>
+                  <gml:pos>0 0 0</gml:pos><gml:pos>1 0 0</gml:pos><gml:pos>1 1 0</gml:pos><gml:pos>0 0 0</gml:pos>
+                </gml:LinearRing>
+              </gml:exterior>
+            </gml:Polygon>
+          </gml:surfaceMember>
+        </gml:MultiSurface>
+      </gen:lod2Geometry>
+    </gen:GenericCityObject>
+  </cityObjectMember>
+</CityModel>"#;
+
+        assert!(check_structure(xml_document).is_empty());
+    }
+
+    #[test]
+    fn test_date_accepts_timezones() {
+        for value in [
+            "2024-05-01",
+            "2024-05-01Z",
+            "2024-05-01+02:00",
+            "2024-05-01-05:30",
+        ] {
+            assert!(SimpleType::Date.accepts(value), "{value}");
+        }
+        for value in ["yesterday", "2024-05-01+2", "2024-05-01T12:00:00"] {
+            assert!(!SimpleType::Date.accepts(value), "{value}");
+        }
+    }
+}
@@ -0,0 +1,329 @@
+use crate::validate::report::{TopologyIssue, TopologyRule};
+use crate::validate::semantic::{distance_to_polygons, polygons};
+use ecitygml_core::model::city_model::CitygmlModel;
+use ecitygml_core::model::transportation::TrafficSpace;
+use egml::model::geometry::{MultiSurface, Polygon};
+use egml::operations::geometry::Geometry;
+use nalgebra::Vector3;
+use std::collections::HashMap;
+
+/// Tolerances of the road network topology rules in the unit of the coordinate reference
+/// system.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TopologyTolerance {
+    /// Maximum distance between the geometries of a traffic space and its successor.
+    pub connection_distance: f64,
+}
+
+impl Default for TopologyTolerance {
+    fn default() -> Self {
+        Self {
+            connection_distance: 0.1,
+        }
+    }
+}
+
+/// Traffic space of a road with the id of the road it belongs to.
+struct NetworkNode<'a> {
+    road_id: String,
+    traffic_space: &'a TrafficSpace,
+}
+
+/// Checks the predecessor and successor links between the traffic spaces of all roads.
+///
+/// Links to traffic spaces, which are not contained in the model, are not checked, since they
+/// are reported as broken hrefs or refer to other documents of a dataset.
+pub fn check_topology(
+    citygml_model: &CitygmlModel,
+    tolerance: &TopologyTolerance,
+) -> Vec<TopologyIssue> {
+    let mut nodes: Vec<NetworkNode> = Vec::new();
+    for road in &citygml_model.road {
+        let road_id = road.space.city_object.abstract_gml.id.to_string();
+        let traffic_spaces = road
+            .section
+            .iter()
+            .flat_map(|x| &x.traffic_space)
+            .chain(road.intersection.iter().flat_map(|x| &x.traffic_space));
+        nodes.extend(traffic_spaces.map(|traffic_space| NetworkNode {
+            road_id: road_id.clone(),
+            traffic_space,
+        }));
+    }
+    let node_indices: HashMap<String, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| (traffic_space_id(node.traffic_space), index))
+        .collect();
+    let successor_indices: Vec<Vec<usize>> = nodes
+        .iter()
+        .map(|node| {
+            node.traffic_space
+                .successor
+                .iter()
+                .filter_map(|x| node_indices.get(&x.to_string()).copied())
+                .collect()
+        })
+        .collect();
+
+    let mut issues = Vec::new();
+    for (index, node) in nodes.iter().enumerate() {
+        let id = traffic_space_id(node.traffic_space);
+        let issue = |rule: TopologyRule, message: String| TopologyIssue {
+            feature_id: id.clone(),
+            parent_id: Some(node.road_id.clone()),
+            rule,
+            message,
+        };
+
+        for successor_index in &successor_indices[index] {
+            let successor = nodes[*successor_index].traffic_space;
+            let successor_id = traffic_space_id(successor);
+            if !successor.predecessor.iter().any(|x| x.to_string() == id) {
+                issues.push(issue(
+                    TopologyRule::PredecessorSuccessorAsymmetric,
+                    format!("successor `{successor_id}` does not list it as predecessor"),
+                ));
+            }
+
+            if let Some(distance) = connection_distance(node.traffic_space, successor)
+                && distance > tolerance.connection_distance
+            {
+                issues.push(issue(
+                    TopologyRule::SuccessorNotConnected,
+                    format!("successor `{successor_id}` is {distance:.3} away"),
+                ));
+            }
+        }
+        for predecessor in node
+            .traffic_space
+            .predecessor
+            .iter()
+            .filter_map(|x| node_indices.get(&x.to_string()))
+            .map(|x| nodes[*x].traffic_space)
+        {
+            if !predecessor.successor.iter().any(|x| x.to_string() == id) {
+                issues.push(issue(
+                    TopologyRule::PredecessorSuccessorAsymmetric,
+                    format!(
+                        "predecessor `{}` does not list it as successor",
+                        traffic_space_id(predecessor)
+                    ),
+                ));
+            }
+        }
+
+        let dangling_ends: Vec<&str> = [
+            (node.traffic_space.predecessor.is_empty(), "predecessor"),
+            (node.traffic_space.successor.is_empty(), "successor"),
+        ]
+        .into_iter()
+        .filter(|x| x.0)
+        .map(|x| x.1)
+        .collect();
+        if !dangling_ends.is_empty() {
+            issues.push(issue(
+                TopologyRule::DanglingTrafficSpace,
+                format!("traffic space has no {}", dangling_ends.join(" and no ")),
+            ));
+        }
+    }
+
+    for cycle in find_cycles(&successor_indices) {
+        let first = &nodes[cycle[0]];
+        let ids: Vec<String> = cycle
+            .iter()
+            .map(|x| traffic_space_id(nodes[*x].traffic_space))
+            .collect();
+        issues.push(TopologyIssue {
+            feature_id: ids[0].clone(),
+            parent_id: Some(first.road_id.clone()),
+            rule: TopologyRule::TrafficSpaceCycle,
+            message: format!("successors form a cycle through {}", ids.join(", ")),
+        });
+    }
+
+    issues
+}
+
+fn traffic_space_id(traffic_space: &TrafficSpace) -> String {
+    traffic_space.space.city_object.abstract_gml.id.to_string()
+}
+
+fn traffic_space_polygons(traffic_space: &TrafficSpace) -> Vec<&Polygon> {
+    let space = &traffic_space.space;
+    [
+        &space.lod0_multi_surface,
+        &space.lod2_multi_surface,
+        &space.lod3_multi_surface,
+    ]
+    .into_iter()
+    .flatten()
+    .flat_map(MultiSurface::surface_member)
+    .chain(
+        traffic_space
+            .traffic_area
+            .iter()
+            .flat_map(|x| polygons(&x.thematic_surface)),
+    )
+    .collect()
+}
+
+/// Returns the smallest distance between the vertices of the traffic space and the polygons of
+/// its successor, if both have surface geometries.
+fn connection_distance(traffic_space: &TrafficSpace, successor: &TrafficSpace) -> Option<f64> {
+    let successor_polygons = traffic_space_polygons(successor);
+    if successor_polygons.is_empty() {
+        return None;
+    }
+
+    traffic_space_polygons(traffic_space)
+        .into_iter()
+        .flat_map(|x| x.exterior.points())
+        .map(|x| distance_to_polygons(&Vector3::from(x), &successor_polygons))
+        .reduce(f64::min)
+}
+
+/// Returns the strongly connected components of the successor graph, which contain a cycle,
+/// with the node indices in ascending order.
+///
+/// Tarjan's algorithm is run iteratively, so that long chains of traffic spaces do not exhaust
+/// the stack.
+fn find_cycles(successor_indices: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let number_of_nodes = successor_indices.len();
+    let mut index_counter = 0;
+    let mut indices: Vec<Option<usize>> = vec![None; number_of_nodes];
+    let mut low_links: Vec<usize> = vec![0; number_of_nodes];
+    let mut on_stack: Vec<bool> = vec![false; number_of_nodes];
+    let mut stack: Vec<usize> = Vec::new();
+    let mut cycles: Vec<Vec<usize>> = Vec::new();
+
+    for root in 0..number_of_nodes {
+        if indices[root].is_some() {
+            continue;
+        }
+
+        // pairs of node and position of the next successor to visit
+        let mut call_stack: Vec<(usize, usize)> = vec![(root, 0)];
+        while let Some((node, next)) = call_stack.pop() {
+            if next == 0 {
+                indices[node] = Some(index_counter);
+                low_links[node] = index_counter;
+                index_counter += 1;
+                stack.push(node);
+                on_stack[node] = true;
+            }
+
+            if let Some(&successor) = successor_indices[node].get(next) {
+                call_stack.push((node, next + 1));
+                match indices[successor] {
+                    None => call_stack.push((successor, 0)),
+                    Some(successor_index) if on_stack[successor] => {
+                        low_links[node] = low_links[node].min(successor_index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            if Some(low_links[node]) == indices[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                if component.len() > 1 || successor_indices[node].contains(&node) {
+                    component.sort();
+                    cycles.push(component);
+                }
+            }
+            if let Some((parent, _)) = call_stack.last() {
+                low_links[*parent] = low_links[*parent].min(low_links[node]);
+            }
+        }
+    }
+
+    cycles.sort();
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CitygmlReader;
+
+    fn traffic_space(id: &str, x: f64, predecessor: &[&str], successor: &[&str]) -> String {
+        let links: String = predecessor
+            .iter()
+            .map(|x| format!("<tran:predecessor xlink:href=\"#{x}\"/>"))
+            .chain(
+                successor
+                    .iter()
+                    .map(|x| format!("<tran:successor xlink:href=\"#{x}\"/>")),
+            )
+            .collect();
+        let pos_list = format!("{x} 0 0 {} 0 0 {} 1 0 {x} 1 0 {x} 0 0", x + 1.0, x + 1.0);
+        format!(
+            "<tran:trafficSpace><tran:TrafficSpace gml:id=\"{id}\"><lod2MultiSurface><gml:MultiSurface><gml:surfaceMember><gml:Polygon><gml:exterior><gml:LinearRing><gml:posList>{pos_list}</gml:posList></gml:LinearRing></gml:exterior></gml:Polygon></gml:surfaceMember></gml:MultiSurface></lod2MultiSurface>{links}</tran:TrafficSpace></tran:trafficSpace>"
+        )
+    }
+
+    fn model(traffic_spaces: &[String]) -> CitygmlModel {
+        let xml_document = format!(
+            "<CityModel xmlns:gml=\"http://www.opengis.net/gml/3.2\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" xmlns:tran=\"http://www.opengis.net/citygml/transportation/3.0\"><cityObjectMember><tran:Road gml:id=\"road_1\"><tran:section><tran:Section gml:id=\"section_1\">{}</tran:Section></tran:section></tran:Road></cityObjectMember></CityModel>",
+            traffic_spaces.join("")
+        );
+        CitygmlReader::from_str(&xml_document)
+            .finish()
+            .expect("should work")
+    }
+
+    fn rules(issues: &[TopologyIssue]) -> Vec<(String, TopologyRule)> {
+        issues
+            .iter()
+            .map(|x| (x.feature_id.clone(), x.rule))
+            .collect()
+    }
+
+    #[test]
+    fn test_check_topology_of_connected_chain() {
+        let citygml_model = model(&[
+            traffic_space("a", 0.0, &["start"], &["b"]),
+            traffic_space("b", 1.0, &["a"], &["c"]),
+            traffic_space("c", 2.0, &["b"], &["end"]),
+        ]);
+
+        let issues = check_topology(&citygml_model, &TopologyTolerance::default());
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn test_check_topology_detects_issues() {
+        let citygml_model = model(&[
+            traffic_space("a", 0.0, &["c"], &["b"]),
+            traffic_space("b", 1.0, &[], &["c"]),
+            traffic_space("c", 5.0, &["b"], &["a"]),
+            traffic_space("d", 10.0, &[], &[]),
+        ]);
+
+        let issues = check_topology(&citygml_model, &TopologyTolerance::default());
+        assert_eq!(
+            rules(&issues),
+            vec![
+                (
+                    "a".to_string(),
+                    TopologyRule::PredecessorSuccessorAsymmetric
+                ),
+                ("b".to_string(), TopologyRule::SuccessorNotConnected),
+                ("b".to_string(), TopologyRule::DanglingTrafficSpace),
+                ("c".to_string(), TopologyRule::SuccessorNotConnected),
+                ("d".to_string(), TopologyRule::DanglingTrafficSpace),
+                ("a".to_string(), TopologyRule::TrafficSpaceCycle),
+            ]
+        );
+        assert_eq!(issues[5].message, "successors form a cycle through a, b, c");
+    }
+}
@@ -1,57 +1,198 @@
 use crate::error::Error;
-use crate::validate::extracted_information::{
-    CityObjectRelation, ExtractedInformation, GmlIdCount,
-};
+use crate::read_impl::{ReadOptions, read_from_file};
+use crate::validate::extracted_information::{CityObjectRelation, ExtractedInformation};
+use crate::validate::geometry::check_geometry;
 use crate::validate::report::Report;
+use crate::validate::report::{SemanticIssue, SemanticRule};
+use crate::validate::structure::check_structure;
+use crate::validate::{
+    Check, GeometryTolerance, SemanticTolerance, TopologyTolerance, ValidationProfile,
+    check_semantics, check_topology,
+};
 use quick_xml::Reader;
 use quick_xml::de;
-use quick_xml::events::Event;
 use quick_xml::events::attributes::Attribute;
+use quick_xml::events::{BytesStart, Event};
 
-use std::io::{BufReader, Read, Seek};
+use std::collections::HashMap;
+use std::io::{BufReader, Read};
 use std::ops::Deref;
+use std::sync::Arc;
+
+/// Options of a validation, which can be shared between the
+/// [`CitygmlReader`](crate::CitygmlReader) and the
+/// [`CitygmlDatasetReader`](crate::CitygmlDatasetReader).
+#[derive(Debug, Clone, Default)]
+pub struct ValidateOptions {
+    pub(crate) structure_validation: bool,
+    pub(crate) geometry_validation: bool,
+    pub(crate) geometry_tolerance: GeometryTolerance,
+    pub(crate) semantic_validation: bool,
+    pub(crate) semantic_tolerance: SemanticTolerance,
+    pub(crate) topology_validation: bool,
+    pub(crate) topology_tolerance: TopologyTolerance,
+    pub(crate) profile: ValidationProfile,
+}
+
+impl ValidateOptions {
+    /// Enables the checks, for which any rule is enabled in the profile, with the tolerances of
+    /// the profile.
+    pub fn from_profile(profile: ValidationProfile) -> Self {
+        Self {
+            structure_validation: profile.is_check_enabled(Check::Structure),
+            geometry_validation: profile.is_check_enabled(Check::Geometry),
+            geometry_tolerance: profile.geometry_tolerance(),
+            semantic_validation: profile.is_check_enabled(Check::Semantic),
+            semantic_tolerance: profile.semantic_tolerance(),
+            topology_validation: profile.is_check_enabled(Check::Topology),
+            topology_tolerance: profile.topology_tolerance(),
+            profile,
+        }
+    }
 
-pub fn validate_from_reader<R: Read + Seek>(reader: R) -> Result<Report, Error> {
+    /// See [`CitygmlReader::with_structure_validation`](crate::CitygmlReader::with_structure_validation).
+    pub fn with_structure_validation(mut self, structure_validation: bool) -> Self {
+        self.structure_validation = structure_validation;
+        self
+    }
+
+    /// See [`CitygmlReader::with_geometry_validation`](crate::CitygmlReader::with_geometry_validation).
+    pub fn with_geometry_validation(mut self, geometry_validation: bool) -> Self {
+        self.geometry_validation = geometry_validation;
+        self
+    }
+
+    /// See [`CitygmlReader::with_semantic_validation`](crate::CitygmlReader::with_semantic_validation).
+    pub fn with_semantic_validation(mut self, semantic_validation: bool) -> Self {
+        self.semantic_validation = semantic_validation;
+        self
+    }
+
+    /// See [`CitygmlReader::with_topology_validation`](crate::CitygmlReader::with_topology_validation).
+    pub fn with_topology_validation(mut self, topology_validation: bool) -> Self {
+        self.topology_validation = topology_validation;
+        self
+    }
+}
+
+pub fn validate_from_reader<R: Read>(
+    reader: R,
+    options: &ValidateOptions,
+    read_options: &ReadOptions,
+) -> Result<Report, Error> {
+    let extracted_information = extract_information(reader, options, read_options)?;
+    let report = extracted_information.compile_report(&options.profile);
+    Ok(report)
+}
+
+/// Compiles the reports of the documents of a dataset, whose references are resolved against
+/// the gml:ids of all documents.
+pub(crate) fn compile_dataset_reports(
+    documents: Vec<(String, ExtractedInformation)>,
+    profile: &ValidationProfile,
+) -> Vec<Report> {
+    let mut dataset_gml_ids: HashMap<String, String> = HashMap::new();
+    for (document, extracted_information) in &documents {
+        for gml_id in extracted_information.gml_id_count.keys() {
+            dataset_gml_ids
+                .entry(gml_id.clone())
+                .or_insert_with(|| document.clone());
+        }
+    }
+    let dataset_gml_ids = Arc::new(dataset_gml_ids);
+
+    documents
+        .into_iter()
+        .map(|(document, mut extracted_information)| {
+            extracted_information.dataset_gml_ids = dataset_gml_ids.clone();
+            let mut report = extracted_information.compile_report(profile);
+            report.source = Some(document);
+            report
+        })
+        .collect()
+}
+
+pub(crate) fn extract_information<R: Read>(
+    reader: R,
+    options: &ValidateOptions,
+    read_options: &ReadOptions,
+) -> Result<ExtractedInformation, Error> {
     let mut extracted_information = ExtractedInformation::default();
 
     // TODO: improve
     let mut file_content: String = Default::default();
     BufReader::new(reader).read_to_string(&mut file_content)?;
-    let mut xml_reader = Reader::from_str(file_content.as_str());
+    match scan_document(&file_content, &mut extracted_information) {
+        Ok(()) => {}
+        // the position of the syntax error is reported by the structural check
+        Err(Error::QuickXmlError(_)) if options.structure_validation => {}
+        Err(e) => return Err(e),
+    }
+
+    if options.structure_validation {
+        extracted_information.structure_violations = check_structure(&file_content);
+    }
+    if options.geometry_validation {
+        extracted_information.geometry_errors =
+            check_geometry(&file_content, options.geometry_tolerance);
+    }
+    if options.semantic_validation || options.topology_validation {
+        match read_from_file(file_content.as_bytes(), read_options) {
+            Ok(citygml_model) => {
+                if options.semantic_validation {
+                    extracted_information.semantic_issues =
+                        check_semantics(&citygml_model, &options.semantic_tolerance);
+                }
+                if options.topology_validation {
+                    extracted_information.topology_issues =
+                        check_topology(&citygml_model, &options.topology_tolerance);
+                }
+            }
+            Err(e) => {
+                extracted_information.semantic_issues = vec![SemanticIssue {
+                    feature_id: String::new(),
+                    parent_id: None,
+                    rule: SemanticRule::ModelNotReadable,
+                    message: e.to_string(),
+                }]
+            }
+        }
+    }
+
+    Ok(extracted_information)
+}
+
+/// Collects the gml:ids, relations and traffic space links of the document.
+fn scan_document(
+    file_content: &str,
+    extracted_information: &mut ExtractedInformation,
+) -> Result<(), Error> {
+    let mut xml_reader = Reader::from_str(file_content);
     xml_reader.config_mut().trim_text(true);
 
     let mut buf = Vec::new();
     loop {
         match xml_reader.read_event_into(&mut buf) {
-            Err(e) => panic!(
-                "Error at position {}: {:?}",
-                xml_reader.buffer_position(),
-                e
-            ),
+            Err(e) => return Err(e.into()),
             Ok(Event::Eof) => break,
             Ok(Event::Start(e)) => {
                 let element_name = e.name();
                 let element_name: String = xml_reader
                     .decoder()
                     .decode(element_name.as_ref())
-                    .unwrap()
+                    .map_err(quick_xml::Error::from)?
                     .to_string();
 
-                let id_attribute: Option<Attribute> = e
-                    .attributes()
-                    .map(|a| a.unwrap())
-                    .find(|a| a.key.local_name().as_ref() == "id".as_bytes());
+                let id_attribute: Option<Attribute> = find_attribute(&e, b"id")?;
 
                 extracted_information
                     .gml_id_count_per_element_type
                     .entry(element_name.clone())
-                    .or_insert(GmlIdCount::default())
+                    .or_default()
                     .increment(id_attribute.is_some());
 
                 if let Some(id_attribute) = id_attribute {
-                    let a = id_attribute
-                        .decode_and_unescape_value(xml_reader.decoder())
-                        .unwrap();
+                    let a = id_attribute.decode_and_unescape_value(xml_reader.decoder())?;
                     *extracted_information
                         .gml_id_count
                         .entry(a.deref().to_string())
@@ -59,8 +200,8 @@ pub fn validate_from_reader<R: Read + Seek>(reader: R) -> Result<Report, Error>
                 };
 
                 if e.name().as_ref() == b"relatedTo" {
-                    let read_text: &str = &xml_reader.read_text(e.name()).unwrap();
-                    let city_object_relation: CityObjectRelation = de::from_str(read_text).unwrap();
+                    let read_text: &str = &xml_reader.read_text(e.name())?;
+                    let city_object_relation = parse_city_object_relation(read_text)?;
 
                     extracted_information
                         .city_object_relations
@@ -70,16 +211,10 @@ pub fn validate_from_reader<R: Read + Seek>(reader: R) -> Result<Report, Error>
             Ok(Event::Empty(e)) => {
                 //let read_text: &str = e.unescape().unwrap();
 
-                let href_attribute: Option<String> = e
-                    .attributes()
-                    .map(|a| a.unwrap())
-                    .find(|a| a.key.local_name().as_ref() == "href".as_bytes())
-                    .map(|a| {
-                        a.decode_and_unescape_value(xml_reader.decoder())
-                            .unwrap()
-                            .deref()
-                            .to_string()
-                    });
+                let href_attribute: Option<String> = find_attribute(&e, b"href")?
+                    .map(|a| a.decode_and_unescape_value(xml_reader.decoder()))
+                    .transpose()?
+                    .map(|a| a.deref().to_string());
 
                 match e.name().as_ref() {
                     b"tran:predecessor" => {
@@ -107,8 +242,20 @@ pub fn validate_from_reader<R: Read + Seek>(reader: R) -> Result<Report, Error>
         buf.clear();
     }
 
-    let report = extracted_information.compile_report();
-    Ok(report)
+    Ok(())
+}
+
+fn find_attribute<'a>(
+    element: &'a BytesStart,
+    local_name: &[u8],
+) -> Result<Option<Attribute<'a>>, Error> {
+    for attribute in element.attributes() {
+        let attribute = attribute.map_err(quick_xml::Error::from)?;
+        if attribute.key.local_name().as_ref() == local_name {
+            return Ok(Some(attribute));
+        }
+    }
+    Ok(None)
 }
 
 fn parse_city_object_relation(source_text: &str) -> Result<CityObjectRelation, Error> {
@@ -118,7 +265,33 @@ fn parse_city_object_relation(source_text: &str) -> Result<CityObjectRelation, E
 
 #[cfg(test)]
 mod tests {
-    use crate::validate_impl::parse_city_object_relation;
+    use crate::CitygmlReader;
+    use crate::error::Error;
+    use crate::validate::ValidationProfile;
+    use crate::validate_impl::{ValidateOptions, parse_city_object_relation};
+
+    const ILL_FORMED_DOCUMENT: &str =
+        "<CityModel xmlns=\"http://www.opengis.net/citygml/3.0\">\n<a></b>\n</CityModel>";
+
+    #[test]
+    fn test_validate_reports_ill_formed_document_as_structure_violation() {
+        let report = CitygmlReader::from_str(ILL_FORMED_DOCUMENT)
+            .with_structure_validation(true)
+            .validate()
+            .expect("should be reported");
+
+        assert_eq!(report.structure_violations.len(), 1);
+        let violation = &report.structure_violations[0];
+        assert_eq!((violation.line, violation.element_name.as_str()), (2, "a"));
+        assert!(violation.message.starts_with("document is not well-formed"));
+    }
+
+    #[test]
+    fn test_validate_returns_error_for_ill_formed_document() {
+        let result = CitygmlReader::from_str(ILL_FORMED_DOCUMENT).validate();
+
+        assert!(matches!(result, Err(Error::QuickXmlError(_))));
+    }
 
     #[test]
     fn parsing_city_object_relation() {
@@ -134,4 +307,20 @@ mod tests {
             "#UUID_c930adc7-9e6c-3eea-a377-b31d9d5b6239"
         );
     }
+
+    #[test]
+    fn test_validate_options_from_profile_with_additional_checks() {
+        let research = ValidationProfile::from_yaml(include_str!("../profiles/research.yaml"))
+            .expect("should work");
+
+        let validate_options = ValidateOptions::from_profile(research.clone());
+        assert!(!validate_options.structure_validation);
+        assert!(validate_options.geometry_validation);
+        assert_eq!(validate_options.geometry_tolerance.planarity, 0.05);
+
+        let validate_options =
+            ValidateOptions::from_profile(research).with_structure_validation(true);
+        assert!(validate_options.structure_validation);
+        assert_eq!(validate_options.geometry_tolerance.planarity, 0.05);
+    }
 }
@@ -0,0 +1,43 @@
+use crate::error::Error;
+use crate::error::Error::{InvalidFileExtension, NoFileExtension};
+use crate::write_impl::write_to_writer;
+use crate::{FILE_EXTENSION_CITYGML_GML_FORMAT, FILE_EXTENSION_CITYGML_XML_FORMAT};
+use ecitygml_core::model::city_model::CitygmlModel;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+/// `CitygmlWriter` writes CityGML 3.0 datasets.
+///
+/// Unknown content preserved during reading and ADE properties are written back.
+#[derive(Debug, Clone)]
+pub struct CitygmlWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> CitygmlWriter<W> {
+    /// Create a new [`CitygmlWriter`] from an existing `Writer`.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn write(self, citygml_model: &CitygmlModel) -> Result<(), Error> {
+        write_to_writer(self.writer, citygml_model)
+    }
+}
+
+impl CitygmlWriter<File> {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let extension = path.as_ref().extension().ok_or(NoFileExtension())?;
+        if extension != FILE_EXTENSION_CITYGML_GML_FORMAT
+            && extension != FILE_EXTENSION_CITYGML_XML_FORMAT
+        {
+            return Err(InvalidFileExtension(
+                extension.to_str().unwrap_or_default().to_string(),
+            ));
+        }
+
+        let file = File::create(path)?;
+        Ok(Self::new(file))
+    }
+}
@@ -0,0 +1,175 @@
+use crate::error::Error;
+use crate::writer::building::write_building;
+use crate::writer::city_object::write_occupied_space;
+use crate::writer::element;
+use crate::writer::transportation::write_road;
+use crate::writer::versioning::{write_version, write_version_transition};
+use ecitygml_core::model::city_model::CitygmlModel;
+use std::collections::BTreeMap;
+use std::io::{BufWriter, Write};
+
+const DEFAULT_NAMESPACE: &str = "http://www.opengis.net/citygml/3.0";
+
+const NAMESPACE_DECLARATIONS: &[(&str, &str)] = &[
+    ("gml", "http://www.opengis.net/gml/3.2"),
+    ("xlink", "http://www.w3.org/1999/xlink"),
+    ("bldg", "http://www.opengis.net/citygml/building/3.0"),
+    ("con", "http://www.opengis.net/citygml/construction/3.0"),
+    ("dyn", "http://www.opengis.net/citygml/dynamizer/3.0"),
+    ("frn", "http://www.opengis.net/citygml/cityfurniture/3.0"),
+    ("gen", "http://www.opengis.net/citygml/generics/3.0"),
+    ("pcl", "http://www.opengis.net/citygml/pointcloud/3.0"),
+    ("tran", "http://www.opengis.net/citygml/transportation/3.0"),
+    ("veg", "http://www.opengis.net/citygml/vegetation/3.0"),
+    ("vers", "http://www.opengis.net/citygml/versioning/3.0"),
+];
+
+pub fn write_to_writer<W: Write>(writer: W, citygml_model: &CitygmlModel) -> Result<(), Error> {
+    let mut namespace_declarations: BTreeMap<String, String> = NAMESPACE_DECLARATIONS
+        .iter()
+        .map(|(prefix, namespace)| (prefix.to_string(), namespace.to_string()))
+        .collect();
+    // declarations of the source document are kept for prefixes of preserved content, but do not
+    // rebind the CityGML 3.0 prefixes
+    for (prefix, namespace) in &citygml_model.namespace_declarations {
+        namespace_declarations
+            .entry(prefix.clone())
+            .or_insert_with(|| namespace.clone());
+    }
+
+    let mut attributes: Vec<(String, String)> =
+        vec![("xmlns".to_string(), DEFAULT_NAMESPACE.to_string())];
+    attributes.extend(
+        namespace_declarations
+            .into_iter()
+            .map(|(prefix, namespace)| (format!("xmlns:{prefix}"), namespace)),
+    );
+    let attributes: Vec<(&str, &str)> = attributes
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect();
+
+    let mut city_object_members: Vec<String> = Vec::new();
+    city_object_members.extend(citygml_model.building.iter().map(write_building));
+    city_object_members.extend(
+        citygml_model
+            .city_furniture
+            .iter()
+            .map(|x| write_occupied_space("frn:CityFurniture", &x.occupied_space, Vec::new())),
+    );
+    city_object_members.extend(citygml_model.road.iter().map(write_road));
+    city_object_members.extend(citygml_model.solitary_vegetation_object.iter().map(|x| {
+        write_occupied_space(
+            "veg:SolitaryVegetationObject",
+            &x.occupied_space,
+            Vec::new(),
+        )
+    }));
+    city_object_members.extend(citygml_model.version.iter().map(write_version));
+    city_object_members.extend(
+        citygml_model
+            .version_transition
+            .iter()
+            .map(write_version_transition),
+    );
+
+    let content: String = city_object_members
+        .iter()
+        .map(|x| element("cityObjectMember", &[], x))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut writer = BufWriter::new(writer);
+    writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(
+        writer,
+        "{}",
+        element("CityModel", &attributes, &format!("\n{content}\n"))
+    )?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CitygmlReader;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_write_round_trip_with_unknown_content() {
+        let xml_document = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<CityModel xmlns=\"http://www.opengis.net/citygml/3.0\" xmlns:gml=\"http://www.opengis.net/gml/3.2\" xmlns:frn=\"http://www.opengis.net/citygml/cityfurniture/3.0\" xmlns:app=\"http://www.opengis.net/citygml/appearance/3.0\">
+  <cityObjectMember>
+    <frn:CityFurniture gml:id=\"furniture_1\">
+      <gml:name>Bench</gml:name>
+      <app:appearance><app:Appearance><app:theme>rgb</app:theme></app:Appearance></app:appearance>
+      <frn:function>1000</frn:function>
+    </frn:CityFurniture>
+  </cityObjectMember>
+</CityModel>";
+
+        let citygml_model = CitygmlReader::new(Cursor::new(xml_document))
+            .with_preserve_unknown_content(true)
+            .finish()
+            .expect("should work");
+        assert_eq!(
+            citygml_model.city_furniture[0]
+                .occupied_space
+                .space
+                .city_object
+                .unknown_content
+                .len(),
+            2
+        );
+
+        let mut output: Vec<u8> = Vec::new();
+        write_to_writer(&mut output, &citygml_model).expect("should work");
+        let output = String::from_utf8(output).expect("should work");
+        assert!(output.contains("xmlns:app=\"http://www.opengis.net/citygml/appearance/3.0\""));
+        assert!(output.contains(
+            "<frn:CityFurniture gml:id=\"furniture_1\"><gml:name>Bench</gml:name><app:appearance>"
+        ));
+        assert!(output.contains("<frn:function>1000</frn:function>"));
+
+        let reread_citygml_model = CitygmlReader::new(Cursor::new(output))
+            .with_preserve_unknown_content(true)
+            .finish()
+            .expect("should work");
+        assert_eq!(
+            reread_citygml_model.city_furniture,
+            citygml_model.city_furniture
+        );
+    }
+
+    #[test]
+    fn test_write_unknown_content_after_preceding_property() {
+        let xml_document = "<CityModel xmlns=\"http://www.opengis.net/citygml/2.0\" xmlns:gml=\"http://www.opengis.net/gml\" xmlns:frn=\"http://www.opengis.net/citygml/cityfurniture/2.0\" xmlns:adv=\"http://www.adv-online.de/namespaces/adv/gid/7_1\">
+  <cityObjectMember>
+    <frn:CityFurniture gml:id=\"furniture_1\">
+      <adv:first/>
+      <creationDate>2017-01-23T00:00:00Z</creationDate>
+      <adv:flag value=\"1\"/>
+      <gml:name>Bench</gml:name>
+    </frn:CityFurniture>
+  </cityObjectMember>
+</CityModel>";
+
+        let citygml_model = CitygmlReader::from_str(xml_document)
+            .with_preserve_unknown_content(true)
+            .finish()
+            .expect("should work");
+        let mut output: Vec<u8> = Vec::new();
+        write_to_writer(&mut output, &citygml_model).expect("should work");
+        let output = String::from_utf8(output).expect("should work");
+
+        assert!(output.contains(
+            "<frn:CityFurniture gml:id=\"furniture_1\"><adv:first/><gml:name>Bench</gml:name><creationDate>2017-01-23T00:00:00Z</creationDate><adv:flag value=\"1\"/>"
+        ));
+        assert!(output.contains("xmlns=\"http://www.opengis.net/citygml/3.0\""));
+        assert!(output.contains("xmlns:gml=\"http://www.opengis.net/gml/3.2\""));
+        assert!(output.contains("xmlns:frn=\"http://www.opengis.net/citygml/cityfurniture/3.0\""));
+        assert!(output.contains("xmlns:adv=\"http://www.adv-online.de/namespaces/adv/gid/7_1\""));
+    }
+}
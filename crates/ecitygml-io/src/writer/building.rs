@@ -0,0 +1,167 @@
+use crate::writer::city_object::{write_occupied_space, write_thematic_surface};
+use crate::writer::element;
+use ecitygml_core::model::building::{Building, BuildingConstructiveElement};
+use ecitygml_core::model::construction::{
+    Door, DoorSurface, InteriorWallSurface, WallSurface, Window, WindowSurface,
+};
+use ecitygml_core::model::core::ThematicSurface;
+
+pub fn write_building(building: &Building) -> String {
+    let boundaries: Vec<(&str, &ThematicSurface)> = building
+        .ground_surface
+        .iter()
+        .map(|x| ("con:GroundSurface", &x.thematic_surface))
+        .chain(
+            building
+                .roof_surface
+                .iter()
+                .map(|x| ("con:RoofSurface", &x.thematic_surface)),
+        )
+        .chain(
+            building
+                .ceiling_surface
+                .iter()
+                .map(|x| ("con:CeilingSurface", &x.thematic_surface)),
+        )
+        .chain(
+            building
+                .floor_surface
+                .iter()
+                .map(|x| ("con:FloorSurface", &x.thematic_surface)),
+        )
+        .chain(
+            building
+                .outer_ceiling_surface
+                .iter()
+                .map(|x| ("con:OuterCeilingSurface", &x.thematic_surface)),
+        )
+        .chain(
+            building
+                .outer_floor_surface
+                .iter()
+                .map(|x| ("con:OuterFloorSurface", &x.thematic_surface)),
+        )
+        .chain(
+            building
+                .closure_surface
+                .iter()
+                .map(|x| ("ClosureSurface", &x.thematic_surface)),
+        )
+        .collect();
+
+    let mut children: Vec<String> = building
+        .wall_surface
+        .iter()
+        .map(|x| element("boundary", &[], &write_wall_surface(x)))
+        .collect();
+    children.extend(
+        building
+            .interior_wall_surface
+            .iter()
+            .map(|x| element("boundary", &[], &write_interior_wall_surface(x))),
+    );
+    children.extend(boundaries.iter().map(|(name, x)| {
+        element(
+            "boundary",
+            &[],
+            &write_thematic_surface(name, x, Vec::new()),
+        )
+    }));
+    children.extend(building.building_constructive_element.iter().map(|x| {
+        element(
+            "bldg:buildingConstructiveElement",
+            &[],
+            &write_building_constructive_element(x),
+        )
+    }));
+
+    write_occupied_space("bldg:Building", &building.occupied_space, children)
+}
+
+pub fn write_building_constructive_element(
+    building_constructive_element: &BuildingConstructiveElement,
+) -> String {
+    let mut children: Vec<String> = building_constructive_element
+        .window
+        .iter()
+        .map(|x| element("con:filling", &[], &write_window(x)))
+        .collect();
+    children.extend(
+        building_constructive_element
+            .door
+            .iter()
+            .map(|x| element("con:filling", &[], &write_door(x))),
+    );
+
+    write_occupied_space(
+        "bldg:BuildingConstructiveElement",
+        &building_constructive_element.occupied_space,
+        children,
+    )
+}
+
+pub fn write_wall_surface(wall_surface: &WallSurface) -> String {
+    let children = write_filling_surfaces(&wall_surface.door_surface, &wall_surface.window_surface);
+    write_thematic_surface("con:WallSurface", &wall_surface.thematic_surface, children)
+}
+
+pub fn write_interior_wall_surface(interior_wall_surface: &InteriorWallSurface) -> String {
+    let children = write_filling_surfaces(
+        &interior_wall_surface.door_surface,
+        &interior_wall_surface.window_surface,
+    );
+    write_thematic_surface(
+        "con:InteriorWallSurface",
+        &interior_wall_surface.thematic_surface,
+        children,
+    )
+}
+
+pub fn write_door(door: &Door) -> String {
+    let children: Vec<String> = door
+        .door_surface
+        .iter()
+        .map(|x| {
+            let door_surface =
+                write_thematic_surface("con:DoorSurface", &x.thematic_surface, Vec::new());
+            element("boundary", &[], &door_surface)
+        })
+        .collect();
+
+    write_occupied_space("con:Door", &door.occupied_space, children)
+}
+
+pub fn write_window(window: &Window) -> String {
+    let children: Vec<String> = window
+        .window_surface
+        .iter()
+        .map(|x| {
+            let window_surface =
+                write_thematic_surface("con:WindowSurface", &x.thematic_surface, Vec::new());
+            element("boundary", &[], &window_surface)
+        })
+        .collect();
+
+    write_occupied_space("con:Window", &window.occupied_space, children)
+}
+
+fn write_filling_surfaces(
+    door_surface: &[DoorSurface],
+    window_surface: &[WindowSurface],
+) -> Vec<String> {
+    let mut children: Vec<String> = window_surface
+        .iter()
+        .map(|x| {
+            let window_surface =
+                write_thematic_surface("con:WindowSurface", &x.thematic_surface, Vec::new());
+            element("con:fillingSurface", &[], &window_surface)
+        })
+        .collect();
+    children.extend(door_surface.iter().map(|x| {
+        let door_surface =
+            write_thematic_surface("con:DoorSurface", &x.thematic_surface, Vec::new());
+        element("con:fillingSurface", &[], &door_surface)
+    }));
+
+    children
+}
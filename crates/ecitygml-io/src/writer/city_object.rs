@@ -0,0 +1,455 @@
+use crate::writer::geometry::{
+    write_implicit_geometry, write_multi_point, write_multi_surface, write_solid,
+};
+use crate::writer::{element, text_element};
+use chrono::{DateTime, SecondsFormat, Utc};
+use ecitygml_core::model::core::{
+    CityObject, ExternalReference, FragmentPosition, GenericAttribute, OccupiedSpace, Space,
+    ThematicSurface, XmlFragment,
+};
+use ecitygml_core::model::dynamizer::{
+    AtomicTimeseries, Dynamizer, TabulatedFileTimeseries, TimeValuePair, Timeseries,
+    TimeseriesValue,
+};
+use ecitygml_core::model::point_cloud::PointCloud;
+use std::collections::HashMap;
+
+/// Serializes a feature with the properties of its city object and the given child elements.
+///
+/// Preserved unknown content is re-inserted after the property it followed in the source
+/// document, or after all properties if that property is not written. ADE properties are
+/// appended at the end.
+pub fn write_feature(
+    element_name: &str,
+    city_object: &CityObject,
+    children: Vec<String>,
+) -> String {
+    let mut properties: Vec<String> = city_object
+        .abstract_gml
+        .name
+        .iter()
+        .map(|x| text_element("gml:name", x))
+        .collect();
+    properties.extend(write_city_object_properties(city_object));
+    properties.extend(children);
+
+    let mut properties = insert_fragments(properties, &city_object.unknown_content);
+    properties.extend(city_object.ade_extension.iter().map(|(_, x)| x.to_xml()));
+
+    element(
+        element_name,
+        &[("gml:id", &city_object.abstract_gml.id.to_string())],
+        &properties.concat(),
+    )
+}
+
+/// Inserts the fragments after the properties matching their preceding element.
+fn insert_fragments(properties: Vec<String>, fragments: &[XmlFragment]) -> Vec<String> {
+    let mut occurrences: HashMap<String, usize> = HashMap::new();
+    let anchors: Vec<FragmentPosition> = properties
+        .iter()
+        .map(|x| {
+            let element_name = local_element_name(x).to_string();
+            let occurrence = occurrences.entry(element_name.clone()).or_default();
+            let anchor = FragmentPosition {
+                preceding_element: Some(element_name),
+                occurrence: *occurrence,
+            };
+            *occurrence += 1;
+            anchor
+        })
+        .collect();
+
+    let (mut placed, unplaced): (Vec<&XmlFragment>, Vec<&XmlFragment>) = fragments
+        .iter()
+        .partition(|x| x.position.preceding_element.is_none() || anchors.contains(&x.position));
+    let mut result: Vec<String> = placed
+        .iter()
+        .filter(|x| x.position.preceding_element.is_none())
+        .map(|x| x.content.clone())
+        .collect();
+    placed.retain(|x| x.position.preceding_element.is_some());
+    for (property, anchor) in properties.into_iter().zip(&anchors) {
+        result.push(property);
+        result.extend(
+            placed
+                .iter()
+                .filter(|x| x.position == *anchor)
+                .map(|x| x.content.clone()),
+        );
+    }
+    result.extend(unplaced.iter().map(|x| x.content.clone()));
+
+    result
+}
+
+/// Returns the local name of the root element of a serialized property.
+fn local_element_name(property: &str) -> &str {
+    let element_name = property
+        .trim_start_matches('<')
+        .split(|x: char| x.is_whitespace() || x == '>' || x == '/')
+        .next()
+        .unwrap_or_default();
+    element_name
+        .split_once(':')
+        .map_or(element_name, |(_, local_name)| local_name)
+}
+
+pub fn write_space(element_name: &str, space: &Space, children: Vec<String>) -> String {
+    let mut properties = write_space_properties(space);
+    properties.extend(children);
+
+    write_feature(element_name, &space.city_object, properties)
+}
+
+pub fn write_occupied_space(
+    element_name: &str,
+    occupied_space: &OccupiedSpace,
+    children: Vec<String>,
+) -> String {
+    let mut properties = write_space_properties(&occupied_space.space);
+    let implicit_representations = [
+        (
+            "lod1ImplicitRepresentation",
+            &occupied_space.lod1_implicit_representation,
+        ),
+        (
+            "lod2ImplicitRepresentation",
+            &occupied_space.lod2_implicit_representation,
+        ),
+        (
+            "lod3ImplicitRepresentation",
+            &occupied_space.lod3_implicit_representation,
+        ),
+    ];
+    properties.extend(
+        implicit_representations
+            .iter()
+            .filter_map(|(name, g)| g.as_ref().map(|g| (name, g)))
+            .map(|(name, g)| element(name, &[], &write_implicit_geometry(g))),
+    );
+    properties.extend(children);
+
+    write_feature(element_name, &occupied_space.space.city_object, properties)
+}
+
+pub fn write_thematic_surface(
+    element_name: &str,
+    thematic_surface: &ThematicSurface,
+    children: Vec<String>,
+) -> String {
+    let multi_surfaces = [
+        ("lod0MultiSurface", &thematic_surface.lod0_multi_surface),
+        ("lod1MultiSurface", &thematic_surface.lod1_multi_surface),
+        ("lod2MultiSurface", &thematic_surface.lod2_multi_surface),
+        ("lod3MultiSurface", &thematic_surface.lod3_multi_surface),
+    ];
+    let mut properties: Vec<String> = multi_surfaces
+        .iter()
+        .filter_map(|(name, g)| g.as_ref().map(|g| (name, g)))
+        .map(|(name, g)| element(name, &[], &write_multi_surface(g)))
+        .collect();
+    properties.extend(children);
+
+    write_feature(element_name, &thematic_surface.city_object, properties)
+}
+
+fn write_space_properties(space: &Space) -> Vec<String> {
+    let multi_surfaces = [
+        ("lod0MultiSurface", &space.lod0_multi_surface),
+        ("lod2MultiSurface", &space.lod2_multi_surface),
+        ("lod3MultiSurface", &space.lod3_multi_surface),
+    ];
+    let solids = [
+        ("lod1Solid", &space.lod1_solid),
+        ("lod2Solid", &space.lod2_solid),
+        ("lod3Solid", &space.lod3_solid),
+    ];
+
+    let mut properties: Vec<String> = multi_surfaces
+        .iter()
+        .filter_map(|(name, g)| g.as_ref().map(|g| (name, g)))
+        .map(|(name, g)| element(name, &[], &write_multi_surface(g)))
+        .collect();
+    properties.extend(
+        solids
+            .iter()
+            .filter_map(|(name, g)| g.as_ref().map(|g| (name, g)))
+            .map(|(name, g)| element(name, &[], &write_solid(g))),
+    );
+    if let Some(point_cloud) = &space.point_cloud {
+        properties.push(element("pointCloud", &[], &write_point_cloud(point_cloud)));
+    }
+
+    properties
+}
+
+fn write_city_object_properties(city_object: &CityObject) -> Vec<String> {
+    let lifespan = [
+        ("creationDate", &city_object.creation_date),
+        ("terminationDate", &city_object.termination_date),
+        ("validFrom", &city_object.valid_from),
+        ("validTo", &city_object.valid_to),
+    ];
+    let mut properties: Vec<String> = lifespan
+        .iter()
+        .filter_map(|(name, x)| x.as_ref().map(|x| text_element(name, &write_date_time(x))))
+        .collect();
+
+    properties.extend(
+        city_object
+            .external_reference
+            .iter()
+            .map(|x| element("externalReference", &[], &write_external_reference(x))),
+    );
+    if let Some(x) = &city_object.relative_to_terrain {
+        properties.push(text_element("relativeToTerrain", &x.to_string()));
+    }
+    if let Some(x) = &city_object.relative_to_water {
+        properties.push(text_element("relativeToWater", &x.to_string()));
+    }
+    properties.extend(
+        city_object
+            .generic_attributes
+            .iter()
+            .map(|x| element("genericAttribute", &[], &write_generic_attribute(x))),
+    );
+    properties.extend(
+        city_object
+            .dynamizer
+            .iter()
+            .map(|x| element("dynamizer", &[], &write_dynamizer(x))),
+    );
+
+    properties
+}
+
+fn write_external_reference(external_reference: &ExternalReference) -> String {
+    let mut content = text_element("targetResource", &external_reference.target_resource);
+    if let Some(x) = &external_reference.information_system {
+        content.push_str(&text_element("informationSystem", x));
+    }
+    if let Some(x) = &external_reference.relation_type {
+        content.push_str(&text_element("relationType", x));
+    }
+
+    element("ExternalReference", &[], &content)
+}
+
+pub fn write_generic_attribute(generic_attribute: &GenericAttribute) -> String {
+    let name = text_element("gen:name", generic_attribute.name());
+
+    match generic_attribute {
+        GenericAttribute::String(x) => element(
+            "gen:StringAttribute",
+            &[],
+            &(name + &text_element("gen:value", &x.value)),
+        ),
+        GenericAttribute::Int(x) => element(
+            "gen:IntAttribute",
+            &[],
+            &(name + &text_element("gen:value", &x.value.to_string())),
+        ),
+        GenericAttribute::Double(x) => element(
+            "gen:DoubleAttribute",
+            &[],
+            &(name + &text_element("gen:value", &x.value.to_string())),
+        ),
+        GenericAttribute::Date(x) => {
+            let timezone = x.timezone.map(|x| x.to_string()).unwrap_or_default();
+            let value = text_element("gen:value", &format!("{}{timezone}", x.value));
+            element("gen:DateAttribute", &[], &(name + &value))
+        }
+        GenericAttribute::Uri(x) => element(
+            "gen:UriAttribute",
+            &[],
+            &(name + &text_element("gen:value", &x.value)),
+        ),
+        GenericAttribute::Measure(x) => {
+            let value = element("gen:value", &[("uom", &x.uom)], &x.value.to_string());
+            element("gen:MeasureAttribute", &[], &(name + &value))
+        }
+        GenericAttribute::Code(x) => {
+            let value = match &x.code_space {
+                Some(code_space) => element(
+                    "gen:value",
+                    &[("codeSpace", code_space)],
+                    &quick_xml::escape::escape(x.value.as_str()),
+                ),
+                None => text_element("gen:value", &x.value),
+            };
+            element("gen:CodeAttribute", &[], &(name + &value))
+        }
+        GenericAttribute::Set(x) => {
+            let mut content = name;
+            if let Some(code_space) = &x.code_space {
+                content.push_str(&text_element("gen:codeSpace", code_space));
+            }
+            content.extend(
+                x.generic_attribute
+                    .iter()
+                    .map(|x| element("gen:genericAttribute", &[], &write_generic_attribute(x))),
+            );
+            element("gen:GenericAttributeSet", &[], &content)
+        }
+    }
+}
+
+pub fn write_dynamizer(dynamizer: &Dynamizer) -> String {
+    let mut content = text_element("dyn:attributeRef", &dynamizer.attribute_ref);
+    if let Some(x) = &dynamizer.start_time {
+        content.push_str(&text_element("dyn:startTime", &write_date_time(x)));
+    }
+    if let Some(x) = &dynamizer.end_time {
+        content.push_str(&text_element("dyn:endTime", &write_date_time(x)));
+    }
+    if let Some(x) = &dynamizer.dynamic_data {
+        let timeseries = match x {
+            Timeseries::Atomic(x) => write_atomic_timeseries(x),
+            Timeseries::TabulatedFile(x) => write_tabulated_file_timeseries(x),
+        };
+        content.push_str(&element("dyn:dynamicData", &[], &timeseries));
+    }
+
+    element(
+        "dyn:Dynamizer",
+        &[("gml:id", &dynamizer.abstract_gml.id.to_string())],
+        &content,
+    )
+}
+
+fn write_atomic_timeseries(timeseries: &AtomicTimeseries) -> String {
+    let mut content = write_timeseries_properties(
+        &timeseries.observation_property,
+        &timeseries.uom,
+        &timeseries.first_timestamp,
+        &timeseries.last_timestamp,
+    );
+    content.extend(
+        timeseries
+            .component
+            .iter()
+            .map(|x| element("dyn:component", &[], &write_time_value_pair(x))),
+    );
+
+    element(
+        "dyn:AtomicTimeseries",
+        &[("gml:id", &timeseries.abstract_gml.id.to_string())],
+        &content,
+    )
+}
+
+fn write_time_value_pair(time_value_pair: &TimeValuePair) -> String {
+    let timestamp = text_element(
+        "dyn:timestamp",
+        &write_date_time(&time_value_pair.timestamp),
+    );
+    let value = match &time_value_pair.value {
+        TimeseriesValue::Int(x) => text_element("dyn:intValue", &x.to_string()),
+        TimeseriesValue::Double(x) => text_element("dyn:doubleValue", &x.to_string()),
+        TimeseriesValue::String(x) => text_element("dyn:stringValue", x),
+        TimeseriesValue::Bool(x) => text_element("dyn:boolValue", &x.to_string()),
+        TimeseriesValue::Uri(x) => text_element("dyn:uriValue", x),
+    };
+
+    element("dyn:TimeValuePair", &[], &(timestamp + &value))
+}
+
+fn write_tabulated_file_timeseries(timeseries: &TabulatedFileTimeseries) -> String {
+    let mut content = write_timeseries_properties(
+        &timeseries.observation_property,
+        &timeseries.uom,
+        &timeseries.first_timestamp,
+        &timeseries.last_timestamp,
+    );
+    content.push_str(&text_element("dyn:fileLocation", &timeseries.file_location));
+
+    let optional_properties = [
+        ("dyn:fileType", timeseries.file_type.clone()),
+        ("dyn:mimeType", timeseries.mime_type.clone()),
+        ("dyn:valueType", timeseries.value_type.clone()),
+        (
+            "dyn:numberOfHeaderLines",
+            timeseries.number_of_header_lines.map(|x| x.to_string()),
+        ),
+        ("dyn:fieldSeparator", timeseries.field_separator.clone()),
+        ("dyn:decimalSymbol", timeseries.decimal_symbol.clone()),
+        (
+            "dyn:idColumnNo",
+            timeseries.id_column_no.map(|x| x.to_string()),
+        ),
+        ("dyn:idColumnName", timeseries.id_column_name.clone()),
+        ("dyn:idValue", timeseries.id_value.clone()),
+        (
+            "dyn:timeColumnNo",
+            timeseries.time_column_no.map(|x| x.to_string()),
+        ),
+        ("dyn:timeColumnName", timeseries.time_column_name.clone()),
+        ("dyn:timeFormat", timeseries.time_format.clone()),
+        (
+            "dyn:valueColumnNo",
+            timeseries.value_column_no.map(|x| x.to_string()),
+        ),
+        ("dyn:valueColumnName", timeseries.value_column_name.clone()),
+    ];
+    content.extend(
+        optional_properties
+            .iter()
+            .filter_map(|(name, x)| x.as_ref().map(|x| text_element(name, x))),
+    );
+
+    element(
+        "dyn:TabulatedFileTimeseries",
+        &[("gml:id", &timeseries.abstract_gml.id.to_string())],
+        &content,
+    )
+}
+
+fn write_timeseries_properties(
+    observation_property: &Option<String>,
+    uom: &Option<String>,
+    first_timestamp: &Option<DateTime<Utc>>,
+    last_timestamp: &Option<DateTime<Utc>>,
+) -> String {
+    let mut content = String::new();
+    if let Some(x) = observation_property {
+        content.push_str(&text_element("dyn:observationProperty", x));
+    }
+    if let Some(x) = uom {
+        content.push_str(&text_element("dyn:uom", x));
+    }
+    if let Some(x) = first_timestamp {
+        content.push_str(&text_element("dyn:firstTimestamp", &write_date_time(x)));
+    }
+    if let Some(x) = last_timestamp {
+        content.push_str(&text_element("dyn:lastTimestamp", &write_date_time(x)));
+    }
+
+    content
+}
+
+fn write_point_cloud(point_cloud: &PointCloud) -> String {
+    let mut content = String::new();
+    if let Some(x) = &point_cloud.mime_type {
+        content.push_str(&text_element("pcl:mimeType", x));
+    }
+    if let Some(x) = &point_cloud.point_file {
+        content.push_str(&text_element("pcl:pointFile", x));
+    }
+    if let Some(x) = &point_cloud.point_file_srs_name {
+        content.push_str(&text_element("pcl:pointFileSrsName", x));
+    }
+    if let Some(x) = &point_cloud.points {
+        content.push_str(&element("pcl:points", &[], &write_multi_point(x)));
+    }
+
+    element(
+        "pcl:PointCloud",
+        &[("gml:id", &point_cloud.abstract_gml.id.to_string())],
+        &content,
+    )
+}
+
+pub fn write_date_time(date_time: &DateTime<Utc>) -> String {
+    date_time.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
@@ -0,0 +1,122 @@
+use crate::writer::{element, href_element};
+use ecitygml_core::model::core::ImplicitGeometry;
+use ecitygml_core::model::point_cloud::MultiPoint;
+use egml::model::base::Id;
+use egml::model::geometry::{DirectPosition, LinearRing, MultiSurface, Polygon, Solid};
+use egml::operations::geometry::Geometry;
+
+pub fn write_multi_surface(multi_surface: &MultiSurface) -> String {
+    let surface_members: String = multi_surface
+        .surface_member()
+        .iter()
+        .map(|x| element("gml:surfaceMember", &[], &write_polygon(x)))
+        .collect();
+
+    element(
+        "gml:MultiSurface",
+        &[("gml:id", &multi_surface.abstract_gml.id.to_string())],
+        &surface_members,
+    )
+}
+
+pub fn write_solid(solid: &Solid) -> String {
+    let surface_members: String = solid
+        .members()
+        .iter()
+        .map(|x| match &x.linear_ring {
+            Some(linear_ring) => {
+                let polygon = element(
+                    "gml:Polygon",
+                    &[],
+                    &element("gml:exterior", &[], &write_linear_ring(linear_ring)),
+                );
+                element("gml:surfaceMember", &[], &polygon)
+            }
+            None => href_element(
+                "gml:surfaceMember",
+                &Id::try_from(x.href.trim_start_matches('#'))
+                    .unwrap_or_else(|_| Id::from_hashed_string(&x.href)),
+            ),
+        })
+        .collect();
+    let shell = element("gml:Shell", &[], &surface_members);
+
+    element(
+        "gml:Solid",
+        &[("gml:id", &solid.abstract_gml.id.to_string())],
+        &element("gml:exterior", &[], &shell),
+    )
+}
+
+pub fn write_polygon(polygon: &Polygon) -> String {
+    let mut content = element("gml:exterior", &[], &write_linear_ring(&polygon.exterior));
+    content.extend(
+        polygon
+            .interior
+            .iter()
+            .map(|x| element("gml:interior", &[], &write_linear_ring(x))),
+    );
+
+    element(
+        "gml:Polygon",
+        &[("gml:id", &polygon.abstract_gml.id.to_string())],
+        &content,
+    )
+}
+
+/// Writes the ring closed, i.e. with the first point repeated at the end.
+pub fn write_linear_ring(linear_ring: &LinearRing) -> String {
+    let mut points: Vec<&DirectPosition> = linear_ring.points();
+    if let Some(first) = points.first() {
+        points.push(first);
+    }
+
+    element(
+        "gml:LinearRing",
+        &[],
+        &element(
+            "gml:posList",
+            &[("srsDimension", "3")],
+            &write_coordinates(&points),
+        ),
+    )
+}
+
+pub fn write_point(point: &DirectPosition) -> String {
+    let pos = element(
+        "gml:pos",
+        &[("srsDimension", "3")],
+        &write_coordinates(&[point]),
+    );
+
+    element("gml:Point", &[], &pos)
+}
+
+pub fn write_multi_point(multi_point: &MultiPoint) -> String {
+    let point_members: String = multi_point
+        .point_member()
+        .iter()
+        .map(|x| element("gml:pointMember", &[], &write_point(x)))
+        .collect();
+
+    element("gml:MultiPoint", &[], &point_members)
+}
+
+pub fn write_implicit_geometry(implicit_geometry: &ImplicitGeometry) -> String {
+    let reference_point = element(
+        "referencePoint",
+        &[],
+        &write_point(&implicit_geometry.reference_point),
+    );
+
+    element("ImplicitGeometry", &[], &reference_point)
+}
+
+fn write_coordinates(points: &[&DirectPosition]) -> String {
+    points
+        .iter()
+        .flat_map(|x| x.coords())
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,32 @@
+pub mod building;
+pub mod city_object;
+pub mod geometry;
+pub mod transportation;
+pub mod versioning;
+
+use egml::model::base::Id;
+use quick_xml::escape::escape;
+
+/// Serializes an element with the given attributes and already serialized content.
+pub fn element(name: &str, attributes: &[(&str, &str)], content: &str) -> String {
+    let attributes: String = attributes
+        .iter()
+        .map(|(key, value)| format!(" {}=\"{}\"", key, escape(*value)))
+        .collect();
+
+    if content.is_empty() {
+        format!("<{name}{attributes}/>")
+    } else {
+        format!("<{name}{attributes}>{content}</{name}>")
+    }
+}
+
+/// Serializes an element containing the escaped text.
+pub fn text_element(name: &str, text: &str) -> String {
+    format!("<{}>{}</{}>", name, escape(text), name)
+}
+
+/// Serializes an element referencing another object by its id.
+pub fn href_element(name: &str, id: &Id) -> String {
+    element(name, &[("xlink:href", &format!("#{id}"))], "")
+}
@@ -0,0 +1,99 @@
+use crate::writer::city_object::{write_space, write_thematic_surface};
+use crate::writer::{element, href_element};
+use ecitygml_core::model::transportation::{
+    AuxiliaryTrafficSpace, Intersection, Road, Section, TrafficSpace,
+};
+
+pub fn write_road(road: &Road) -> String {
+    let mut children: Vec<String> = road
+        .section
+        .iter()
+        .map(|x| element("tran:section", &[], &write_section(x)))
+        .collect();
+    children.extend(
+        road.intersection
+            .iter()
+            .map(|x| element("tran:intersection", &[], &write_intersection(x))),
+    );
+
+    write_space("tran:Road", &road.space, children)
+}
+
+pub fn write_section(section: &Section) -> String {
+    let children = write_traffic_spaces(&section.traffic_space, &section.auxiliary_traffic_space);
+    write_space("tran:Section", &section.space, children)
+}
+
+pub fn write_intersection(intersection: &Intersection) -> String {
+    let children = write_traffic_spaces(
+        &intersection.traffic_space,
+        &intersection.auxiliary_traffic_space,
+    );
+    write_space("tran:Intersection", &intersection.space, children)
+}
+
+pub fn write_traffic_space(traffic_space: &TrafficSpace) -> String {
+    let mut children: Vec<String> = traffic_space
+        .traffic_area
+        .iter()
+        .map(|x| {
+            let traffic_area =
+                write_thematic_surface("tran:TrafficArea", &x.thematic_surface, Vec::new());
+            element("boundary", &[], &traffic_area)
+        })
+        .collect();
+    children.extend(
+        traffic_space
+            .predecessor
+            .iter()
+            .map(|x| href_element("tran:predecessor", x)),
+    );
+    children.extend(
+        traffic_space
+            .successor
+            .iter()
+            .map(|x| href_element("tran:successor", x)),
+    );
+
+    write_space("tran:TrafficSpace", &traffic_space.space, children)
+}
+
+pub fn write_auxiliary_traffic_space(auxiliary_traffic_space: &AuxiliaryTrafficSpace) -> String {
+    let children: Vec<String> = auxiliary_traffic_space
+        .auxiliary_traffic_area
+        .iter()
+        .map(|x| {
+            let auxiliary_traffic_area = write_thematic_surface(
+                "tran:AuxiliaryTrafficArea",
+                &x.thematic_surface,
+                Vec::new(),
+            );
+            element("boundary", &[], &auxiliary_traffic_area)
+        })
+        .collect();
+
+    write_space(
+        "tran:AuxiliaryTrafficSpace",
+        &auxiliary_traffic_space.space,
+        children,
+    )
+}
+
+fn write_traffic_spaces(
+    traffic_space: &[TrafficSpace],
+    auxiliary_traffic_space: &[AuxiliaryTrafficSpace],
+) -> Vec<String> {
+    let mut children: Vec<String> = traffic_space
+        .iter()
+        .map(|x| element("tran:trafficSpace", &[], &write_traffic_space(x)))
+        .collect();
+    children.extend(auxiliary_traffic_space.iter().map(|x| {
+        element(
+            "tran:auxiliaryTrafficSpace",
+            &[],
+            &write_auxiliary_traffic_space(x),
+        )
+    }));
+
+    children
+}
@@ -0,0 +1,73 @@
+use crate::writer::{element, href_element, text_element};
+use ecitygml_core::model::versioning::{Transaction, Version, VersionTransition};
+
+pub fn write_version(version: &Version) -> String {
+    let mut content: String = version
+        .abstract_gml
+        .name
+        .iter()
+        .map(|x| text_element("gml:name", x))
+        .collect();
+    content.extend(version.tag.iter().map(|x| text_element("vers:tag", x)));
+    content.extend(
+        version
+            .version_member
+            .iter()
+            .map(|x| href_element("vers:versionMember", x)),
+    );
+
+    element(
+        "vers:Version",
+        &[("gml:id", &version.abstract_gml.id.to_string())],
+        &content,
+    )
+}
+
+pub fn write_version_transition(version_transition: &VersionTransition) -> String {
+    let mut content: String = version_transition
+        .abstract_gml
+        .name
+        .iter()
+        .map(|x| text_element("gml:name", x))
+        .collect();
+    if let Some(x) = &version_transition.reason {
+        content.push_str(&text_element("vers:reason", x));
+    }
+    content.push_str(&text_element(
+        "vers:clonePredecessor",
+        &version_transition.clone_predecessor.to_string(),
+    ));
+    if let Some(x) = &version_transition.transition_type {
+        content.push_str(&text_element("vers:type", &x.to_string()));
+    }
+    if let Some(x) = &version_transition.from {
+        content.push_str(&href_element("vers:from", x));
+    }
+    if let Some(x) = &version_transition.to {
+        content.push_str(&href_element("vers:to", x));
+    }
+    content.extend(
+        version_transition
+            .transaction
+            .iter()
+            .map(|x| element("vers:transaction", &[], &write_transaction(x))),
+    );
+
+    element(
+        "vers:VersionTransition",
+        &[("gml:id", &version_transition.abstract_gml.id.to_string())],
+        &content,
+    )
+}
+
+fn write_transaction(transaction: &Transaction) -> String {
+    let mut content = text_element("vers:type", &transaction.transaction_type.to_string());
+    if let Some(x) = &transaction.old_feature {
+        content.push_str(&href_element("vers:oldFeature", x));
+    }
+    if let Some(x) = &transaction.new_feature {
+        content.push_str(&href_element("vers:newFeature", x));
+    }
+
+    element("vers:Transaction", &[], &content)
+}
@@ -6,4 +6,7 @@ pub enum Error {
     EcitygmlError(#[from] ecitygml_core::Error),
     #[error(transparent)]
     EgmlError(#[from] egml::Error),
+
+    #[error("no version with tag `{0}` found")]
+    VersionNotFound(String),
 }
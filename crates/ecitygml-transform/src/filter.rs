@@ -1,8 +1,10 @@
 use crate::error::Error;
 use ecitygml_core::model::city_model::CitygmlModel;
-use ecitygml_core::model::core::{OccupiedSpace, Space, ThematicSurface};
+use ecitygml_core::model::core::{CityObject, OccupiedSpace, Space, ThematicSurface};
+use egml::model::base::Id;
 use egml::model::geometry::Envelope;
 use egml::operations::geometry::Geometry;
+use std::collections::HashSet;
 
 pub fn filter_by_bounding_box(
     mut city_model: CitygmlModel,
@@ -18,6 +20,24 @@ pub fn filter_by_bounding_box(
             || f.ground_surface
                 .iter()
                 .any(|w| contains_thematic_surface(filter_envelope, &w.thematic_surface))
+            || f.ceiling_surface
+                .iter()
+                .any(|w| contains_thematic_surface(filter_envelope, &w.thematic_surface))
+            || f.floor_surface
+                .iter()
+                .any(|w| contains_thematic_surface(filter_envelope, &w.thematic_surface))
+            || f.interior_wall_surface
+                .iter()
+                .any(|w| contains_thematic_surface(filter_envelope, &w.thematic_surface))
+            || f.outer_ceiling_surface
+                .iter()
+                .any(|w| contains_thematic_surface(filter_envelope, &w.thematic_surface))
+            || f.outer_floor_surface
+                .iter()
+                .any(|w| contains_thematic_surface(filter_envelope, &w.thematic_surface))
+            || f.closure_surface
+                .iter()
+                .any(|w| contains_thematic_surface(filter_envelope, &w.thematic_surface))
             || f.building_constructive_element
                 .iter()
                 .any(|w| contains_occupied_space(filter_envelope, &w.occupied_space))
@@ -36,6 +56,47 @@ pub fn filter_by_bounding_box(
     Ok(city_model)
 }
 
+/// Materializes the state of the city model as of the version with the given tag.
+///
+/// Top-level features that are members of the version are kept, as well as features that
+/// do not belong to any version. Features belonging only to other versions are removed.
+pub fn filter_by_version_tag(
+    mut city_model: CitygmlModel,
+    version_tag: &str,
+) -> Result<CitygmlModel, Error> {
+    let version = city_model
+        .find_version_by_tag(version_tag)
+        .ok_or(Error::VersionNotFound(version_tag.to_string()))?
+        .clone();
+
+    let selected_ids: HashSet<&Id> = version.version_member_ids();
+    let versioned_ids: HashSet<Id> = city_model
+        .version
+        .iter()
+        .flat_map(|x| x.version_member.iter().cloned())
+        .collect();
+    let keep = |city_object: &CityObject| {
+        let id = &city_object.abstract_gml.id;
+        selected_ids.contains(id) || !versioned_ids.contains(id)
+    };
+
+    city_model
+        .building
+        .retain(|f| keep(&f.occupied_space.space.city_object));
+    city_model
+        .city_furniture
+        .retain(|f| keep(&f.occupied_space.space.city_object));
+    city_model.road.retain(|f| keep(&f.space.city_object));
+    city_model
+        .solitary_vegetation_object
+        .retain(|f| keep(&f.occupied_space.space.city_object));
+
+    city_model.version = vec![version];
+    city_model.version_transition.clear();
+
+    Ok(city_model)
+}
+
 fn contains_thematic_surface(
     filter_envelope: &Envelope,
     thematic_surface: &ThematicSurface,
@@ -119,3 +180,94 @@ fn contains_space(filter_envelope: &Envelope, space: &Space) -> bool {
 
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ecitygml_core::model::building::Building;
+    use ecitygml_core::model::city_furniture::CityFurniture;
+    use ecitygml_core::model::versioning::{Version, VersionTransition};
+    use egml::model::base::AbstractGml;
+
+    fn abstract_gml(id: &str) -> AbstractGml {
+        AbstractGml::new(Id::try_from(id).expect("should work"))
+    }
+
+    fn occupied_space(id: &str) -> OccupiedSpace {
+        OccupiedSpace::new(Space::new(CityObject::new(abstract_gml(id), Vec::new())))
+    }
+
+    fn version(id: &str, tag: &str, members: &[&str]) -> Version {
+        let mut version = Version::new(abstract_gml(id));
+        version.tag = vec![tag.to_string()];
+        version.version_member = members
+            .iter()
+            .map(|x| Id::try_from(*x).expect("should work"))
+            .collect();
+        version
+    }
+
+    fn city_model() -> CitygmlModel {
+        let mut city_model = CitygmlModel::new(
+            vec![
+                Building::new(occupied_space("building_1_2020")),
+                Building::new(occupied_space("building_1_2030")),
+                Building::new(occupied_space("building_2")),
+            ],
+            vec![CityFurniture::new(occupied_space("bench"))],
+            Vec::new(),
+            Vec::new(),
+        );
+        city_model.version = vec![
+            version("version_2020", "2020", &["building_1_2020"]),
+            version("version_2030", "2030", &["building_1_2030"]),
+        ];
+        city_model.version_transition = vec![VersionTransition::new(abstract_gml("transition"))];
+        city_model
+    }
+
+    fn building_ids(city_model: &CitygmlModel) -> Vec<String> {
+        city_model
+            .building
+            .iter()
+            .map(|x| {
+                x.occupied_space
+                    .space
+                    .city_object
+                    .abstract_gml
+                    .id
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_by_version_tag_keeps_members_and_unversioned_features() {
+        let filtered = filter_by_version_tag(city_model(), "2030").expect("should work");
+
+        assert_eq!(
+            building_ids(&filtered),
+            vec!["building_1_2030", "building_2"]
+        );
+        assert_eq!(filtered.city_furniture.len(), 1);
+        assert_eq!(filtered.version.len(), 1);
+        assert_eq!(
+            filtered.version[0].abstract_gml.id.to_string(),
+            "version_2030"
+        );
+        assert!(filtered.version_transition.is_empty());
+
+        let filtered = filter_by_version_tag(city_model(), "2020").expect("should work");
+        assert_eq!(
+            building_ids(&filtered),
+            vec!["building_1_2020", "building_2"]
+        );
+    }
+
+    #[test]
+    fn test_filter_by_unknown_version_tag() {
+        let result = filter_by_version_tag(city_model(), "2040");
+
+        assert!(matches!(result, Err(Error::VersionNotFound(x)) if x == "2040"));
+    }
+}
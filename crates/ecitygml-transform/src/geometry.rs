@@ -0,0 +1,80 @@
+use ecitygml_core::operations::{polygon_normal, ring_vectors};
+use egml::model::geometry::{DirectPosition, Polygon};
+use nalgebra::Vector3;
+
+/// Returns the signed volume of the tetrahedra between `reference` and a fan triangulation of
+/// the face.
+///
+/// The reference should be a vertex of the shell, since the products of georeferenced
+/// coordinates would cancel out the volume of small solids.
+pub(crate) fn signed_volume(face: &[DirectPosition], reference: &Vector3<f64>) -> f64 {
+    let vertices: Vec<Vector3<f64>> = face.iter().map(|x| Vector3::from(x) - reference).collect();
+    let Some(first) = vertices.first() else {
+        return 0.0;
+    };
+    vertices
+        .windows(2)
+        .skip(1)
+        .map(|x| first.dot(&x[0].cross(&x[1])) / 6.0)
+        .sum()
+}
+
+/// Returns the slope in degrees and, unless the normal is vertical, the azimuth in degrees
+/// clockwise from north of the upward facing normal.
+///
+/// The x-axis of the coordinate reference system is assumed to point east and the y-axis
+/// north.
+pub(crate) fn slope_and_azimuth(normal: &Vector3<f64>) -> (f64, Option<f64>) {
+    let upward_normal = if normal.z < 0.0 { -normal } else { *normal };
+    let slope = upward_normal.z.clamp(0.0, 1.0).acos().to_degrees();
+    let azimuth = (upward_normal.xy().norm() > f64::EPSILON).then(|| {
+        upward_normal
+            .x
+            .atan2(upward_normal.y)
+            .to_degrees()
+            .rem_euclid(360.0)
+    });
+
+    (slope, azimuth)
+}
+
+/// Returns the area-weighted centroid of the polygon.
+pub(crate) fn polygon_centroid(polygon: &Polygon) -> Option<Vector3<f64>> {
+    let normal = polygon_normal(polygon)?;
+    let (exterior_area, exterior_moment) = ring_moment(&ring_vectors(&polygon.exterior), &normal);
+    let (area, moment) = polygon
+        .interior
+        .iter()
+        .map(|x| ring_moment(&ring_vectors(x), &normal))
+        .fold((exterior_area, exterior_moment), |(area, moment), x| {
+            (area - x.0, moment - x.1)
+        });
+
+    (area > f64::EPSILON).then(|| moment / area)
+}
+
+/// Returns the area of the ring and its centroid multiplied by the area using a fan
+/// triangulation.
+fn ring_moment(ring: &[Vector3<f64>], normal: &Vector3<f64>) -> (f64, Vector3<f64>) {
+    let Some(first) = ring.first() else {
+        return (0.0, Vector3::zeros());
+    };
+    let (signed_area, signed_moment) = ring.windows(2).skip(1).fold(
+        (0.0, Vector3::zeros()),
+        |(area, moment): (f64, Vector3<f64>), x| {
+            let triangle_area = (x[0] - first).cross(&(x[1] - first)).dot(normal) / 2.0;
+            let triangle_centroid = (first + x[0] + x[1]) / 3.0;
+            (
+                area + triangle_area,
+                moment + triangle_centroid * triangle_area,
+            )
+        },
+    );
+
+    // rings may be oriented opposite to the normal
+    if signed_area < 0.0 {
+        (-signed_area, -signed_moment)
+    } else {
+        (signed_area, signed_moment)
+    }
+}
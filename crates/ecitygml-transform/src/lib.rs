@@ -1,5 +1,9 @@
 mod error;
 pub mod filter;
+mod geometry;
+pub mod metrics;
+pub mod repair;
+pub mod roof;
 
 #[doc(inline)]
 pub use crate::error::Error;
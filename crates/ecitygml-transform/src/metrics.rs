@@ -0,0 +1,562 @@
+use crate::geometry::{signed_volume, slope_and_azimuth};
+use ecitygml_core::model::building::Building;
+use ecitygml_core::model::city_model::CitygmlModel;
+use ecitygml_core::model::common::CityObjectClass;
+use ecitygml_core::model::core::{Space, ThematicSurface};
+use ecitygml_core::operations::{polygon_area, polygon_normal};
+use egml::model::geometry::{DirectPosition, MultiSurface, Polygon, Solid};
+use egml::operations::geometry::Geometry;
+use nalgebra::Vector3;
+
+/// Maximum slope in degrees of roof polygons counted as flat.
+pub const FLAT_ROOF_MAXIMUM_SLOPE: f64 = 5.0;
+
+/// Area divided by the cardinal direction the surfaces face.
+///
+/// The direction is derived from the horizontal component of the surface normal, assuming that
+/// the x-axis of the coordinate reference system points east and the y-axis north. Surfaces
+/// with a slope of at most [`FLAT_ROOF_MAXIMUM_SLOPE`] are counted as flat.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct OrientedArea {
+    pub flat: f64,
+    pub north: f64,
+    pub east: f64,
+    pub south: f64,
+    pub west: f64,
+}
+
+impl OrientedArea {
+    pub fn total(&self) -> f64 {
+        self.flat + self.north + self.east + self.south + self.west
+    }
+
+    fn add(&mut self, polygon: &Polygon) {
+        let area = polygon_area(polygon);
+        let Some(normal) = polygon_normal(polygon) else {
+            return;
+        };
+
+        let (slope, azimuth) = slope_and_azimuth(&normal);
+        let Some(azimuth) = azimuth.filter(|_| slope > FLAT_ROOF_MAXIMUM_SLOPE) else {
+            self.flat += area;
+            return;
+        };
+        match azimuth {
+            x if !(45.0..315.0).contains(&x) => self.north += area,
+            x if x < 135.0 => self.east += area,
+            x if x < 225.0 => self.south += area,
+            _ => self.west += area,
+        }
+    }
+}
+
+/// Metrics of a city object computed from its geometries in the unit of the coordinate
+/// reference system.
+///
+/// Metrics are `None`, if the city object has no geometry to derive them from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CityObjectMetrics {
+    pub gml_id: String,
+    pub class: CityObjectClass,
+    /// Area of the ground surfaces projected onto the xy-plane, or of the LOD0 multi surface.
+    pub footprint_area: Option<f64>,
+    /// Area of the roof surfaces divided by their orientation.
+    pub roof_area: Option<OrientedArea>,
+    /// Area of the wall surfaces.
+    pub wall_area: Option<f64>,
+    /// Volume enclosed by the `lod2Solid`, or `None`, if the shell references faces that cannot
+    /// be resolved.
+    pub volume: Option<f64>,
+    /// Difference between the highest roof vertex and the lowest ground vertex, or the vertical
+    /// extent of the space geometries, if the boundary surfaces are missing.
+    pub height: Option<f64>,
+}
+
+impl CityObjectMetrics {
+    /// Computes the metrics of a space without thematic boundary surfaces.
+    pub fn from_space(class: CityObjectClass, space: &Space) -> Self {
+        Self {
+            gml_id: space.city_object.abstract_gml.id.to_string(),
+            class,
+            footprint_area: space
+                .lod0_multi_surface
+                .as_ref()
+                .map(|x| projected_area(x.surface_member())),
+            roof_area: None,
+            wall_area: None,
+            volume: space.lod2_solid.as_ref().and_then(|x| solid_volume(x, &[])),
+            height: vertical_extent(space_points(space)),
+        }
+    }
+
+    /// Computes the metrics of a building from its boundary surfaces and `lod2Solid`.
+    ///
+    /// The polygons of the highest LOD of each boundary surface are used. Faces of the
+    /// `lod2Solid` referenced by href are resolved among the polygons of all LODs of the
+    /// boundary surfaces.
+    pub fn from_building(building: &Building) -> Self {
+        let space = &building.occupied_space.space;
+        let mut metrics = Self::from_space(CityObjectClass::Building, space);
+        metrics.volume = space
+            .lod2_solid
+            .as_ref()
+            .and_then(|x| solid_volume(x, &boundary_polygons(building)));
+
+        let ground_polygons: Vec<&Polygon> = building
+            .ground_surface
+            .iter()
+            .flat_map(|x| highest_lod_polygons(&x.thematic_surface))
+            .collect();
+        let roof_polygons: Vec<&Polygon> = building
+            .roof_surface
+            .iter()
+            .flat_map(|x| highest_lod_polygons(&x.thematic_surface))
+            .collect();
+
+        if !ground_polygons.is_empty() {
+            metrics.footprint_area = Some(projected_area(ground_polygons.iter().copied()));
+        }
+        if !roof_polygons.is_empty() {
+            let mut roof_area = OrientedArea::default();
+            roof_polygons.iter().for_each(|x| roof_area.add(x));
+            metrics.roof_area = Some(roof_area);
+        }
+        if !building.wall_surface.is_empty() {
+            metrics.wall_area = Some(
+                building
+                    .wall_surface
+                    .iter()
+                    .map(|x| thematic_surface_area(&x.thematic_surface))
+                    .sum(),
+            );
+        }
+
+        let max_roof_z = roof_polygons
+            .iter()
+            .flat_map(|x| x.points())
+            .map(DirectPosition::z)
+            .reduce(f64::max);
+        let min_ground_z = ground_polygons
+            .iter()
+            .flat_map(|x| x.points())
+            .map(DirectPosition::z)
+            .reduce(f64::min);
+        if let (Some(max_roof_z), Some(min_ground_z)) = (max_roof_z, min_ground_z) {
+            metrics.height = Some(max_roof_z - min_ground_z);
+        }
+
+        metrics
+    }
+}
+
+/// Computes the metrics of all buildings, city furniture and solitary vegetation objects.
+pub fn compute_metrics(city_model: &CitygmlModel) -> Vec<CityObjectMetrics> {
+    let buildings = city_model
+        .building
+        .iter()
+        .map(CityObjectMetrics::from_building);
+    let city_furniture = city_model.city_furniture.iter().map(|x| {
+        CityObjectMetrics::from_space(CityObjectClass::CityFurniture, &x.occupied_space.space)
+    });
+    let solitary_vegetation_objects = city_model.solitary_vegetation_object.iter().map(|x| {
+        CityObjectMetrics::from_space(
+            CityObjectClass::SolitaryVegetationObject,
+            &x.occupied_space.space,
+        )
+    });
+
+    buildings
+        .chain(city_furniture)
+        .chain(solitary_vegetation_objects)
+        .collect()
+}
+
+/// Returns the area of the polygons of the highest LOD of the thematic surface.
+pub fn thematic_surface_area(thematic_surface: &ThematicSurface) -> f64 {
+    highest_lod_polygons(thematic_surface)
+        .iter()
+        .map(polygon_area)
+        .sum()
+}
+
+/// Returns the volume enclosed by the solid, which is independent of the orientation of the
+/// shell as a whole.
+///
+/// Faces referenced by href are resolved among the boundary polygons by their `gml:id`. The
+/// volume is `None`, if a referenced face cannot be resolved or the solid has no faces.
+pub fn solid_volume(solid: &Solid, boundary_polygons: &[&Polygon]) -> Option<f64> {
+    let mut rings: Vec<Vec<DirectPosition>> = Vec::new();
+    for member in solid.members() {
+        if let Some(linear_ring) = &member.linear_ring {
+            rings.push(linear_ring.points().into_iter().copied().collect());
+            continue;
+        }
+        let polygon = boundary_polygons
+            .iter()
+            .find(|x| x.abstract_gml.id.to_string() == member.href.trim_start_matches('#'))?;
+        rings.extend(
+            std::iter::once(&polygon.exterior)
+                .chain(&polygon.interior)
+                .map(|x| x.points().into_iter().copied().collect()),
+        );
+    }
+
+    let reference = Vector3::from(rings.first()?.first()?);
+    Some(
+        rings
+            .iter()
+            .map(|x| signed_volume(x, &reference))
+            .sum::<f64>()
+            .abs(),
+    )
+}
+
+pub(crate) fn highest_lod_polygons(thematic_surface: &ThematicSurface) -> &[Polygon] {
+    [
+        &thematic_surface.lod3_multi_surface,
+        &thematic_surface.lod2_multi_surface,
+        &thematic_surface.lod1_multi_surface,
+        &thematic_surface.lod0_multi_surface,
+    ]
+    .into_iter()
+    .flatten()
+    .map(|x| x.surface_member().as_slice())
+    .next()
+    .unwrap_or_default()
+}
+
+/// Returns the polygons of all LODs of the outer boundary surfaces of the building.
+fn boundary_polygons(building: &Building) -> Vec<&Polygon> {
+    let thematic_surfaces = building
+        .wall_surface
+        .iter()
+        .map(|x| &x.thematic_surface)
+        .chain(building.roof_surface.iter().map(|x| &x.thematic_surface))
+        .chain(building.ground_surface.iter().map(|x| &x.thematic_surface))
+        .chain(building.closure_surface.iter().map(|x| &x.thematic_surface))
+        .chain(
+            building
+                .outer_ceiling_surface
+                .iter()
+                .map(|x| &x.thematic_surface),
+        )
+        .chain(
+            building
+                .outer_floor_surface
+                .iter()
+                .map(|x| &x.thematic_surface),
+        );
+
+    thematic_surfaces
+        .flat_map(|x| {
+            [
+                &x.lod0_multi_surface,
+                &x.lod1_multi_surface,
+                &x.lod2_multi_surface,
+                &x.lod3_multi_surface,
+            ]
+        })
+        .flatten()
+        .flat_map(MultiSurface::surface_member)
+        .collect()
+}
+
+/// Returns the area of the polygons projected onto the xy-plane.
+fn projected_area<'a>(polygons: impl IntoIterator<Item = &'a Polygon>) -> f64 {
+    polygons
+        .into_iter()
+        .map(|x| {
+            let normal = polygon_normal(x).unwrap_or_else(Vector3::zeros);
+            polygon_area(x) * normal.z.abs()
+        })
+        .sum()
+}
+
+fn space_points(space: &Space) -> Vec<&DirectPosition> {
+    let solids = [&space.lod1_solid, &space.lod2_solid, &space.lod3_solid]
+        .into_iter()
+        .flatten()
+        .flat_map(Solid::points);
+    let multi_surfaces = [
+        &space.lod0_multi_surface,
+        &space.lod2_multi_surface,
+        &space.lod3_multi_surface,
+    ]
+    .into_iter()
+    .flatten()
+    .flat_map(MultiSurface::points);
+
+    solids.chain(multi_surfaces).collect()
+}
+
+fn vertical_extent(points: Vec<&DirectPosition>) -> Option<f64> {
+    let min_z = points.iter().map(|x| x.z()).reduce(f64::min)?;
+    let max_z = points.iter().map(|x| x.z()).reduce(f64::max)?;
+    Some(max_z - min_z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ecitygml_core::model::city_furniture::CityFurniture;
+    use ecitygml_core::model::construction::{GroundSurface, RoofSurface, WallSurface};
+    use ecitygml_core::model::core::{CityObject, OccupiedSpace};
+    use egml::model::base::{AbstractGml, Id};
+    use egml::model::geometry::{LinearRing, SurfaceProperty};
+    use nalgebra::Rotation3;
+
+    fn abstract_gml(id: &str) -> AbstractGml {
+        AbstractGml::new(Id::try_from(id).expect("should work"))
+    }
+
+    fn ring(id: &str, coords: &[[f64; 3]]) -> LinearRing {
+        LinearRing::new(
+            abstract_gml(id),
+            coords
+                .iter()
+                .map(|x| DirectPosition::new(x[0], x[1], x[2]).expect("should work"))
+                .collect(),
+        )
+        .expect("should work")
+    }
+
+    fn polygon(id: &str, coords: &[[f64; 3]]) -> Polygon {
+        Polygon::new(
+            abstract_gml(id),
+            ring(&format!("{id}_exterior"), coords),
+            Vec::new(),
+        )
+        .expect("should work")
+    }
+
+    fn thematic_surface(id: &str, polygons: Vec<Polygon>) -> ThematicSurface {
+        let mut thematic_surface =
+            ThematicSurface::new(CityObject::new(abstract_gml(id), Vec::new()));
+        thematic_surface.lod2_multi_surface = Some(
+            MultiSurface::new(abstract_gml(&format!("{id}_geometry")), polygons)
+                .expect("should work"),
+        );
+        thematic_surface
+    }
+
+    /// Returns the outward oriented faces of the box between the offset and the offset moved by
+    /// the corner.
+    fn box_faces(corner: [f64; 3], offset: [f64; 3]) -> [[[f64; 3]; 4]; 6] {
+        let [x, y, z] = corner;
+        [
+            [[0.0, 0.0, 0.0], [0.0, y, 0.0], [x, y, 0.0], [x, 0.0, 0.0]],
+            [[0.0, 0.0, z], [x, 0.0, z], [x, y, z], [0.0, y, z]],
+            [[0.0, 0.0, 0.0], [x, 0.0, 0.0], [x, 0.0, z], [0.0, 0.0, z]],
+            [[0.0, y, 0.0], [0.0, y, z], [x, y, z], [x, y, 0.0]],
+            [[0.0, 0.0, 0.0], [0.0, 0.0, z], [0.0, y, z], [0.0, y, 0.0]],
+            [[x, 0.0, 0.0], [x, y, 0.0], [x, y, z], [x, 0.0, z]],
+        ]
+        .map(|face| face.map(|v| [v[0] + offset[0], v[1] + offset[1], v[2] + offset[2]]))
+    }
+
+    /// Returns the outward oriented box between the origin and the corner.
+    fn box_solid(id: &str, corner: [f64; 3]) -> Solid {
+        let members = box_faces(corner, [0.0, 0.0, 0.0])
+            .iter()
+            .enumerate()
+            .map(|(index, face)| {
+                SurfaceProperty::new(String::new(), Some(ring(&format!("{id}_{index}"), face)))
+                    .expect("should work")
+            })
+            .collect();
+        Solid::new(abstract_gml(id), members).expect("should work")
+    }
+
+    /// Returns a building with a box of 10 × 8 × 5, whose `lod2Solid` references the faces
+    /// given as wall surface polygons by href.
+    fn building_with_referenced_faces(offset: [f64; 3], face_count: usize) -> Building {
+        let faces = box_faces([10.0, 8.0, 5.0], offset);
+        let members = (0..faces.len())
+            .map(|index| SurfaceProperty::new(format!("#face_{index}"), None).expect("should work"))
+            .collect();
+        let mut space = Space::new(CityObject::new(abstract_gml("building"), Vec::new()));
+        space.lod2_solid = Some(Solid::new(abstract_gml("solid"), members).expect("should work"));
+        let mut building = Building::new(OccupiedSpace::new(space));
+
+        let polygons = faces
+            .iter()
+            .take(face_count)
+            .enumerate()
+            .map(|(index, face)| polygon(&format!("face_{index}"), face))
+            .collect();
+        building.wall_surface = vec![WallSurface::new(thematic_surface("wall", polygons))];
+        building
+    }
+
+    /// Returns a roof polygon with a slope of 45° facing south, which is rotated clockwise by
+    /// the azimuth and has an area of `width * sqrt(2)`.
+    fn tilted_polygon(id: &str, width: f64, azimuth: f64) -> Polygon {
+        let rotation =
+            Rotation3::from_axis_angle(&Vector3::z_axis(), -(azimuth - 180.0).to_radians());
+        let coords = [
+            [0.0, 0.0, 0.0],
+            [width, 0.0, 0.0],
+            [width, 1.0, 1.0],
+            [0.0, 1.0, 1.0],
+        ]
+        .map(|x| (rotation * Vector3::from(x)).into());
+        polygon(id, &coords)
+    }
+
+    /// Returns a building of 10 × 8 with walls of 5 and a gable roof with the ridge at 8.
+    fn gable_roof_building() -> Building {
+        let mut space = Space::new(CityObject::new(abstract_gml("building"), Vec::new()));
+        space.lod2_solid = Some(box_solid("solid", [10.0, 8.0, 5.0]));
+        let mut building = Building::new(OccupiedSpace::new(space));
+
+        building.ground_surface = vec![GroundSurface::new(thematic_surface(
+            "ground",
+            vec![polygon(
+                "ground_polygon",
+                &[
+                    [0.0, 0.0, 0.0],
+                    [0.0, 8.0, 0.0],
+                    [10.0, 8.0, 0.0],
+                    [10.0, 0.0, 0.0],
+                ],
+            )],
+        ))];
+        building.roof_surface = vec![RoofSurface::new(thematic_surface(
+            "roof",
+            vec![
+                polygon(
+                    "roof_south",
+                    &[
+                        [0.0, 0.0, 5.0],
+                        [10.0, 0.0, 5.0],
+                        [10.0, 4.0, 8.0],
+                        [0.0, 4.0, 8.0],
+                    ],
+                ),
+                polygon(
+                    "roof_north",
+                    &[
+                        [10.0, 8.0, 5.0],
+                        [0.0, 8.0, 5.0],
+                        [0.0, 4.0, 8.0],
+                        [10.0, 4.0, 8.0],
+                    ],
+                ),
+            ],
+        ))];
+        building.wall_surface = vec![WallSurface::new(thematic_surface(
+            "wall",
+            vec![
+                polygon(
+                    "wall_south",
+                    &[
+                        [0.0, 0.0, 0.0],
+                        [10.0, 0.0, 0.0],
+                        [10.0, 0.0, 5.0],
+                        [0.0, 0.0, 5.0],
+                    ],
+                ),
+                polygon(
+                    "wall_west",
+                    &[
+                        [0.0, 0.0, 0.0],
+                        [0.0, 0.0, 5.0],
+                        [0.0, 8.0, 5.0],
+                        [0.0, 8.0, 0.0],
+                    ],
+                ),
+            ],
+        ))];
+        building
+    }
+
+    #[test]
+    fn test_building_metrics() {
+        let metrics = CityObjectMetrics::from_building(&gable_roof_building());
+
+        assert_eq!(metrics.gml_id, "building");
+        assert_eq!(metrics.class, CityObjectClass::Building);
+        assert!((metrics.footprint_area.expect("should be present") - 80.0).abs() < 1e-9);
+        assert!((metrics.wall_area.expect("should be present") - 90.0).abs() < 1e-9);
+        assert!((metrics.volume.expect("should be present") - 400.0).abs() < 1e-9);
+        assert!((metrics.height.expect("should be present") - 8.0).abs() < 1e-9);
+
+        let roof_area = metrics.roof_area.expect("should be present");
+        assert!((roof_area.south - 50.0).abs() < 1e-9);
+        assert!((roof_area.north - 50.0).abs() < 1e-9);
+        assert_eq!(roof_area.flat + roof_area.east + roof_area.west, 0.0);
+    }
+
+    #[test]
+    fn test_oriented_area_splits_by_direction() {
+        let mut oriented_area = OrientedArea::default();
+        for (index, azimuth) in [0.0, 90.0, 180.0, 270.0, 30.0].into_iter().enumerate() {
+            let width = index as f64 + 1.0;
+            oriented_area.add(&tilted_polygon(&format!("roof_{index}"), width, azimuth));
+        }
+        // slope of 3° below the flat roof threshold
+        let rise = 3.0_f64.to_radians().tan();
+        oriented_area.add(&polygon(
+            "roof_flat",
+            &[
+                [0.0, 0.0, 0.0],
+                [2.0, 0.0, 0.0],
+                [2.0, 1.0, rise],
+                [0.0, 1.0, rise],
+            ],
+        ));
+
+        let sqrt_2 = 2.0_f64.sqrt();
+        assert!((oriented_area.north - 6.0 * sqrt_2).abs() < 1e-9);
+        assert!((oriented_area.east - 2.0 * sqrt_2).abs() < 1e-9);
+        assert!((oriented_area.south - 3.0 * sqrt_2).abs() < 1e-9);
+        assert!((oriented_area.west - 4.0 * sqrt_2).abs() < 1e-9);
+        assert!((oriented_area.flat - 2.0 * rise.hypot(1.0)).abs() < 1e-9);
+        assert!((oriented_area.total() - (15.0 * sqrt_2 + 2.0 * rise.hypot(1.0))).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_space_metrics_without_boundary_surfaces() {
+        let mut space = Space::new(CityObject::new(abstract_gml("bench"), Vec::new()));
+        space.lod2_solid = Some(box_solid("bench_solid", [2.0, 0.5, 0.8]));
+        let city_model = CitygmlModel::new(
+            Vec::new(),
+            vec![CityFurniture::new(OccupiedSpace::new(space))],
+            Vec::new(),
+            Vec::new(),
+        );
+
+        let metrics = compute_metrics(&city_model);
+
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].class, CityObjectClass::CityFurniture);
+        assert_eq!(metrics[0].footprint_area, None);
+        assert_eq!(metrics[0].roof_area, None);
+        assert!((metrics[0].volume.expect("should be present") - 0.8).abs() < 1e-9);
+        assert!((metrics[0].height.expect("should be present") - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_building_volume_resolves_referenced_faces() {
+        let metrics =
+            CityObjectMetrics::from_building(&building_with_referenced_faces([0.0; 3], 6));
+
+        assert!((metrics.volume.expect("should be present") - 400.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_building_volume_with_unresolved_face() {
+        let metrics =
+            CityObjectMetrics::from_building(&building_with_referenced_faces([0.0; 3], 5));
+
+        assert_eq!(metrics.volume, None);
+    }
+
+    #[test]
+    fn test_building_volume_at_projected_coordinates() {
+        let offset = [32690123.457, 5336789.013, 512.3];
+        let metrics = CityObjectMetrics::from_building(&building_with_referenced_faces(offset, 6));
+
+        assert!((metrics.volume.expect("should be present") - 400.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,1039 @@
+use crate::geometry::signed_volume;
+use ecitygml_core::model::building::{Building, BuildingConstructiveElement};
+use ecitygml_core::model::city_model::CitygmlModel;
+use ecitygml_core::model::construction::{DoorSurface, WindowSurface};
+use ecitygml_core::model::core::{OccupiedSpace, Space, ThematicSurface};
+use ecitygml_core::model::transportation::Road;
+use ecitygml_core::operations::{newell_vector, polygon_area, ring_vectors};
+use egml::model::base::{AbstractGml, Id};
+use egml::model::geometry::{
+    DirectPosition, LinearRing, MultiSurface, Polygon, Solid, SurfaceProperty,
+};
+use egml::operations::geometry::Geometry;
+use egml::operations::triangulate::Triangulate;
+use nalgebra::Vector3;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use tracing::info;
+
+/// Tolerances of the geometry repair in the unit of the coordinate reference system.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RepairOptions {
+    /// Maximum distance between vertices, which are snapped together.
+    pub snap_tolerance: f64,
+    /// Polygons with an area not larger than this are dropped.
+    pub area_tolerance: f64,
+    /// Maximum distance of a vertex to the plane of its polygon before the polygon is split.
+    pub planarity_tolerance: f64,
+}
+
+impl Default for RepairOptions {
+    fn default() -> Self {
+        Self {
+            snap_tolerance: 0.001,
+            area_tolerance: 1e-6,
+            planarity_tolerance: 0.01,
+        }
+    }
+}
+
+/// Repair operations applied to the geometries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RepairOperation {
+    SnapVertices,
+    RemoveDuplicateVertices,
+    CloseRing,
+    ReorientFaces,
+    DropDegeneratePolygon,
+    SplitNonPlanarPolygon,
+}
+
+impl fmt::Display for RepairOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RepairOperation::SnapVertices => "snap_vertices",
+            RepairOperation::RemoveDuplicateVertices => "remove_duplicate_vertices",
+            RepairOperation::CloseRing => "close_ring",
+            RepairOperation::ReorientFaces => "reorient_faces",
+            RepairOperation::DropDegeneratePolygon => "drop_degenerate_polygon",
+            RepairOperation::SplitNonPlanarPolygon => "split_non_planar_polygon",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Change applied to a geometry during the repair.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairChange {
+    /// gml:id of the city object the geometry belongs to.
+    pub feature_id: String,
+    /// gml:id of the changed ring, polygon or solid.
+    pub gml_id: String,
+    pub operation: RepairOperation,
+    pub message: String,
+}
+
+/// Repairs the geometries of all city objects in place and returns the applied changes.
+///
+/// The operations are applied in the following order:
+/// 1. vertices within the snap tolerance are snapped together per top-level feature
+/// 2. consecutive duplicate vertices are removed
+/// 3. rings, whose last vertex coincides with the first one, are closed by removing the
+///    repeated vertex, since rings are implicitly closed in the model and closed again when
+///    written
+/// 4. polygons with less than three distinct vertices or no area are dropped
+/// 5. non-planar polygons are split into triangles by triangulation
+/// 6. the faces of solids are reoriented consistently, so that they point outwards
+///
+/// Each change is logged with the gml:id of the geometry and of its city object. Implicit
+/// geometries are not changed, since their templates may be shared between features.
+pub fn repair(city_model: &mut CitygmlModel, options: &RepairOptions) -> Vec<RepairChange> {
+    let mut repairer = Repairer::new(*options);
+
+    for building in &mut city_model.building {
+        repairer.start_feature();
+        repairer.repair_building(building);
+    }
+    for city_furniture in &mut city_model.city_furniture {
+        repairer.start_feature();
+        repairer.repair_occupied_space(&mut city_furniture.occupied_space);
+    }
+    for road in &mut city_model.road {
+        repairer.start_feature();
+        repairer.repair_road(road);
+    }
+    for solitary_vegetation_object in &mut city_model.solitary_vegetation_object {
+        repairer.start_feature();
+        repairer.repair_occupied_space(&mut solitary_vegetation_object.occupied_space);
+    }
+
+    repairer.changes
+}
+
+struct Repairer {
+    options: RepairOptions,
+    snapper: VertexSnapper,
+    feature_id: String,
+    changes: Vec<RepairChange>,
+}
+
+impl Repairer {
+    fn new(options: RepairOptions) -> Self {
+        Self {
+            options,
+            snapper: VertexSnapper::new(options.snap_tolerance),
+            feature_id: String::new(),
+            changes: Vec::new(),
+        }
+    }
+
+    /// Vertices are only snapped within a top-level feature and its children.
+    fn start_feature(&mut self) {
+        self.snapper = VertexSnapper::new(self.options.snap_tolerance);
+    }
+
+    fn record(&mut self, gml_id: &Id, operation: RepairOperation, message: String) {
+        info!(
+            "Repaired `{gml_id}` of `{}` ({operation}): {message}",
+            self.feature_id
+        );
+        self.changes.push(RepairChange {
+            feature_id: self.feature_id.clone(),
+            gml_id: gml_id.to_string(),
+            operation,
+            message,
+        });
+    }
+
+    fn repair_building(&mut self, building: &mut Building) {
+        self.repair_occupied_space(&mut building.occupied_space);
+
+        for x in &mut building.wall_surface {
+            self.repair_thematic_surface(&mut x.thematic_surface);
+            self.repair_openings(&mut x.door_surface, &mut x.window_surface);
+        }
+        for x in &mut building.interior_wall_surface {
+            self.repair_thematic_surface(&mut x.thematic_surface);
+            self.repair_openings(&mut x.door_surface, &mut x.window_surface);
+        }
+        let thematic_surfaces = building
+            .roof_surface
+            .iter_mut()
+            .map(|x| &mut x.thematic_surface)
+            .chain(
+                building
+                    .ground_surface
+                    .iter_mut()
+                    .map(|x| &mut x.thematic_surface),
+            )
+            .chain(
+                building
+                    .ceiling_surface
+                    .iter_mut()
+                    .map(|x| &mut x.thematic_surface),
+            )
+            .chain(
+                building
+                    .floor_surface
+                    .iter_mut()
+                    .map(|x| &mut x.thematic_surface),
+            )
+            .chain(
+                building
+                    .outer_ceiling_surface
+                    .iter_mut()
+                    .map(|x| &mut x.thematic_surface),
+            )
+            .chain(
+                building
+                    .outer_floor_surface
+                    .iter_mut()
+                    .map(|x| &mut x.thematic_surface),
+            )
+            .chain(
+                building
+                    .closure_surface
+                    .iter_mut()
+                    .map(|x| &mut x.thematic_surface),
+            );
+        for thematic_surface in thematic_surfaces {
+            self.repair_thematic_surface(thematic_surface);
+        }
+
+        for x in &mut building.building_constructive_element {
+            self.repair_building_constructive_element(x);
+        }
+    }
+
+    fn repair_building_constructive_element(&mut self, element: &mut BuildingConstructiveElement) {
+        self.repair_occupied_space(&mut element.occupied_space);
+        for door in &mut element.door {
+            self.repair_occupied_space(&mut door.occupied_space);
+            self.repair_openings(&mut door.door_surface, &mut []);
+        }
+        for window in &mut element.window {
+            self.repair_occupied_space(&mut window.occupied_space);
+            self.repair_openings(&mut [], &mut window.window_surface);
+        }
+    }
+
+    fn repair_openings(
+        &mut self,
+        door_surface: &mut [DoorSurface],
+        window_surface: &mut [WindowSurface],
+    ) {
+        for x in door_surface {
+            self.repair_thematic_surface(&mut x.thematic_surface);
+        }
+        for x in window_surface {
+            self.repair_thematic_surface(&mut x.thematic_surface);
+        }
+    }
+
+    fn repair_road(&mut self, road: &mut Road) {
+        self.repair_space(&mut road.space);
+
+        let sections = road.section.iter_mut().map(|x| {
+            (
+                &mut x.space,
+                &mut x.traffic_space,
+                &mut x.auxiliary_traffic_space,
+            )
+        });
+        let intersections = road.intersection.iter_mut().map(|x| {
+            (
+                &mut x.space,
+                &mut x.traffic_space,
+                &mut x.auxiliary_traffic_space,
+            )
+        });
+        for (space, traffic_space, auxiliary_traffic_space) in sections.chain(intersections) {
+            self.repair_space(space);
+            for x in traffic_space {
+                self.repair_space(&mut x.space);
+                for traffic_area in &mut x.traffic_area {
+                    self.repair_thematic_surface(&mut traffic_area.thematic_surface);
+                }
+            }
+            for x in auxiliary_traffic_space {
+                self.repair_space(&mut x.space);
+                for auxiliary_traffic_area in &mut x.auxiliary_traffic_area {
+                    self.repair_thematic_surface(&mut auxiliary_traffic_area.thematic_surface);
+                }
+            }
+        }
+    }
+
+    fn repair_occupied_space(&mut self, occupied_space: &mut OccupiedSpace) {
+        self.repair_space(&mut occupied_space.space);
+    }
+
+    fn repair_space(&mut self, space: &mut Space) {
+        self.feature_id = space.city_object.abstract_gml.id.to_string();
+
+        for solid in [
+            &mut space.lod1_solid,
+            &mut space.lod2_solid,
+            &mut space.lod3_solid,
+        ] {
+            self.repair_solid(solid);
+        }
+        for multi_surface in [
+            &mut space.lod0_multi_surface,
+            &mut space.lod2_multi_surface,
+            &mut space.lod3_multi_surface,
+        ] {
+            self.repair_multi_surface(multi_surface);
+        }
+    }
+
+    fn repair_thematic_surface(&mut self, thematic_surface: &mut ThematicSurface) {
+        self.feature_id = thematic_surface.city_object.abstract_gml.id.to_string();
+
+        for multi_surface in [
+            &mut thematic_surface.lod0_multi_surface,
+            &mut thematic_surface.lod1_multi_surface,
+            &mut thematic_surface.lod2_multi_surface,
+            &mut thematic_surface.lod3_multi_surface,
+        ] {
+            self.repair_multi_surface(multi_surface);
+        }
+    }
+
+    /// Repairs the polygons of the multi surface, which is removed if no polygon remains.
+    fn repair_multi_surface(&mut self, multi_surface: &mut Option<MultiSurface>) {
+        let Some(current_multi_surface) = multi_surface else {
+            return;
+        };
+
+        let polygons: Vec<Polygon> = current_multi_surface
+            .surface_member()
+            .clone()
+            .into_iter()
+            .flat_map(|x| self.repair_polygon(x))
+            .collect();
+        if current_multi_surface.set_surface_member(polygons).is_err() {
+            *multi_surface = None;
+        }
+    }
+
+    /// Returns the repaired polygon, which is empty if the polygon is degenerate, or the
+    /// triangles of a non-planar polygon.
+    fn repair_polygon(&mut self, mut polygon: Polygon) -> Vec<Polygon> {
+        let polygon_id = polygon.abstract_gml.id.clone();
+        if !self.repair_ring(&mut polygon.exterior) {
+            self.record(
+                &polygon_id,
+                RepairOperation::DropDegeneratePolygon,
+                "exterior ring has less than three distinct vertices".to_string(),
+            );
+            return Vec::new();
+        }
+
+        let mut interior = Vec::with_capacity(polygon.interior.len());
+        for mut ring in std::mem::take(&mut polygon.interior) {
+            if self.repair_ring(&mut ring) {
+                interior.push(ring);
+            } else {
+                self.record(
+                    &ring.abstract_gml.id,
+                    RepairOperation::DropDegeneratePolygon,
+                    format!(
+                        "interior ring of polygon `{polygon_id}` has less than three distinct vertices"
+                    ),
+                );
+            }
+        }
+        polygon.interior = interior;
+
+        let area = polygon_area(&polygon);
+        if area <= self.options.area_tolerance {
+            self.record(
+                &polygon_id,
+                RepairOperation::DropDegeneratePolygon,
+                format!("polygon has an area of {area:.6}"),
+            );
+            return Vec::new();
+        }
+
+        if let Some(distance) = planarity_distance(&polygon)
+            && distance > self.options.planarity_tolerance
+            && let Some(triangles) = self.triangulate_polygon(&polygon)
+        {
+            self.record(
+                &polygon_id,
+                RepairOperation::SplitNonPlanarPolygon,
+                format!(
+                    "vertex is {distance:.3} off the plane, split into {} triangles",
+                    triangles.len()
+                ),
+            );
+            return triangles;
+        }
+
+        vec![polygon]
+    }
+
+    /// Triangulates the polygon into polygons with the gml:ids `{polygon_id}_{index}`.
+    ///
+    /// Returns `None` if the polygon cannot be triangulated, for example due to a ring
+    /// touching itself.
+    fn triangulate_polygon(&mut self, polygon: &Polygon) -> Option<Vec<Polygon>> {
+        let points = polygon.points();
+        let distinct_points: HashSet<VertexKey> = points.iter().map(|x| vertex_key(x)).collect();
+        if distinct_points.len() != points.len() {
+            return None;
+        }
+
+        let triangulated_surface = polygon.triangulate().ok()?;
+        triangulated_surface
+            .patches()
+            .iter()
+            .enumerate()
+            .map(|(index, triangle)| {
+                let exterior_id =
+                    Id::try_from(format!("{}_{index}", polygon.exterior.abstract_gml.id)).ok()?;
+                let polygon_id =
+                    Id::try_from(format!("{}_{index}", polygon.abstract_gml.id)).ok()?;
+                let exterior = LinearRing::new(
+                    AbstractGml::new(exterior_id),
+                    vec![triangle.a, triangle.b, triangle.c],
+                )
+                .ok()?;
+                Polygon::new(AbstractGml::new(polygon_id), exterior, Vec::new()).ok()
+            })
+            .collect()
+    }
+
+    /// Snaps the vertices of the ring and removes duplicate and closing vertices.
+    ///
+    /// Returns `false` without changing the ring, if less than three distinct vertices remain.
+    fn repair_ring(&mut self, ring: &mut LinearRing) -> bool {
+        let ring_id = ring.abstract_gml.id.clone();
+        let original_points: Vec<DirectPosition> = ring.points().into_iter().copied().collect();
+
+        let mut points: Vec<DirectPosition> = original_points
+            .iter()
+            .map(|x| self.snapper.snap(*x))
+            .collect();
+        let number_of_snapped = points
+            .iter()
+            .zip(&original_points)
+            .filter(|(a, b)| a != b)
+            .count();
+
+        let number_of_points = points.len();
+        points.dedup();
+        let number_of_duplicates = number_of_points - points.len();
+
+        let is_closed = points.len() > 1 && points.first() == points.last();
+        if is_closed {
+            points.pop();
+        }
+
+        if points.len() < 3 {
+            return false;
+        }
+        if points == original_points {
+            return true;
+        }
+
+        if number_of_snapped > 0 {
+            self.record(
+                &ring_id,
+                RepairOperation::SnapVertices,
+                format!("snapped {number_of_snapped} vertices"),
+            );
+        }
+        if number_of_duplicates > 0 {
+            self.record(
+                &ring_id,
+                RepairOperation::RemoveDuplicateVertices,
+                format!("removed {number_of_duplicates} duplicate vertices"),
+            );
+        }
+        if is_closed {
+            self.record(
+                &ring_id,
+                RepairOperation::CloseRing,
+                "removed last vertex repeating the first vertex".to_string(),
+            );
+        }
+        ring.set_points(points)
+            .expect("ring should have three distinct vertices");
+        true
+    }
+
+    /// Repairs the faces of the solid and orients them consistently outwards.
+    ///
+    /// Faces referenced by href are kept unchanged. The solid is removed if no face remains.
+    fn repair_solid(&mut self, solid: &mut Option<Solid>) {
+        let Some(current_solid) = solid else {
+            return;
+        };
+        let solid_id = current_solid.abstract_gml.id.clone();
+
+        let mut members = Vec::with_capacity(current_solid.members().len());
+        for member in current_solid.members().clone() {
+            let Some(ring) = member.linear_ring else {
+                members.push(member);
+                continue;
+            };
+
+            let polygon = Polygon {
+                abstract_gml: ring.abstract_gml.clone(),
+                exterior: ring,
+                interior: Vec::new(),
+            };
+            members.extend(
+                self.repair_polygon(polygon)
+                    .into_iter()
+                    .map(|x| SurfaceProperty {
+                        href: member.href.clone(),
+                        linear_ring: Some(x.exterior),
+                    }),
+            );
+        }
+
+        let number_of_reversed_faces = orient_faces(&mut members);
+        if number_of_reversed_faces > 0 {
+            self.record(
+                &solid_id,
+                RepairOperation::ReorientFaces,
+                format!("reversed the orientation of {number_of_reversed_faces} faces"),
+            );
+        }
+
+        if current_solid.set_members(members).is_err() {
+            *solid = None;
+        }
+    }
+}
+
+/// Snaps vertices to the first vertex within the tolerance, which are looked up in a grid with
+/// the cell size of the tolerance.
+struct VertexSnapper {
+    tolerance: f64,
+    cells: HashMap<[i64; 3], Vec<DirectPosition>>,
+}
+
+impl VertexSnapper {
+    fn new(tolerance: f64) -> Self {
+        Self {
+            tolerance,
+            cells: HashMap::new(),
+        }
+    }
+
+    fn snap(&mut self, point: DirectPosition) -> DirectPosition {
+        if self.tolerance <= 0.0 {
+            return point;
+        }
+
+        let coords = point.coords();
+        let cell = coords.map(|x| (x / self.tolerance).floor() as i64);
+        let position = Vector3::from(point);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let neighbor = [cell[0] + dx, cell[1] + dy, cell[2] + dz];
+                    if let Some(snapped) = self.cells.get(&neighbor).and_then(|x| {
+                        x.iter()
+                            .find(|x| (Vector3::from(*x) - position).norm() <= self.tolerance)
+                    }) {
+                        return *snapped;
+                    }
+                }
+            }
+        }
+
+        self.cells.entry(cell).or_default().push(point);
+        point
+    }
+}
+
+/// Bit patterns of the coordinates, which identify vertices after snapping.
+type VertexKey = [u64; 3];
+
+fn vertex_key(point: &DirectPosition) -> VertexKey {
+    point.coords().map(f64::to_bits)
+}
+
+/// Returns the largest distance of a vertex to the plane through the centroid of the exterior
+/// ring.
+fn planarity_distance(polygon: &Polygon) -> Option<f64> {
+    let exterior = ring_vectors(&polygon.exterior);
+    let normal = newell_vector(&exterior).try_normalize(f64::EPSILON)?;
+    let centroid = exterior.iter().sum::<Vector3<f64>>() / exterior.len() as f64;
+
+    polygon
+        .points()
+        .into_iter()
+        .map(|x| (Vector3::from(x) - centroid).dot(&normal).abs())
+        .reduce(f64::max)
+}
+
+/// Orients the faces consistently by propagating the orientation over shared edges, which
+/// must be traversed in opposite directions by adjacent faces.
+///
+/// Closed shells are oriented outwards, so that their signed volume is positive. Edges shared
+/// by more than two faces are ignored. Returns the number of reversed faces.
+fn orient_faces(members: &mut [SurfaceProperty]) -> usize {
+    let faces: Vec<Vec<DirectPosition>> = members
+        .iter()
+        .map(|x| x.points().into_iter().copied().collect())
+        .collect();
+
+    let mut edges: HashMap<(VertexKey, VertexKey), Vec<(usize, bool)>> = HashMap::new();
+    for (face_index, face) in faces.iter().enumerate() {
+        for (a, b) in face.iter().zip(face.iter().cycle().skip(1)) {
+            let (a, b) = (vertex_key(a), vertex_key(b));
+            let (edge, is_forward) = if a < b {
+                ((a, b), true)
+            } else {
+                ((b, a), false)
+            };
+            edges
+                .entry(edge)
+                .or_default()
+                .push((face_index, is_forward));
+        }
+    }
+    let mut neighbors: Vec<Vec<(usize, bool)>> = vec![Vec::new(); faces.len()];
+    let mut open_edge_faces: HashSet<usize> = HashSet::new();
+    for uses in edges.values() {
+        match uses.as_slice() {
+            [(a, a_forward), (b, b_forward)] if a != b => {
+                // the faces are consistent, if they traverse the edge in opposite directions
+                neighbors[*a].push((*b, a_forward == b_forward));
+                neighbors[*b].push((*a, a_forward == b_forward));
+            }
+            _ => open_edge_faces.extend(uses.iter().map(|x| x.0)),
+        }
+    }
+
+    let mut is_reversed: Vec<Option<bool>> = vec![None; faces.len()];
+    for root in 0..faces.len() {
+        if is_reversed[root].is_some() || faces[root].is_empty() {
+            continue;
+        }
+
+        is_reversed[root] = Some(false);
+        let mut component = vec![root];
+        let mut queue = VecDeque::from([root]);
+        while let Some(face_index) = queue.pop_front() {
+            let reversed = is_reversed[face_index].expect("face should be oriented");
+            for (neighbor, needs_flip) in &neighbors[face_index] {
+                if is_reversed[*neighbor].is_none() {
+                    is_reversed[*neighbor] = Some(reversed != *needs_flip);
+                    component.push(*neighbor);
+                    queue.push_back(*neighbor);
+                }
+            }
+        }
+
+        let is_closed = component.iter().all(|x| !open_edge_faces.contains(x));
+        let reference = Vector3::from(&faces[root][0]);
+        let signed_volume: f64 = component
+            .iter()
+            .map(|x| {
+                let volume = signed_volume(&faces[*x], &reference);
+                if is_reversed[*x] == Some(true) {
+                    -volume
+                } else {
+                    volume
+                }
+            })
+            .sum();
+        if is_closed && signed_volume < 0.0 {
+            for face_index in &component {
+                is_reversed[*face_index] = is_reversed[*face_index].map(|x| !x);
+            }
+        }
+    }
+
+    let mut number_of_reversed_faces = 0;
+    for ((member, mut face), reversed) in members.iter_mut().zip(faces).zip(is_reversed) {
+        if let Some(ring) = &mut member.linear_ring
+            && reversed == Some(true)
+        {
+            face.reverse();
+            ring.set_points(face)
+                .expect("reversed ring should remain valid");
+            number_of_reversed_faces += 1;
+        }
+    }
+    number_of_reversed_faces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ecitygml_core::model::city_furniture::CityFurniture;
+    use ecitygml_core::model::core::CityObject;
+
+    fn position(coords: [f64; 3]) -> DirectPosition {
+        DirectPosition::new(coords[0], coords[1], coords[2]).expect("should work")
+    }
+
+    fn ring(id: &str, coords: &[[f64; 3]]) -> LinearRing {
+        LinearRing::new(
+            AbstractGml::new(Id::try_from(id).expect("should work")),
+            coords.iter().copied().map(position).collect(),
+        )
+        .expect("should work")
+    }
+
+    fn polygon(id: &str, coords: &[[f64; 3]]) -> Polygon {
+        Polygon::new(
+            AbstractGml::new(Id::try_from(id).expect("should work")),
+            ring(&format!("{id}_exterior"), coords),
+            Vec::new(),
+        )
+        .expect("should work")
+    }
+
+    fn city_model_with_space(space: Space) -> CitygmlModel {
+        let city_furniture = CityFurniture::new(OccupiedSpace::new(space));
+        CitygmlModel::new(Vec::new(), vec![city_furniture], Vec::new(), Vec::new())
+    }
+
+    fn city_model_with_multi_surface(polygons: Vec<Polygon>) -> CitygmlModel {
+        let mut space = Space::new(CityObject::new(
+            AbstractGml::new(Id::try_from("furniture").expect("should work")),
+            Vec::new(),
+        ));
+        space.lod2_multi_surface = Some(
+            MultiSurface::new(
+                AbstractGml::new(Id::try_from("multi_surface").expect("should work")),
+                polygons,
+            )
+            .expect("should work"),
+        );
+        city_model_with_space(space)
+    }
+
+    fn lod2_polygons(city_model: &CitygmlModel) -> &[Polygon] {
+        city_model.city_furniture[0]
+            .occupied_space
+            .space
+            .lod2_multi_surface
+            .as_ref()
+            .map(|x| x.surface_member().as_slice())
+            .unwrap_or_default()
+    }
+
+    fn change(gml_id: &str, operation: RepairOperation, message: &str) -> RepairChange {
+        RepairChange {
+            feature_id: "furniture".to_string(),
+            gml_id: gml_id.to_string(),
+            operation,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_repair_snaps_vertices_of_adjacent_polygons() {
+        let mut city_model = city_model_with_multi_surface(vec![
+            polygon(
+                "left",
+                &[
+                    [0.0, 0.0, 0.0],
+                    [1.0, 0.0, 0.0],
+                    [1.0, 1.0, 0.0],
+                    [0.0, 1.0, 0.0],
+                ],
+            ),
+            polygon(
+                "right",
+                &[
+                    [1.0004, 0.0, 0.0],
+                    [2.0, 0.0, 0.0],
+                    [2.0, 1.0, 0.0],
+                    [1.0, 1.0003, 0.0],
+                ],
+            ),
+        ]);
+
+        let changes = repair(&mut city_model, &RepairOptions::default());
+
+        assert_eq!(
+            changes,
+            vec![change(
+                "right_exterior",
+                RepairOperation::SnapVertices,
+                "snapped 2 vertices"
+            )]
+        );
+        let right_points = lod2_polygons(&city_model)[1].points();
+        assert_eq!(*right_points[0], position([1.0, 0.0, 0.0]));
+        assert_eq!(*right_points[3], position([1.0, 1.0, 0.0]));
+    }
+
+    #[test]
+    fn test_repair_removes_duplicate_and_closing_vertices() {
+        let mut city_model = city_model_with_multi_surface(vec![polygon(
+            "square",
+            &[
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0002, 0.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 0.0003, 0.0],
+            ],
+        )]);
+
+        let changes = repair(&mut city_model, &RepairOptions::default());
+
+        assert_eq!(
+            changes,
+            vec![
+                change(
+                    "square_exterior",
+                    RepairOperation::SnapVertices,
+                    "snapped 2 vertices"
+                ),
+                change(
+                    "square_exterior",
+                    RepairOperation::RemoveDuplicateVertices,
+                    "removed 1 duplicate vertices"
+                ),
+                change(
+                    "square_exterior",
+                    RepairOperation::CloseRing,
+                    "removed last vertex repeating the first vertex"
+                ),
+            ]
+        );
+        assert_eq!(lod2_polygons(&city_model)[0].points().len(), 4);
+    }
+
+    #[test]
+    fn test_repair_drops_collapsed_ring() {
+        let mut city_model = city_model_with_multi_surface(vec![
+            polygon(
+                "square",
+                &[
+                    [0.0, 0.0, 0.0],
+                    [1.0, 0.0, 0.0],
+                    [1.0, 1.0, 0.0],
+                    [0.0, 1.0, 0.0],
+                ],
+            ),
+            polygon(
+                "sliver",
+                &[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0004, 0.0, 0.0]],
+            ),
+        ]);
+
+        let changes = repair(&mut city_model, &RepairOptions::default());
+
+        assert_eq!(
+            changes,
+            vec![change(
+                "sliver",
+                RepairOperation::DropDegeneratePolygon,
+                "exterior ring has less than three distinct vertices"
+            )]
+        );
+        let polygons = lod2_polygons(&city_model);
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0].abstract_gml.id.to_string(), "square");
+    }
+
+    #[test]
+    fn test_repair_drops_multi_surface_without_remaining_polygons() {
+        let mut city_model = city_model_with_multi_surface(vec![polygon(
+            "line",
+            &[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [2.0, 0.0, 0.0]],
+        )]);
+
+        let changes = repair(&mut city_model, &RepairOptions::default());
+
+        assert_eq!(
+            changes,
+            vec![change(
+                "line",
+                RepairOperation::DropDegeneratePolygon,
+                "polygon has an area of 0.000000"
+            )]
+        );
+        assert!(
+            city_model.city_furniture[0]
+                .occupied_space
+                .space
+                .lod2_multi_surface
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_repair_splits_non_planar_quad() {
+        let mut city_model = city_model_with_multi_surface(vec![polygon(
+            "quad",
+            &[
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 1.0, 0.5],
+                [0.0, 1.0, 0.0],
+            ],
+        )]);
+
+        let changes = repair(&mut city_model, &RepairOptions::default());
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].gml_id, "quad");
+        assert_eq!(changes[0].operation, RepairOperation::SplitNonPlanarPolygon);
+        assert!(changes[0].message.ends_with("split into 2 triangles"));
+        let polygons = lod2_polygons(&city_model);
+        assert_eq!(polygons.len(), 2);
+        assert_eq!(polygons[0].abstract_gml.id.to_string(), "quad_0");
+        assert_eq!(polygons[1].abstract_gml.id.to_string(), "quad_1");
+        assert!(polygons.iter().all(|x| x.points().len() == 3));
+    }
+
+    /// Faces of a unit cube with outward normals.
+    const CUBE_FACES: [(&str, [[f64; 3]; 4]); 6] = [
+        (
+            "bottom",
+            [
+                [0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [1.0, 0.0, 0.0],
+            ],
+        ),
+        (
+            "top",
+            [
+                [0.0, 0.0, 1.0],
+                [1.0, 0.0, 1.0],
+                [1.0, 1.0, 1.0],
+                [0.0, 1.0, 1.0],
+            ],
+        ),
+        (
+            "front",
+            [
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 0.0, 1.0],
+                [0.0, 0.0, 1.0],
+            ],
+        ),
+        (
+            "back",
+            [
+                [0.0, 1.0, 0.0],
+                [0.0, 1.0, 1.0],
+                [1.0, 1.0, 1.0],
+                [1.0, 1.0, 0.0],
+            ],
+        ),
+        (
+            "left",
+            [
+                [0.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0],
+                [0.0, 1.0, 1.0],
+                [0.0, 1.0, 0.0],
+            ],
+        ),
+        (
+            "right",
+            [
+                [1.0, 0.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [1.0, 1.0, 1.0],
+                [1.0, 0.0, 1.0],
+            ],
+        ),
+    ];
+
+    /// Returns city furniture with the cube as LOD2 solid, whose coordinates are scaled and
+    /// offset, with the given faces flipped inwards.
+    fn city_model_with_cube(
+        scale: [f64; 3],
+        offset: [f64; 3],
+        flipped_faces: &[&str],
+    ) -> CitygmlModel {
+        let members = CUBE_FACES
+            .into_iter()
+            .map(|(id, coords)| {
+                let mut coords = coords.map(|x| {
+                    [
+                        x[0] * scale[0] + offset[0],
+                        x[1] * scale[1] + offset[1],
+                        x[2] * scale[2] + offset[2],
+                    ]
+                });
+                if flipped_faces.contains(&id) {
+                    coords.reverse();
+                }
+                SurfaceProperty::new(String::new(), Some(ring(id, &coords))).expect("should work")
+            })
+            .collect();
+        let mut space = Space::new(CityObject::new(
+            AbstractGml::new(Id::try_from("furniture").expect("should work")),
+            Vec::new(),
+        ));
+        space.lod2_solid = Some(
+            Solid::new(
+                AbstractGml::new(Id::try_from("solid").expect("should work")),
+                members,
+            )
+            .expect("should work"),
+        );
+        city_model_with_space(space)
+    }
+
+    fn lod2_solid(city_model: &CitygmlModel) -> &Solid {
+        city_model.city_furniture[0]
+            .occupied_space
+            .space
+            .lod2_solid
+            .as_ref()
+            .expect("should be kept")
+    }
+
+    #[test]
+    fn test_repair_reorients_flipped_faces_of_cube_outwards() {
+        let mut city_model = city_model_with_cube([1.0; 3], [0.0; 3], &["bottom", "top"]);
+
+        let changes = repair(&mut city_model, &RepairOptions::default());
+
+        assert_eq!(
+            changes,
+            vec![change(
+                "solid",
+                RepairOperation::ReorientFaces,
+                "reversed the orientation of 2 faces"
+            )]
+        );
+        let signed_volume: f64 = lod2_solid(&city_model)
+            .members()
+            .iter()
+            .map(|x| {
+                signed_volume(
+                    &x.points().into_iter().copied().collect::<Vec<_>>(),
+                    &Vector3::zeros(),
+                )
+            })
+            .sum();
+        assert!((signed_volume - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_repair_keeps_outward_cube_at_projected_coordinates() {
+        // a 1 × 1 × 0.5 box at coordinates of a projected CRS with zone prefix
+        let mut city_model =
+            city_model_with_cube([1.0, 1.0, 0.5], [32690123.457, 5336789.013, 512.3], &[]);
+        let solid = lod2_solid(&city_model).clone();
+
+        let changes = repair(&mut city_model, &RepairOptions::default());
+
+        assert_eq!(changes, Vec::new());
+        assert_eq!(lod2_solid(&city_model), &solid);
+    }
+}
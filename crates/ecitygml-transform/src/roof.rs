@@ -0,0 +1,301 @@
+use crate::geometry::{polygon_centroid, slope_and_azimuth};
+use crate::metrics::FLAT_ROOF_MAXIMUM_SLOPE;
+use ecitygml_core::model::building::Building;
+use ecitygml_core::model::city_model::CitygmlModel;
+use ecitygml_core::operations::{polygon_area, polygon_normal};
+use egml::model::base::Id;
+use egml::model::geometry::{DirectPosition, MultiSurface, Polygon};
+use std::collections::HashMap;
+
+/// Thresholds deciding which roof polygons are usable, for example for solar panels.
+///
+/// Angles are in degrees, the azimuth clockwise from north.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoofAnalysisOptions {
+    /// Maximum slope of usable roof polygons.
+    pub maximum_slope: f64,
+    /// Roof polygons with a slope of at most this are usable regardless of their azimuth.
+    pub flat_roof_maximum_slope: f64,
+    /// Start of the usable azimuth range.
+    pub minimum_azimuth: f64,
+    /// End of the usable azimuth range, which wraps around north if it is smaller than the
+    /// start.
+    pub maximum_azimuth: f64,
+    /// Minimum planar area of usable roof polygons in the unit of the coordinate reference
+    /// system.
+    pub minimum_area: f64,
+}
+
+impl Default for RoofAnalysisOptions {
+    fn default() -> Self {
+        Self {
+            maximum_slope: 60.0,
+            flat_roof_maximum_slope: FLAT_ROOF_MAXIMUM_SLOPE,
+            minimum_azimuth: 90.0,
+            maximum_azimuth: 270.0,
+            minimum_area: 1.0,
+        }
+    }
+}
+
+impl RoofAnalysisOptions {
+    fn is_usable(&self, slope: f64, azimuth: Option<f64>, area: f64) -> bool {
+        if area < self.minimum_area || slope > self.maximum_slope {
+            return false;
+        }
+        if slope <= self.flat_roof_maximum_slope {
+            return true;
+        }
+
+        azimuth.is_some_and(|x| {
+            if self.minimum_azimuth <= self.maximum_azimuth {
+                (self.minimum_azimuth..=self.maximum_azimuth).contains(&x)
+            } else {
+                x >= self.minimum_azimuth || x <= self.maximum_azimuth
+            }
+        })
+    }
+}
+
+/// Geometric properties of a polygon of a `RoofSurface`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoofPolygonAnalysis {
+    /// gml:id of the polygon.
+    pub gml_id: Id,
+    /// gml:id of the `RoofSurface` the polygon belongs to.
+    pub roof_surface_id: Id,
+    /// Angle between the polygon and the horizontal plane in degrees.
+    pub slope: f64,
+    /// Direction the polygon faces in degrees clockwise from north, which is `None` for
+    /// horizontal polygons.
+    ///
+    /// The x-axis of the coordinate reference system is assumed to point east and the y-axis
+    /// north.
+    pub azimuth: Option<f64>,
+    /// Area of the polygon in its plane.
+    pub area: f64,
+    /// Area-weighted centroid of the polygon.
+    pub centroid: DirectPosition,
+    pub is_usable: bool,
+}
+
+/// Roof polygons of a building with the aggregated area of the usable polygons.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildingRoofAnalysis {
+    pub roof_polygons: Vec<RoofPolygonAnalysis>,
+    pub usable_roof_area: f64,
+}
+
+impl BuildingRoofAnalysis {
+    /// Analyzes the polygons of the `lod2MultiSurface` of the roof surfaces of the building.
+    ///
+    /// Degenerate polygons without a normal vector are skipped.
+    pub fn from_building(building: &Building, options: &RoofAnalysisOptions) -> Self {
+        let roof_polygons: Vec<RoofPolygonAnalysis> = building
+            .roof_surface
+            .iter()
+            .flat_map(|roof_surface| {
+                let thematic_surface = &roof_surface.thematic_surface;
+                thematic_surface
+                    .lod2_multi_surface
+                    .iter()
+                    .flat_map(MultiSurface::surface_member)
+                    .filter_map(|x| {
+                        analyze_polygon(x, &thematic_surface.city_object.abstract_gml.id, options)
+                    })
+            })
+            .collect();
+        let usable_roof_area = roof_polygons
+            .iter()
+            .filter(|x| x.is_usable)
+            .map(|x| x.area)
+            .sum();
+
+        Self {
+            roof_polygons,
+            usable_roof_area,
+        }
+    }
+}
+
+/// Analyzes the roofs of all buildings keyed by the gml:id of the building.
+///
+/// Buildings without LOD2 roof surfaces are not included.
+pub fn analyze_roofs(
+    city_model: &CitygmlModel,
+    options: &RoofAnalysisOptions,
+) -> HashMap<Id, BuildingRoofAnalysis> {
+    city_model
+        .building
+        .iter()
+        .map(|x| {
+            (
+                x.occupied_space.space.city_object.abstract_gml.id.clone(),
+                BuildingRoofAnalysis::from_building(x, options),
+            )
+        })
+        .filter(|(_, x)| !x.roof_polygons.is_empty())
+        .collect()
+}
+
+fn analyze_polygon(
+    polygon: &Polygon,
+    roof_surface_id: &Id,
+    options: &RoofAnalysisOptions,
+) -> Option<RoofPolygonAnalysis> {
+    let normal = polygon_normal(polygon)?;
+    let centroid = polygon_centroid(polygon)?;
+    let (slope, azimuth) = slope_and_azimuth(&normal);
+    let area = polygon_area(polygon);
+
+    Some(RoofPolygonAnalysis {
+        gml_id: polygon.abstract_gml.id.clone(),
+        roof_surface_id: roof_surface_id.clone(),
+        slope,
+        azimuth,
+        area,
+        centroid: DirectPosition::from(centroid),
+        is_usable: options.is_usable(slope, azimuth, area),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ecitygml_core::model::construction::RoofSurface;
+    use ecitygml_core::model::core::{CityObject, OccupiedSpace, Space, ThematicSurface};
+    use egml::model::base::AbstractGml;
+    use egml::model::geometry::LinearRing;
+
+    fn abstract_gml(id: &str) -> AbstractGml {
+        AbstractGml::new(Id::try_from(id).expect("should work"))
+    }
+
+    fn polygon(id: &str, coords: &[[f64; 3]]) -> Polygon {
+        let exterior = LinearRing::new(
+            abstract_gml(&format!("{id}_exterior")),
+            coords
+                .iter()
+                .map(|x| DirectPosition::new(x[0], x[1], x[2]).expect("should work"))
+                .collect(),
+        )
+        .expect("should work");
+        Polygon::new(abstract_gml(id), exterior, Vec::new()).expect("should work")
+    }
+
+    /// Returns a building with a 45° roof polygon facing south, one facing north and a flat
+    /// roof polygon, each of 4 × 4 in the xy-plane.
+    fn city_model() -> CitygmlModel {
+        let mut thematic_surface =
+            ThematicSurface::new(CityObject::new(abstract_gml("roof"), Vec::new()));
+        thematic_surface.lod2_multi_surface = Some(
+            MultiSurface::new(
+                abstract_gml("roof_geometry"),
+                vec![
+                    polygon(
+                        "roof_south",
+                        &[
+                            [0.0, 0.0, 10.0],
+                            [4.0, 0.0, 10.0],
+                            [4.0, 4.0, 14.0],
+                            [0.0, 4.0, 14.0],
+                        ],
+                    ),
+                    polygon(
+                        "roof_north",
+                        &[
+                            [4.0, 8.0, 10.0],
+                            [0.0, 8.0, 10.0],
+                            [0.0, 4.0, 14.0],
+                            [4.0, 4.0, 14.0],
+                        ],
+                    ),
+                    polygon(
+                        "roof_flat",
+                        &[
+                            [4.0, 0.0, 10.0],
+                            [8.0, 0.0, 10.0],
+                            [8.0, 4.0, 10.0],
+                            [4.0, 4.0, 10.0],
+                        ],
+                    ),
+                ],
+            )
+            .expect("should work"),
+        );
+        let mut building = Building::new(OccupiedSpace::new(Space::new(CityObject::new(
+            abstract_gml("building"),
+            Vec::new(),
+        ))));
+        building.roof_surface = vec![RoofSurface::new(thematic_surface)];
+
+        CitygmlModel::new(vec![building], Vec::new(), Vec::new(), Vec::new())
+    }
+
+    fn roof_polygon<'a>(
+        analysis: &'a HashMap<Id, BuildingRoofAnalysis>,
+        gml_id: &str,
+    ) -> &'a RoofPolygonAnalysis {
+        analysis[&Id::try_from("building").expect("should work")]
+            .roof_polygons
+            .iter()
+            .find(|x| x.gml_id.to_string() == gml_id)
+            .expect("should be analyzed")
+    }
+
+    #[test]
+    fn test_analyze_roofs_slope_azimuth_and_centroid() {
+        let analysis = analyze_roofs(&city_model(), &RoofAnalysisOptions::default());
+
+        let south = roof_polygon(&analysis, "roof_south");
+        assert_eq!(south.roof_surface_id.to_string(), "roof");
+        assert!((south.slope - 45.0).abs() < 1e-9);
+        assert!((south.azimuth.expect("should face a direction") - 180.0).abs() < 1e-9);
+        assert!((south.area - 16.0 * 2.0_f64.sqrt()).abs() < 1e-9);
+        let centroid = south.centroid.coords();
+        assert!((centroid[0] - 2.0).abs() < 1e-9);
+        assert!((centroid[1] - 2.0).abs() < 1e-9);
+        assert!((centroid[2] - 12.0).abs() < 1e-9);
+        assert!(south.is_usable);
+
+        let north = roof_polygon(&analysis, "roof_north");
+        assert!((north.azimuth.expect("should face a direction") - 0.0).abs() < 1e-9);
+        assert!(!north.is_usable);
+
+        let flat = roof_polygon(&analysis, "roof_flat");
+        assert_eq!(flat.slope, 0.0);
+        assert_eq!(flat.azimuth, None);
+        assert!(flat.is_usable);
+
+        let usable_roof_area =
+            analysis[&Id::try_from("building").expect("should work")].usable_roof_area;
+        assert!((usable_roof_area - 16.0 * 2.0_f64.sqrt() - 16.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_analyze_roofs_with_azimuth_range_wrapping_north() {
+        let options = RoofAnalysisOptions {
+            minimum_azimuth: 300.0,
+            maximum_azimuth: 60.0,
+            ..Default::default()
+        };
+
+        let analysis = analyze_roofs(&city_model(), &options);
+
+        assert!(roof_polygon(&analysis, "roof_north").is_usable);
+        assert!(!roof_polygon(&analysis, "roof_south").is_usable);
+        assert!(roof_polygon(&analysis, "roof_flat").is_usable);
+    }
+
+    #[test]
+    fn test_is_usable_thresholds() {
+        let options = RoofAnalysisOptions::default();
+
+        assert!(options.is_usable(30.0, Some(90.0), 10.0));
+        assert!(options.is_usable(30.0, Some(270.0), 10.0));
+        assert!(!options.is_usable(30.0, Some(89.0), 10.0));
+        assert!(!options.is_usable(61.0, Some(180.0), 10.0));
+        assert!(!options.is_usable(30.0, Some(180.0), 0.5));
+        assert!(options.is_usable(FLAT_ROOF_MAXIMUM_SLOPE, Some(0.0), 10.0));
+    }
+}